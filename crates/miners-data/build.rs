@@ -0,0 +1,232 @@
+//! Generates block state, block and item tables from vanilla data generator reports
+//!
+//! Reports are read from `reports/<version>` (or `$MINERS_DATA_REPORTS/<version>`).
+//! Versions without reports get empty tables, so the crate always builds.
+//! Properties which reports of older versions don't contain are read from `data/<version>`.
+
+use std::{env, fmt::Write, fs, path::{Path, PathBuf}};
+
+use serde_json::Value;
+
+/// Supported versions (name of the reports directory and name of the generated module)
+const VERSIONS: &[(&str, &str)] = &[
+    ("1.19.2", "v1_19_2"),
+];
+
+fn main() {
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let reports_dir = env::var_os("MINERS_DATA_REPORTS")
+        .map(PathBuf::from)
+        .unwrap_or_else(|| manifest_dir.join("reports"));
+    println!("cargo:rerun-if-env-changed=MINERS_DATA_REPORTS");
+    println!("cargo:rerun-if-changed={}", reports_dir.display());
+
+    let mut code = String::new();
+    for (version, module) in VERSIONS {
+        let dir = reports_dir.join(version);
+        let data_dir = manifest_dir.join("data").join(version);
+        println!("cargo:rerun-if-changed={}", dir.display());
+        println!("cargo:rerun-if-changed={}", data_dir.display());
+        // Tests which need the tables are only run if the reports were found
+        println!("cargo:rustc-check-cfg=cfg(reports_{})", module);
+        let tables = match Tables::load(&dir, &data_dir) {
+            Ok(tables) => {
+                println!("cargo:rustc-cfg=reports_{}", module);
+                tables
+            },
+            Err(e) => {
+                println!("cargo:warning=No data reports for {} ({}), its tables will be empty", version, e);
+                Tables::default()
+            }
+        };
+        tables.write(&mut code, module);
+    }
+
+    let out = PathBuf::from(env::var("OUT_DIR").unwrap()).join("data.rs");
+    fs::write(out, code).unwrap();
+}
+
+struct Block {
+    id: i32,
+    name: String,
+    /// Properties with their possible values
+    properties: Vec<(String, Vec<String>)>,
+    min_state: i32,
+    max_state: i32,
+    default_state: i32,
+    /// Properties used for breaking the block (hardness, whether it requires tool, tool kind and tier)
+    breaking: Option<(f32, bool, Option<String>, Option<String>)>,
+}
+
+#[derive(Default)]
+struct Tables {
+    blocks: Vec<Block>,
+    /// States (index is the state id) with block index and property values
+    states: Vec<(usize, Vec<(String, String)>)>,
+    /// Items with their ids, names, max stack sizes and max damage
+    items: Vec<(i32, String, u8, Option<u16>)>,
+    /// Block entity types with their ids
+    block_entity_types: Vec<(i32, String)>,
+}
+
+impl Tables {
+    fn load(dir: &Path, data_dir: &Path) -> Result<Tables, String> {
+        let blocks = read_json(&dir.join("blocks.json"))?;
+        let registries = read_json(&dir.join("registries.json"))?;
+        // Item components are only reported by newer versions
+        let items = read_json(&dir.join("items.json")).ok();
+        let item_properties = read_json(&data_dir.join("item_properties.json"))?;
+        let block_properties = read_json(&data_dir.join("block_properties.json"))?;
+        let block_properties = block_properties.as_object().ok_or("block_properties.json is not an object")?;
+
+        let block_ids = registry_entries(&registries, "minecraft:block");
+        let mut tables = Tables::default();
+        let mut states = Vec::new();
+        for (index, (name, block)) in blocks.as_object().ok_or("blocks.json is not an object")?.iter().enumerate() {
+            let properties = block.get("properties").and_then(Value::as_object).map(|properties| {
+                properties.iter().map(|(name, values)| {
+                    let values = values.as_array().into_iter().flatten().filter_map(Value::as_str).map(String::from).collect();
+                    (name.clone(), values)
+                }).collect()
+            }).unwrap_or_default();
+
+            let (mut min_state, mut max_state, mut default_state) = (i32::MAX, i32::MIN, -1);
+            for state in block.get("states").and_then(Value::as_array).ok_or(format!("{} has no states", name))? {
+                let id = state.get("id").and_then(Value::as_i64).ok_or(format!("state of {} has no id", name))? as i32;
+                let values = state.get("properties").and_then(Value::as_object).map(|values| {
+                    values.iter().map(|(k, v)| (k.clone(), v.as_str().unwrap_or_default().to_string())).collect()
+                }).unwrap_or_default();
+                if state.get("default").and_then(Value::as_bool).unwrap_or(false) {
+                    default_state = id;
+                }
+                min_state = min_state.min(id);
+                max_state = max_state.max(id);
+                states.push((id, tables.blocks.len(), values));
+            }
+
+            let id = block_ids.iter().find(|(n, _)| n == name).map_or(index as i32, |(_, id)| *id);
+            // Blocks which aren't listed can't be broken by the client (their breaking time is unknown)
+            let breaking = match block_properties.get(name) {
+                Some(properties) => {
+                    let hardness = properties.get("hardness").and_then(Value::as_f64).ok_or(format!("{} has no hardness", name))?;
+                    let requires_tool = properties.get("requires_tool").and_then(Value::as_bool).unwrap_or(false);
+                    // Tool kind and tier are stored as names of enum variants (e.g. `pickaxe` is `ToolKind::Pickaxe`)
+                    let variant = |key: &str, variants: &[&str]| match properties.get(key).and_then(Value::as_str) {
+                        Some(value) => variants.iter().find(|variant| variant.eq_ignore_ascii_case(value))
+                            .map(|variant| Some(variant.to_string()))
+                            .ok_or(format!("{} has unknown {} {}", name, key, value)),
+                        None => Ok(None),
+                    };
+                    let tool = variant("tool", &["Pickaxe", "Axe", "Shovel", "Hoe"])?;
+                    let tier = variant("tier", &["Stone", "Iron", "Diamond"])?;
+                    Some((hardness as f32, requires_tool, tool, tier))
+                },
+                None => None,
+            };
+            tables.blocks.push(Block {
+                id,
+                name: name.clone(),
+                properties,
+                min_state,
+                max_state,
+                default_state: default_state.max(min_state),
+                breaking,
+            });
+        }
+
+        if let Some(name) = block_properties.keys().find(|name| !tables.blocks.iter().any(|block| block.name == **name)) {
+            return Err(format!("block_properties.json contains unknown block {}", name));
+        }
+
+        // State ids are contiguous, so states can be indexed by their ids
+        states.sort_by_key(|(id, _, _)| *id);
+        for (index, (id, block, values)) in states.into_iter().enumerate() {
+            if id != index as i32 {
+                return Err(format!("state ids are not contiguous (expected {}, found {})", index, id));
+            }
+            tables.states.push((block, values));
+        }
+
+        let item_ids = registry_entries(&registries, "minecraft:item");
+        let item_properties = item_properties.as_object().ok_or("item_properties.json is not an object")?;
+        if let Some(name) = item_properties.keys().find(|name| !item_ids.iter().any(|(item, _)| item == *name)) {
+            return Err(format!("item_properties.json contains unknown item {}", name));
+        }
+        for (name, id) in item_ids {
+            let components = items.as_ref().and_then(|items| items.get(&name)).and_then(|item| item.get("components"));
+            let (max_stack_size, max_damage) = match components {
+                Some(components) => {
+                    let component = |key: &str| components.get(key).and_then(Value::as_u64);
+                    (component("minecraft:max_stack_size").map_or(64, |v| v as u8), component("minecraft:max_damage").map(|v| v as u16))
+                },
+                None => {
+                    // Items which aren't listed stack to 64 and have no durability (defaults of vanilla item properties)
+                    let properties = item_properties.get(&name);
+                    let property = |key: &str| properties.and_then(|properties| properties.get(key)).and_then(Value::as_u64);
+                    (property("max_stack_size").map_or(64, |v| v as u8), property("max_damage").map(|v| v as u16))
+                },
+            };
+            tables.items.push((id, name, max_stack_size, max_damage));
+        }
+        tables.items.sort_by_key(|(id, ..)| *id);
+
+        tables.block_entity_types = registry_entries(&registries, "minecraft:block_entity_type").into_iter().map(|(name, id)| (id, name)).collect();
+        tables.block_entity_types.sort_by_key(|(id, _)| *id);
+
+        Ok(tables)
+    }
+
+    fn write(&self, code: &mut String, module: &str) {
+        writeln!(code, "pub(crate) mod {} {{", module).unwrap();
+        writeln!(code, "    use crate::tables::{{BlockData, StateData, ItemData}};").unwrap();
+
+        writeln!(code, "    pub static BLOCKS: &[BlockData] = &[").unwrap();
+        for block in &self.blocks {
+            let properties = block.properties.iter().map(|(name, values)| format!("({:?}, &{:?})", name, values)).collect::<Vec<_>>().join(", ");
+            // Paths are absolute, so the module has no unused imports when the tables are empty
+            let breaking = match &block.breaking {
+                Some((hardness, requires_tool, tool, tier)) => format!(
+                    "Some(crate::tables::BreakingData {{ hardness: {:?}, requires_tool: {}, tool: {}, tier: {} }})",
+                    hardness, requires_tool,
+                    tool.as_ref().map_or("None".to_string(), |tool| format!("Some(crate::blocks::ToolKind::{})", tool)),
+                    tier.as_ref().map_or("None".to_string(), |tier| format!("Some(crate::blocks::ToolTier::{})", tier))),
+                None => "None".to_string(),
+            };
+            writeln!(code, "        BlockData {{ id: {}, name: {:?}, properties: &[{}], min_state: {}, max_state: {}, default_state: {}, breaking: {} }},",
+                block.id, block.name, properties, block.min_state, block.max_state, block.default_state, breaking).unwrap();
+        }
+        writeln!(code, "    ];").unwrap();
+
+        writeln!(code, "    pub static STATES: &[StateData] = &[").unwrap();
+        for (block, values) in &self.states {
+            let values = values.iter().map(|(k, v)| format!("({:?}, {:?})", k, v)).collect::<Vec<_>>().join(", ");
+            writeln!(code, "        StateData {{ block: {}, properties: &[{}] }},", block, values).unwrap();
+        }
+        writeln!(code, "    ];").unwrap();
+
+        writeln!(code, "    pub static ITEMS: &[ItemData] = &[").unwrap();
+        for (id, name, max_stack_size, max_damage) in &self.items {
+            writeln!(code, "        ItemData {{ id: {}, name: {:?}, max_stack_size: {:?}, max_damage: {:?} }},", id, name, max_stack_size, max_damage).unwrap();
+        }
+        writeln!(code, "    ];").unwrap();
+
+        writeln!(code, "    pub static BLOCK_ENTITY_TYPES: &[(i32, &str)] = &[").unwrap();
+        for (id, name) in &self.block_entity_types {
+            writeln!(code, "        ({}, {:?}),", id, name).unwrap();
+        }
+        writeln!(code, "    ];").unwrap();
+        writeln!(code, "}}").unwrap();
+    }
+}
+
+fn read_json(path: &Path) -> Result<Value, String> {
+    let json = fs::read_to_string(path).map_err(|e| format!("{}: {}", path.display(), e))?;
+    serde_json::from_str(&json).map_err(|e| format!("{}: {}", path.display(), e))
+}
+
+/// Returns names and protocol ids of registry entries (from `registries.json`)
+fn registry_entries(registries: &Value, registry: &str) -> Vec<(String, i32)> {
+    registries.get(registry).and_then(|r| r.get("entries")).and_then(Value::as_object).map(|entries| {
+        entries.iter().filter_map(|(name, entry)| Some((name.clone(), entry.get("protocol_id")?.as_i64()? as i32))).collect()
+    }).unwrap_or_default()
+}
\ No newline at end of file
@@ -0,0 +1,27 @@
+#!/bin/sh
+# Generates data reports of a vanilla version into reports/<version>
+# Usage: ./generate.sh [version] (requires curl, jq and java)
+set -e
+
+VERSION="${1:-1.19.2}"
+DIR="$(cd "$(dirname "$0")" && pwd)"
+WORK="$(mktemp -d)"
+trap 'rm -rf "$WORK"' EXIT
+
+MANIFEST="https://piston-meta.mojang.com/mc/game/version_manifest_v2.json"
+VERSION_URL="$(curl -fsSL "$MANIFEST" | jq -r --arg v "$VERSION" '.versions[] | select(.id == $v) | .url')"
+if [ -z "$VERSION_URL" ]; then
+    echo "Unknown version $VERSION" >&2
+    exit 1
+fi
+SERVER_URL="$(curl -fsSL "$VERSION_URL" | jq -r '.downloads.server.url')"
+curl -fsSL -o "$WORK/server.jar" "$SERVER_URL"
+
+(cd "$WORK" && java -DbundlerMainClass=net.minecraft.data.Main -jar server.jar --reports)
+
+mkdir -p "$DIR/$VERSION"
+for report in blocks.json registries.json items.json; do
+    if [ -f "$WORK/generated/reports/$report" ]; then
+        cp "$WORK/generated/reports/$report" "$DIR/$VERSION/"
+    fi
+done
@@ -0,0 +1,261 @@
+use crate::{Version, names_match, tables::{BlockData, StateData}};
+
+/// Block state (block with specific property values)
+#[derive(Clone, Copy)]
+pub struct BlockState {
+    pub version: Version,
+    pub id: i32,
+    data: &'static StateData,
+    block: &'static BlockData,
+}
+
+impl BlockState {
+    /// Returns block state with specified id (`None` if it's unknown)
+    pub fn from_id(version: Version, id: i32) -> Option<BlockState> {
+        let tables = version.tables();
+        let data = tables.states.get(usize::try_from(id).ok()?)?;
+        Some(BlockState {
+            version,
+            id,
+            data,
+            block: &tables.blocks[data.block],
+        })
+    }
+
+    /// Name of the block (e.g. `minecraft:oak_log`)
+    pub fn name(&self) -> &'static str {
+        self.block.name
+    }
+
+    /// Block of the state
+    pub fn block(&self) -> Block {
+        Block { version: self.version, data: self.block }
+    }
+
+    /// Property values of the state (e.g. `[("axis", "y")]`)
+    pub fn properties(&self) -> &'static [(&'static str, &'static str)] {
+        self.data.properties
+    }
+
+    /// Returns value of the property
+    pub fn property(&self, name: &str) -> Option<&'static str> {
+        self.data.properties.iter().find(|(key, _)| *key == name).map(|(_, value)| *value)
+    }
+
+    /// Whether this is the default state of the block
+    pub fn is_default(&self) -> bool {
+        self.block.default_state == self.id
+    }
+
+    /// Returns state of the same block with the property changed (`None` if the block has no such property or value)
+    pub fn with_property(&self, name: &str, value: &str) -> Option<BlockState> {
+        self.block().state_with(self.data.properties.iter().map(|(key, v)| (*key, if *key == name { value } else { *v })))
+            .filter(|state| state.property(name) == Some(value))
+    }
+}
+
+impl std::fmt::Debug for BlockState {
+    /// Formats state like vanilla (e.g. `minecraft:oak_log[axis=y]`)
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())?;
+        if !self.data.properties.is_empty() {
+            let properties = self.data.properties.iter().map(|(k, v)| format!("{}={}", k, v)).collect::<Vec<String>>();
+            write!(f, "[{}]", properties.join(","))?;
+        }
+        Ok(())
+    }
+}
+
+impl PartialEq for BlockState {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version && self.id == other.id
+    }
+}
+
+impl Eq for BlockState {}
+
+/// Block type (e.g. `minecraft:oak_log`) with all its states
+#[derive(Clone, Copy)]
+pub struct Block {
+    pub version: Version,
+    data: &'static BlockData,
+}
+
+impl Block {
+    /// Returns block with specified name (namespace is optional)
+    pub fn from_name(version: Version, name: &str) -> Option<Block> {
+        let data = version.tables().blocks.iter().find(|block| names_match(block.name, name))?;
+        Some(Block { version, data })
+    }
+
+    /// Returns block with specified id (from `minecraft:block` registry)
+    pub fn from_id(version: Version, id: i32) -> Option<Block> {
+        let data = version.tables().blocks.iter().find(|block| block.id == id)?;
+        Some(Block { version, data })
+    }
+
+    /// Returns all blocks of the version
+    pub fn all(version: Version) -> impl Iterator<Item = Block> {
+        version.tables().blocks.iter().map(move |data| Block { version, data })
+    }
+
+    pub fn id(&self) -> i32 {
+        self.data.id
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.data.name
+    }
+
+    /// Properties with their possible values
+    pub fn properties(&self) -> &'static [(&'static str, &'static [&'static str])] {
+        self.data.properties
+    }
+
+    /// Time needed to break the block (vanilla `destroyTime`, negative if it can't be broken, `None` if it's unknown)
+    pub fn hardness(&self) -> Option<f32> {
+        self.data.breaking.as_ref().map(|breaking| breaking.hardness)
+    }
+
+    /// Whether the block drops only when broken with the correct tool (it's broken slower without it)
+    pub fn requires_tool(&self) -> bool {
+        self.data.breaking.as_ref().is_some_and(|breaking| breaking.requires_tool)
+    }
+
+    /// Kind of tool which breaks the block faster
+    pub fn tool(&self) -> Option<ToolKind> {
+        self.data.breaking.as_ref()?.tool
+    }
+
+    /// Minimum tier of the tool needed for the block to drop
+    pub fn tool_tier(&self) -> Option<ToolTier> {
+        self.data.breaking.as_ref()?.tier
+    }
+
+    /// Default state of the block (placed when no properties are specified)
+    pub fn default_state(&self) -> BlockState {
+        self.state(self.data.default_state)
+    }
+
+    /// Returns all states of the block
+    pub fn states(&self) -> impl Iterator<Item = BlockState> + '_ {
+        (self.data.min_state..=self.data.max_state).map(|id| self.state(id))
+    }
+
+    /// Returns state with specified property values (`None` if there is no such state)
+    pub fn state_with<'a>(&self, properties: impl IntoIterator<Item = (&'a str, &'a str)>) -> Option<BlockState> {
+        let properties = properties.into_iter().collect::<Vec<(&str, &str)>>();
+        self.states().find(|state| properties.iter().all(|(key, value)| state.property(key) == Some(value)))
+    }
+
+    fn state(&self, id: i32) -> BlockState {
+        BlockState {
+            version: self.version,
+            id,
+            data: &self.version.tables().states[id as usize],
+            block: self.data,
+        }
+    }
+}
+
+impl std::fmt::Debug for Block {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl PartialEq for Block {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version && self.data.id == other.data.id
+    }
+}
+
+impl Eq for Block {}
+
+/// Kind of tool which breaks the block faster (vanilla `mineable/<tool>` block tags)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToolKind {
+    Pickaxe,
+    Axe,
+    Shovel,
+    Hoe,
+}
+
+/// Material of the tool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ToolTier {
+    Wood,
+    Stone,
+    Iron,
+    Diamond,
+    Netherite,
+    Gold,
+}
+
+impl ToolTier {
+    /// Mining level (blocks which need higher tier don't drop when broken with the tool)
+    pub fn level(&self) -> u8 {
+        match self {
+            ToolTier::Wood | ToolTier::Gold => 0,
+            ToolTier::Stone => 1,
+            ToolTier::Iron => 2,
+            ToolTier::Diamond => 3,
+            ToolTier::Netherite => 4,
+        }
+    }
+
+    /// Speed of breaking blocks which are mineable with the tool (1.0 without a tool)
+    pub fn speed(&self) -> f32 {
+        match self {
+            ToolTier::Wood => 2.0,
+            ToolTier::Stone => 4.0,
+            ToolTier::Iron => 6.0,
+            ToolTier::Diamond => 8.0,
+            ToolTier::Netherite => 9.0,
+            ToolTier::Gold => 12.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(not(reports_v1_19_2), ignore = "needs 1.19.2 data reports")]
+    fn known_states() {
+        let version = Version::V1_19_2;
+        let stone = BlockState::from_id(version, 1).unwrap();
+        assert_eq!(stone.name(), "minecraft:stone");
+        assert!(stone.properties().is_empty() && stone.is_default());
+        assert_eq!(Block::from_name(version, "stone").unwrap().id(), 1);
+        assert_eq!(BlockState::from_id(version, 0).unwrap().name(), "minecraft:air");
+
+        let oak_log = BlockState::from_id(version, 118).unwrap();
+        assert_eq!(format!("{:?}", oak_log), "minecraft:oak_log[axis=y]");
+        assert!(oak_log.is_default());
+        assert_eq!(oak_log.with_property("axis", "x").map(|state| state.id), Some(117));
+        assert_eq!(oak_log.with_property("axis", "w"), None);
+        assert_eq!(oak_log.block().states().map(|state| state.id).collect::<Vec<i32>>(), [117, 118, 119]);
+
+        let grass = Block::from_name(version, "minecraft:grass_block").unwrap();
+        assert_eq!(grass.default_state().id, 9);
+        assert_eq!(grass.state_with([("snowy", "true")]).map(|state| state.id), Some(8));
+        assert!(BlockState::from_id(version, -1).is_none());
+    }
+
+    #[test]
+    #[cfg_attr(not(reports_v1_19_2), ignore = "needs 1.19.2 data reports")]
+    fn breaking_properties() {
+        let version = Version::V1_19_2;
+        let block = |name: &str| Block::from_name(version, name).unwrap();
+        let properties = |name: &str| (block(name).hardness(), block(name).requires_tool(), block(name).tool(), block(name).tool_tier());
+        assert_eq!(properties("stone"), (Some(1.5), true, Some(ToolKind::Pickaxe), None));
+        assert_eq!(properties("grass_block"), (Some(0.6), false, Some(ToolKind::Shovel), None));
+        assert_eq!(properties("oak_log"), (Some(2.0), false, Some(ToolKind::Axe), None));
+        assert_eq!(properties("air"), (Some(0.0), false, None, None));
+        assert_eq!(properties("bedrock"), (Some(-1.0), false, None, None));
+        assert_eq!(properties("iron_ore"), (Some(3.0), true, Some(ToolKind::Pickaxe), Some(ToolTier::Stone)));
+        assert_eq!(properties("deepslate_gold_ore"), (Some(4.5), true, Some(ToolKind::Pickaxe), Some(ToolTier::Iron)));
+    }
+}
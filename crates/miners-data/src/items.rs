@@ -0,0 +1,85 @@
+use crate::{Version, names_match, tables::ItemData};
+
+/// Item type (e.g. `minecraft:diamond_sword`)
+#[derive(Clone, Copy)]
+pub struct Item {
+    pub version: Version,
+    data: &'static ItemData,
+}
+
+impl Item {
+    /// Returns item with specified id (from `minecraft:item` registry)
+    pub fn from_id(version: Version, id: i32) -> Option<Item> {
+        let items = version.tables().items;
+        let index = items.binary_search_by_key(&id, |item| item.id).ok()?;
+        Some(Item { version, data: &items[index] })
+    }
+
+    /// Returns item with specified name (namespace is optional)
+    pub fn from_name(version: Version, name: &str) -> Option<Item> {
+        let data = version.tables().items.iter().find(|item| names_match(item.name, name))?;
+        Some(Item { version, data })
+    }
+
+    /// Returns all items of the version
+    pub fn all(version: Version) -> impl Iterator<Item = Item> {
+        version.tables().items.iter().map(move |data| Item { version, data })
+    }
+
+    pub fn id(&self) -> i32 {
+        self.data.id
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.data.name
+    }
+
+    /// Maximum number of items in a single stack
+    pub fn max_stack_size(&self) -> u8 {
+        self.data.max_stack_size
+    }
+
+    /// Durability of the item (`None` if it has no durability)
+    pub fn max_damage(&self) -> Option<u16> {
+        self.data.max_damage
+    }
+}
+
+impl std::fmt::Debug for Item {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+impl PartialEq for Item {
+    fn eq(&self, other: &Self) -> bool {
+        self.version == other.version && self.data.id == other.data.id
+    }
+}
+
+impl Eq for Item {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[cfg_attr(not(reports_v1_19_2), ignore = "needs 1.19.2 data reports")]
+    fn known_items() {
+        let version = Version::V1_19_2;
+        let stone = Item::from_id(version, 1).unwrap();
+        assert_eq!(stone.name(), "minecraft:stone");
+        assert_eq!((stone.max_stack_size(), stone.max_damage()), (64, None));
+        assert_eq!(Item::from_name(version, "granite").map(|item| item.id()), Some(2));
+
+        let properties = |name: &str| Item::from_name(version, name).map(|item| (item.max_stack_size(), item.max_damage()));
+        assert_eq!(properties("diamond_sword"), Some((1, Some(1561))));
+        assert_eq!(properties("netherite_chestplate"), Some((1, Some(592))));
+        assert_eq!(properties("elytra"), Some((1, Some(432))));
+        assert_eq!(properties("ender_pearl"), Some((16, None)));
+        assert_eq!(properties("written_book"), Some((16, None)));
+        assert_eq!(properties("water_bucket"), Some((1, None)));
+        assert_eq!(properties("red_bed"), Some((1, None)));
+        assert!(Item::from_id(version, -1).is_none());
+    }
+}
@@ -0,0 +1,79 @@
+//! Block state, block and item registries generated from vanilla data generator reports
+//!
+//! # Example
+//! ```rs
+//! let state = BlockState::from_id(Version::V1_19_2, 118)?;
+//! assert_eq!(state.name(), "minecraft:oak_log");
+//! assert_eq!(state.property("axis"), Some("y"));
+//! ```
+
+pub mod blocks;
+pub mod items;
+pub(crate) mod tables;
+
+pub use blocks::{Block, BlockState, ToolKind, ToolTier};
+pub use items::Item;
+
+/// Game version with bundled data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Version {
+    V1_19_2,
+}
+
+impl Version {
+    /// All supported versions
+    pub const ALL: &'static [Version] = &[Version::V1_19_2];
+
+    /// Returns version using specified protocol version (`None` if it's not supported)
+    pub fn from_protocol(protocol_version: i32) -> Option<Version> {
+        Version::ALL.iter().copied().find(|version| version.protocol() == protocol_version)
+    }
+
+    /// Protocol version number
+    pub fn protocol(&self) -> i32 {
+        match self {
+            Version::V1_19_2 => 760,
+        }
+    }
+
+    /// Name of the version (e.g. `1.19.2`)
+    pub fn name(&self) -> &'static str {
+        match self {
+            Version::V1_19_2 => "1.19.2",
+        }
+    }
+
+    /// Data version stored in saves (`DataVersion` tag)
+    pub fn data_version(&self) -> i32 {
+        match self {
+            Version::V1_19_2 => 3120,
+        }
+    }
+
+    /// Returns name of the block entity type (from `minecraft:block_entity_type` registry)
+    pub fn block_entity_type(&self, id: i32) -> Option<&'static str> {
+        let types = self.tables().block_entity_types;
+        types.binary_search_by_key(&id, |(id, _)| *id).ok().map(|index| types[index].1)
+    }
+
+    /// Whether reports of the version were available when the crate was built (tables are empty otherwise)
+    pub fn has_data(&self) -> bool {
+        !self.tables().states.is_empty()
+    }
+
+    pub(crate) fn tables(&self) -> tables::Tables {
+        match self {
+            Version::V1_19_2 => tables::Tables {
+                blocks: tables::v1_19_2::BLOCKS,
+                states: tables::v1_19_2::STATES,
+                items: tables::v1_19_2::ITEMS,
+                block_entity_types: tables::v1_19_2::BLOCK_ENTITY_TYPES,
+            },
+        }
+    }
+}
+
+/// Compares registry names (namespace is optional, `minecraft` is assumed if it's missing)
+pub(crate) fn names_match(name: &str, query: &str) -> bool {
+    name == query || name.strip_prefix("minecraft:") == Some(query)
+}
\ No newline at end of file
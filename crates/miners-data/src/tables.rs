@@ -0,0 +1,51 @@
+//! Tables generated by the build script (see `build.rs`)
+
+pub(crate) struct BlockData {
+    pub id: i32,
+    pub name: &'static str,
+    /// Properties with their possible values
+    pub properties: &'static [(&'static str, &'static [&'static str])],
+    pub min_state: i32,
+    pub max_state: i32,
+    pub default_state: i32,
+    /// `None` if the block isn't listed in `block_properties.json`
+    pub breaking: Option<BreakingData>,
+}
+
+/// Properties of the block used for computing time needed to break it
+pub(crate) struct BreakingData {
+    /// Negative if the block can't be broken
+    pub hardness: f32,
+    pub requires_tool: bool,
+    pub tool: Option<crate::blocks::ToolKind>,
+    /// Minimum tier of the tool which drops the block
+    pub tier: Option<crate::blocks::ToolTier>,
+}
+
+pub(crate) struct StateData {
+    /// Index of the block in `BLOCKS`
+    pub block: usize,
+    pub properties: &'static [(&'static str, &'static str)],
+}
+
+pub(crate) struct ItemData {
+    pub id: i32,
+    pub name: &'static str,
+    pub max_stack_size: u8,
+    /// `None` if the item has no durability
+    pub max_damage: Option<u16>,
+}
+
+/// Tables of a single version
+#[derive(Clone, Copy)]
+pub(crate) struct Tables {
+    pub blocks: &'static [BlockData],
+    /// Indexed by state id
+    pub states: &'static [StateData],
+    /// Sorted by id
+    pub items: &'static [ItemData],
+    /// Names of block entity types (sorted by id)
+    pub block_entity_types: &'static [(i32, &'static str)],
+}
+
+include!(concat!(env!("OUT_DIR"), "/data.rs"));
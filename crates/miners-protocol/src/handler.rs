@@ -135,6 +135,7 @@ impl PacketHandler for LoginPlayHandler {
         
         // Print debug info about this packet
         log::debug!(target: "miners-protocol", "Login play packet received: {:#?}", login_play_packet);
+        connection.login_play = Some(login_play_packet);
 
         // Throw an error to exit the loop of handle_packets
         Err(HandlerError::ExitRequested)
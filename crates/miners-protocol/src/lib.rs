@@ -7,7 +7,7 @@ use packet::{IntoPacket, RawPacket};
 use packets::{handshake::HandshakePacket, status::StatusResponse, EmptyPacket};
 use serde::Deserialize;
 
-use crate::packets::login::LoginStartPacket;
+use crate::{packets::login::{LoginStartPacket, LoginPlayPacket}, utils::certificates::PlayerCertificates};
 
 pub mod packet;
 pub mod handler;
@@ -23,6 +23,8 @@ pub struct RawMinecraftSocket {
     pub state: ConnectionState,
     pub protocol_version: i32,
     pub uuid: u128,
+    /// Login (play) packet received at the end of login (contains registry codec and world info)
+    pub login_play: Option<LoginPlayPacket>,
 }
 
 impl Debug for RawMinecraftSocket {
@@ -51,6 +53,13 @@ pub struct LoginConfig {
     pub username: String,
     pub host: String,
     pub port: u16,
+    /// Player certificates (profile key pair), required by servers that enforce secure chat
+    pub certificates: Option<PlayerCertificates>,
+    /// UUID of the profile the certificates belong to
+    ///
+    /// Uses the layout of `RawPacket::read_uuid` (most significant long in the low 64 bits),
+    /// so a UUID parsed with `u128::from_str_radix` needs its halves swapped (`uuid.rotate_left(64)`)
+    pub uuid: Option<u128>,
 }
 
 impl Default for LoginConfig {
@@ -59,6 +68,8 @@ impl Default for LoginConfig {
             username: String::from("miners_client"),
             host: String::from("localhost"),
             port: 25565,
+            certificates: None,
+            uuid: None,
         }
     }
 }
@@ -74,6 +85,7 @@ impl RawMinecraftSocket {
             state: ConnectionState::Handshake,
             protocol_version: -1,
             uuid: 0,
+            login_play: None,
         }
     }
 
@@ -103,6 +115,9 @@ impl RawMinecraftSocket {
 
         let status = StatusResponse::from(socket.wait_for_packet().unwrap());
         log::debug!(target: "miners-protocol", "Server status: {:?}", status);
+        if status.enforces_secure_chat.unwrap_or(false) && config.certificates.is_none() {
+            log::warn!(target: "miners-protocol", "Server enforces secure chat, but no player certificates were provided");
+        }
 
         // Login
         let mut socket = Self::from_host(&config.host, config.port).map_err(|_| PacketError {
@@ -122,7 +137,11 @@ impl RawMinecraftSocket {
         )).unwrap();
         socket.state = ConnectionState::Login; // Change state to login
 
-        socket.send_packet(LoginStartPacket::new(config.username)).unwrap();
+        socket.send_packet(LoginStartPacket {
+            username: config.username,
+            certificates: config.certificates,
+            uuid: config.uuid,
+        }).unwrap();
 
         socket.handle_packets().ok();
         socket.handler_manager.lock().unwrap().unregister_all(); // Unregister all handlers
@@ -1,5 +1,7 @@
 use std::io::Read;
 
+use crate::utils::{location::BlockPos, nbt::NBTType};
+
 /// Represents a raw packet (id + data)
 /// 
 /// This is the packet that is sent over the network.
@@ -43,8 +45,16 @@ impl RawPacket {
         // If non_blocking is true, this will return an error if there is no data to read
         if non_blocking {
             let mut buf = [0];
-            if socket.peek(&mut buf).is_err() {
-                return Err(crate::PacketError::text("No data to read".to_string()));
+            // Peek without blocking, so caller doesn't hold the socket while nothing is being received
+            socket.set_nonblocking(true).map_err(|e| crate::PacketError::text(format!("Failed to set socket non-blocking: {}", e)))?;
+            let peeked = socket.peek(&mut buf);
+            socket.set_nonblocking(false).map_err(|e| crate::PacketError::text(format!("Failed to set socket blocking: {}", e)))?;
+            match peeked {
+                // Peeking 0 bytes means that the server closed the connection
+                Ok(0) => return Err(crate::PacketError::text("Connection closed".to_string())),
+                Ok(_) => {},
+                Err(e) if e.kind() == std::io::ErrorKind::WouldBlock => return Err(crate::PacketError::text("No data to read".to_string())),
+                Err(e) => return Err(crate::PacketError::text(format!("Failed to peek socket: {}", e))),
             }
         }
 
@@ -153,6 +163,21 @@ impl RawPacket {
         self.write_byte(long as u8);
     }
 
+    /// Writes an int (4 bytes, big endian) to the packet
+    pub fn write_int(&mut self, int: i32) {
+        self.write_bytes(int.to_be_bytes().to_vec());
+    }
+
+    /// Writes a float (4 bytes, big endian) to the packet
+    pub fn write_float(&mut self, float: f32) {
+        self.write_bytes(float.to_be_bytes().to_vec());
+    }
+
+    /// Writes a double (8 bytes, big endian) to the packet
+    pub fn write_double(&mut self, double: f64) {
+        self.write_bytes(double.to_be_bytes().to_vec());
+    }
+
     /// Writes unsigned long to the packet
     pub fn write_ulong(&mut self, long: u64) {
         // Write long as 8 bytes (from MSB to LSB)
@@ -171,6 +196,26 @@ impl RawPacket {
         self.write_byte(boolean as u8);
     }
 
+    /// Writes a UUID to the packet (same layout as [`read_uuid`](RawPacket::read_uuid))
+    pub fn write_uuid(&mut self, uuid: u128) {
+        self.write_ulong(uuid as u64);
+        self.write_ulong((uuid >> 64) as u64);
+    }
+
+    /// Writes a byte array prefixed with its length (VarInt) to the packet
+    pub fn write_byte_array(&mut self, bytes: &[u8]) {
+        self.write_varint(bytes.len() as i32);
+        self.data.extend_from_slice(bytes);
+    }
+
+    /// Writes NBT with empty root name (as sent by the network, e.g. in item slots)
+    pub fn write_nbt(&mut self, nbt: &NBTType) {
+        // Writing to a vector fails only for lists with mixed types, which can't be represented in NBT
+        if let Err(e) = nbt.write_to(&mut self.data, Some("")) {
+            log::warn!(target: "miners-protocol", "Failed to write NBT: {}", e);
+        }
+    }
+
     // ====< Readers >====
     /// Reads a byte from the packet
     pub fn read_byte(&mut self) -> u8 {
@@ -200,6 +245,19 @@ impl RawPacket {
         result
     }
 
+    /// Reads a VarLong from the packet
+    pub fn read_varlong(&mut self) -> i64 {
+        let mut result = 0;
+        for i in 0..10 {
+            let byte = self.read_byte();
+            result |= ((byte & 0b0111_1111) as i64) << (7 * i);
+            if (byte & 0b1000_0000) == 0 {
+                break;
+            }
+        }
+        result
+    }
+
     /// Tries to read a VarInt from the packet
     /// Returns None if there is no VarInt to read
     pub fn try_read_varint(&mut self) -> Option<i32> {
@@ -364,11 +422,89 @@ impl RawPacket {
 
     /// Reads `n` bytes from the packet
     pub fn read_bytes(&mut self, n: usize) -> Vec<u8> {
-        let mut result = Vec::new();
-        for _ in 0..n {
-            result.push(self.read_byte());
+        // Drain all bytes at once (removing them one by one is slow for large arrays)
+        self.data.drain(..n).collect()
+    }
+
+    /// Reads a byte array prefixed with its length (VarInt) from the packet
+    pub fn read_byte_array(&mut self) -> Vec<u8> {
+        let length = self.read_varint();
+        self.read_bytes(length as usize)
+    }
+
+    /// Reads an angle (in steps of 1/256 of a full turn) from the packet and converts it to degrees
+    pub fn read_angle(&mut self) -> f32 {
+        self.read_byte() as i8 as f32 * 360.0 / 256.0
+    }
+
+    /// Reads a block position (packed into a long) from the packet
+    pub fn read_position(&mut self) -> BlockPos {
+        BlockPos::from_long(self.read_long())
+    }
+
+    /// Reads a bit set (array of longs prefixed with its length as VarInt) from the packet
+    pub fn read_bitset(&mut self) -> Vec<i64> {
+        let length = self.read_varint();
+        (0..length).map(|_| self.read_long()).collect()
+    }
+
+    /// Tries to read `n` bytes from the packet
+    /// Returns None if the packet is shorter
+    pub fn try_read_bytes(&mut self, n: usize) -> Option<Vec<u8>> {
+        (self.data.len() >= n).then(|| self.read_bytes(n))
+    }
+
+    /// Tries to read a byte from the packet
+    /// Returns None if there is no byte to read
+    pub fn try_read_byte(&mut self) -> Option<u8> {
+        (!self.data.is_empty()).then(|| self.read_byte())
+    }
+
+    /// Tries to read a bool from the packet
+    /// Returns None if there is no bool to read
+    pub fn try_read_bool(&mut self) -> Option<bool> {
+        Some(self.try_read_byte()? == 1)
+    }
+
+    /// Tries to read i64 from the packet
+    /// Returns None if there is no i64 to read
+    pub fn try_read_long(&mut self) -> Option<i64> {
+        (self.data.len() >= 8).then(|| self.read_long())
+    }
+
+    /// Tries to read f32 from the packet
+    /// Returns None if there is no f32 to read
+    pub fn try_read_float(&mut self) -> Option<f32> {
+        (self.data.len() >= 4).then(|| self.read_float())
+    }
+
+    /// Tries to read a UUID from the packet
+    /// Returns None if there is no UUID to read
+    pub fn try_read_uuid(&mut self) -> Option<u128> {
+        (self.data.len() >= 16).then(|| self.read_uuid())
+    }
+
+    /// Tries to read a byte array prefixed with its length (VarInt) from the packet
+    /// Returns None if the length is negative or the packet is shorter
+    pub fn try_read_byte_array(&mut self) -> Option<Vec<u8>> {
+        let length = usize::try_from(self.try_read_varint()?).ok()?;
+        self.try_read_bytes(length)
+    }
+
+    /// Tries to read a block position from the packet
+    /// Returns None if there is no position to read
+    pub fn try_read_position(&mut self) -> Option<BlockPos> {
+        self.try_read_long().map(BlockPos::from_long)
+    }
+
+    /// Tries to read a bit set from the packet
+    /// Returns None if the length is negative or the packet is shorter
+    pub fn try_read_bitset(&mut self) -> Option<Vec<i64>> {
+        let length = usize::try_from(self.try_read_varint()?).ok()?;
+        if self.data.len() / 8 < length {
+            return None;
         }
-        result
+        Some((0..length).map(|_| self.read_long()).collect())
     }
 }
 
@@ -1,14 +1,19 @@
-use crate::{packet::{IntoPacket, RawPacket}, utils::{location::Location, nbt::NBTType}};
+use crate::{packet::{IntoPacket, RawPacket}, utils::{location::Location, nbt::NBTType, certificates::PlayerCertificates}};
 
 #[derive(Debug, Clone)]
 pub struct LoginStartPacket {
     pub username: String,
+    /// Player certificates, public key of which is sent to the server (required for secure chat)
+    pub certificates: Option<PlayerCertificates>,
+    pub uuid: Option<u128>,
 }
 
 impl LoginStartPacket {
     pub fn new(username: String) -> LoginStartPacket {
         LoginStartPacket {
             username,
+            certificates: None,
+            uuid: None,
         }
     }
 }
@@ -18,8 +23,21 @@ impl IntoPacket for LoginStartPacket {
         let mut packet = crate::packet::RawPacket::empty(0);
         packet.write_string(&self.username);
         // TODO: Base this on protocol version
-        packet.write_bool(false); // Has sig data
-        packet.write_bool(false); // Has UUID
+        if let Some(certificates) = &self.certificates {
+            packet.write_bool(true); // Has sig data
+            packet.write_long(certificates.expires_at);
+            packet.write_byte_array(&certificates.public_key_der());
+            packet.write_byte_array(&certificates.public_key_signature);
+        } else {
+            packet.write_bool(false); // Has sig data
+        }
+
+        if let Some(uuid) = self.uuid {
+            packet.write_bool(true); // Has UUID
+            packet.write_uuid(uuid);
+        } else {
+            packet.write_bool(false); // Has UUID
+        }
         packet
     }
 }
@@ -0,0 +1,249 @@
+use std::{fmt::Debug, sync::OnceLock};
+
+use base64::Engine;
+use rsa::{RsaPrivateKey, RsaPublicKey, pkcs8::{DecodePrivateKey, DecodePublicKey, EncodePublicKey}, pkcs1::{DecodeRsaPrivateKey, DecodeRsaPublicKey}, pkcs1v15::{SigningKey, VerifyingKey, Signature}, signature::{Signer, Verifier}, sha2::Sha256};
+use serde::Deserialize;
+use sha1::Sha1;
+
+use crate::{PacketError, packet::RawPacket};
+
+/// Public key of Mojang services (`yggdrasil_session_pubkey.der` bundled with the game) used to sign profile keys
+const MOJANG_SERVICES_KEY: &str = "\
+MIICIjANBgkqhkiG9w0BAQEFAAOCAg8AMIICCgKCAgEAylB4B6m5lz7jwrcFz6Fd\
+/fnfUhcvlxsTSn5kIK/2aGG1C3kMy4VjhwlxF6BFUSnfxhNswPjh3ZitkBxEAFY2\
+5uzkJFRwHwVA9mdwjashXILtR6OqdLXXFVyUPIURLOSWqGNBtb08EN5fMnG8iFLg\
+EJIBMxs9BvF3s3/FhuHyPKiVTZmXY0WY4ZyYqvoKR+XjaTRPPvBsDa4WI2u1zxXM\
+eHlodT3lnCzVvyOYBLXL6CJgByuOxccJ8hnXfF9yY4F0aeL080Jz/3+EBNG8RO4B\
+yhtBf4Ny8NQ6stWsjfeUIvH7bU/4zCYcYOq4WrInXHqS8qruDmIl7P5XXGcabuzQ\
+stPf/h2CRAUpP/PlHXcMlvewjmGU6MfDK+lifScNYwjPxRo4nKTGFZf/0aqHCh/E\
+AsQyLKrOIYRE0lDG3bzBh8ogIMLAugsAfBb6M3mqCqKaTMAf/VAjh5FFJnjS+7bE\
++bZEV0qwax1CEoPPJL1fIQjOS8zj086gjpGRCtSy9+bTPTfTR/SJ+VUB5G2IeCIt\
+kNHpJX2ygojFZ9n5Fnj7R9ZnOM+L8nyIjPu3aePvtcrXlyLhH/hvOfIOjPxOlqW+\
+O5QwSFP4OEcyLAUgDdUgyW36Z5mB285uKW/ighzZsOTevVUG2QwDItObIV6i8RCx\
+FbN2oDHyPaO5j1tTaBNyVt8CAwEAAQ==";
+
+/// Returns decoded public key of Mojang services
+pub fn mojang_services_key() -> &'static RsaPublicKey {
+    static KEY: OnceLock<RsaPublicKey> = OnceLock::new();
+    KEY.get_or_init(|| {
+        let der = decode_pem(MOJANG_SERVICES_KEY).expect("Bundled Mojang services key is valid base64");
+        RsaPublicKey::from_public_key_der(&der).expect("Bundled Mojang services key is valid DER")
+    })
+}
+
+/// Player certificates (profile key pair) used for signing chat messages (1.19.x secure chat)
+///
+/// Certificates can be loaded from the JSON returned by the account provider
+/// (`https://api.minecraftservices.com/player/certificates`) or from a local file containing the same JSON.
+///
+/// # Example
+/// ```rs
+/// let certificates = PlayerCertificates::from_file("certificates.json")?;
+/// ```
+#[derive(Clone)]
+pub struct PlayerCertificates {
+    pub private_key: RsaPrivateKey,
+    pub public_key: RsaPublicKey,
+    /// Signature of the public key made by Mojang (`publicKeySignatureV2`)
+    pub public_key_signature: Vec<u8>,
+    /// Time when the key expires (in milliseconds since UNIX epoch)
+    pub expires_at: i64,
+}
+
+// Private key should never end up in logs
+impl Debug for PlayerCertificates {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PlayerCertificates")
+            .field("public_key", &self.public_key)
+            .field("expires_at", &self.expires_at)
+            .finish()
+    }
+}
+
+/// Raw certificates JSON (as returned by the account provider)
+#[derive(Debug, Deserialize)]
+struct RawCertificates {
+    #[serde(rename = "keyPair")]
+    key_pair: RawKeyPair,
+    #[serde(rename = "publicKeySignatureV2")]
+    public_key_signature_v2: String,
+    #[serde(rename = "expiresAt")]
+    expires_at: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct RawKeyPair {
+    #[serde(rename = "privateKey")]
+    private_key: String,
+    #[serde(rename = "publicKey")]
+    public_key: String,
+}
+
+impl PlayerCertificates {
+    /// Parses certificates from the account provider JSON response
+    pub fn from_json(json: &str) -> Result<PlayerCertificates, PacketError> {
+        let raw: RawCertificates = serde_json::from_str(json).map_err(|e| certificates_error(format!("Invalid certificates JSON: {}", e)))?;
+
+        // Mojang labels both keys as PKCS#1 but they are actually PKCS#8 / X.509, so accept both
+        let private_der = decode_pem(&raw.key_pair.private_key)?;
+        let private_key = RsaPrivateKey::from_pkcs8_der(&private_der)
+            .or_else(|_| RsaPrivateKey::from_pkcs1_der(&private_der))
+            .map_err(|e| certificates_error(format!("Invalid private key: {}", e)))?;
+
+        let public_der = decode_pem(&raw.key_pair.public_key)?;
+        let public_key = RsaPublicKey::from_public_key_der(&public_der)
+            .or_else(|_| RsaPublicKey::from_pkcs1_der(&public_der))
+            .map_err(|e| certificates_error(format!("Invalid public key: {}", e)))?;
+
+        let public_key_signature = base64::engine::general_purpose::STANDARD.decode(raw.public_key_signature_v2.trim())
+            .map_err(|e| certificates_error(format!("Invalid public key signature: {}", e)))?;
+
+        let expires_at = parse_timestamp(&raw.expires_at)
+            .ok_or_else(|| certificates_error(format!("Invalid expiration date: {}", raw.expires_at)))?;
+
+        Ok(PlayerCertificates {
+            private_key,
+            public_key,
+            public_key_signature,
+            expires_at,
+        })
+    }
+
+    /// Loads certificates from a local JSON file (same format as the account provider response)
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> Result<PlayerCertificates, PacketError> {
+        let json = std::fs::read_to_string(path).map_err(|e| certificates_error(format!("Failed to read certificates file: {}", e)))?;
+        Self::from_json(&json)
+    }
+
+    /// Returns public key encoded as DER (X.509 SubjectPublicKeyInfo), which is the format sent to the server
+    pub fn public_key_der(&self) -> Vec<u8> {
+        self.public_key.to_public_key_der().unwrap().as_bytes().to_vec()
+    }
+
+    /// Returns public part of the certificates (as other clients see it)
+    pub fn profile_public_key(&self) -> ProfilePublicKey {
+        ProfilePublicKey {
+            key: self.public_key.clone(),
+            expires_at: self.expires_at,
+            key_signature: self.public_key_signature.clone(),
+        }
+    }
+
+    /// Checks if the key pair has already expired
+    pub fn is_expired(&self) -> bool {
+        let now = std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as i64;
+        now >= self.expires_at
+    }
+
+    /// Signs data using `SHA256withRSA` (the algorithm used by secure chat)
+    pub fn sign(&self, data: &[u8]) -> Vec<u8> {
+        let signing_key = SigningKey::<Sha256>::new(self.private_key.clone());
+        let signature: Signature = signing_key.sign(data);
+        Box::<[u8]>::from(signature).into_vec()
+    }
+}
+
+/// Public key of other player's profile (sent by the server in Player Info packet)
+#[derive(Debug, Clone)]
+pub struct ProfilePublicKey {
+    pub key: RsaPublicKey,
+    /// Time when the key expires (in milliseconds since UNIX epoch)
+    pub expires_at: i64,
+    /// Signature of the key made by Mojang
+    pub key_signature: Vec<u8>,
+}
+
+impl ProfilePublicKey {
+    /// Decodes public key from DER (X.509 SubjectPublicKeyInfo) as it is sent over the network
+    pub fn from_der(expires_at: i64, der: &[u8], key_signature: Vec<u8>) -> Result<ProfilePublicKey, PacketError> {
+        let key = RsaPublicKey::from_public_key_der(der).map_err(|e| certificates_error(format!("Invalid public key: {}", e)))?;
+        Ok(ProfilePublicKey {
+            key,
+            expires_at,
+            key_signature,
+        })
+    }
+
+    /// Checks if the key has already expired
+    pub fn is_expired(&self) -> bool {
+        let now = std::time::UNIX_EPOCH.elapsed().unwrap().as_millis() as i64;
+        now >= self.expires_at
+    }
+
+    /// Checks that the key belongs to the profile, was signed by Mojang (`SHA1withRSA`) and has not expired
+    ///
+    /// Keys which fail this check must be treated as absent, otherwise server could forge messages of any player
+    pub fn validate(&self, profile_id: u128) -> bool {
+        if self.is_expired() {
+            return false;
+        }
+
+        let Ok(key_der) = self.key.to_public_key_der() else {
+            return false;
+        };
+
+        // UUID (most significant bits first) + expiration time + key (DER)
+        let mut payload = RawPacket::empty(0);
+        payload.write_uuid(profile_id);
+        payload.write_long(self.expires_at);
+        payload.write_bytes(key_der.as_bytes().to_vec());
+
+        let verifying_key = VerifyingKey::<Sha1>::new(mojang_services_key().clone());
+        match Signature::try_from(self.key_signature.as_slice()) {
+            Ok(signature) => verifying_key.verify(&payload.data, &signature).is_ok(),
+            Err(_) => false,
+        }
+    }
+
+    /// Verifies `SHA256withRSA` signature made with the private key matching this public key
+    pub fn verify(&self, data: &[u8], signature: &[u8]) -> bool {
+        verify_signature(&self.key, data, signature)
+    }
+}
+
+/// Verifies `SHA256withRSA` signature of the data with given public key
+pub fn verify_signature(public_key: &RsaPublicKey, data: &[u8], signature: &[u8]) -> bool {
+    let verifying_key = VerifyingKey::<Sha256>::new(public_key.clone());
+    match Signature::try_from(signature) {
+        Ok(signature) => verifying_key.verify(data, &signature).is_ok(),
+        Err(_) => false,
+    }
+}
+
+fn certificates_error(reason: String) -> PacketError {
+    PacketError::new(String::from("miners.error.certificates.invalid"), vec![reason])
+}
+
+/// Decodes PEM armored key into raw DER bytes (header and footer labels are ignored)
+fn decode_pem(pem: &str) -> Result<Vec<u8>, PacketError> {
+    let body = pem.lines()
+        .filter(|line| !line.starts_with("-----"))
+        .collect::<String>()
+        .replace(['\r', ' ', '\\'], "");
+    base64::engine::general_purpose::STANDARD.decode(body).map_err(|e| certificates_error(format!("Invalid PEM data: {}", e)))
+}
+
+/// Parses ISO-8601 UTC timestamp (e.g. `2022-08-05T18:38:01.419412Z`) into milliseconds since UNIX epoch
+fn parse_timestamp(timestamp: &str) -> Option<i64> {
+    let timestamp = timestamp.trim().trim_end_matches('Z');
+    let (date, time) = timestamp.split_once('T')?;
+
+    let mut date = date.split('-').map(|v| v.parse::<i64>());
+    let (year, month, day) = (date.next()?.ok()?, date.next()?.ok()?, date.next()?.ok()?);
+
+    let (time, fraction) = time.split_once('.').unwrap_or((time, "0"));
+    let mut time = time.split(':').map(|v| v.parse::<i64>());
+    let (hour, minute, second) = (time.next()?.ok()?, time.next()?.ok()?, time.next()?.ok()?);
+    // Only milliseconds are kept
+    let millis = format!("{:0<3}", fraction.chars().take(3).collect::<String>()).parse::<i64>().ok()?;
+
+    // Days since epoch (civil from days algorithm by Howard Hinnant)
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = if y >= 0 { y } else { y - 399 } / 400;
+    let yoe = y - era * 400;
+    let doy = (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146097 + doe - 719468;
+
+    Some((((days * 24 + hour) * 60 + minute) * 60 + second) * 1000 + millis)
+}
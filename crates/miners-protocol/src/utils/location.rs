@@ -15,4 +15,112 @@ impl Location {
             z: 0.0,
         }
     }
+}
+
+/// 3D vector (used for positions and velocities)
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Vec3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl Vec3 {
+    pub const ZERO: Vec3 = Vec3 { x: 0.0, y: 0.0, z: 0.0 };
+
+    pub fn new(x: f64, y: f64, z: f64) -> Vec3 {
+        Vec3 { x, y, z }
+    }
+
+    /// Length of the vector
+    pub fn length(&self) -> f64 {
+        self.length_squared().sqrt()
+    }
+
+    /// Squared length of the vector (faster than `length`, useful for comparisons)
+    pub fn length_squared(&self) -> f64 {
+        self.x * self.x + self.y * self.y + self.z * self.z
+    }
+
+    /// Distance to other point
+    pub fn distance(&self, other: Vec3) -> f64 {
+        (*self - other).length()
+    }
+
+    /// Squared distance to other point
+    pub fn distance_squared(&self, other: Vec3) -> f64 {
+        (*self - other).length_squared()
+    }
+
+    /// Horizontal (XZ plane) distance to other point
+    pub fn horizontal_distance(&self, other: Vec3) -> f64 {
+        ((self.x - other.x).powi(2) + (self.z - other.z).powi(2)).sqrt()
+    }
+}
+
+impl std::ops::Add for Vec3 {
+    type Output = Vec3;
+
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+}
+
+impl std::ops::Sub for Vec3 {
+    type Output = Vec3;
+
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+}
+
+impl std::ops::Mul<f64> for Vec3 {
+    type Output = Vec3;
+
+    fn mul(self, scale: f64) -> Vec3 {
+        Vec3::new(self.x * scale, self.y * scale, self.z * scale)
+    }
+}
+
+/// Position of the block
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct BlockPos {
+    pub x: i32,
+    pub y: i32,
+    pub z: i32,
+}
+
+impl BlockPos {
+    pub fn new(x: i32, y: i32, z: i32) -> BlockPos {
+        BlockPos { x, y, z }
+    }
+
+    /// Decodes position packed into a long (x: 26 bits, z: 26 bits, y: 12 bits)
+    pub fn from_long(value: i64) -> BlockPos {
+        BlockPos {
+            x: (value >> 38) as i32,
+            y: (value << 52 >> 52) as i32,
+            z: (value << 26 >> 38) as i32,
+        }
+    }
+
+    /// Encodes position into a long (inverse of `from_long`)
+    pub fn to_long(&self) -> i64 {
+        ((self.x as i64 & 0x3FFFFFF) << 38) | ((self.z as i64 & 0x3FFFFFF) << 12) | (self.y as i64 & 0xFFF)
+    }
+
+    /// Returns position of the block containing the point
+    pub fn containing(point: Vec3) -> BlockPos {
+        BlockPos::new(point.x.floor() as i32, point.y.floor() as i32, point.z.floor() as i32)
+    }
+
+    /// Returns center of the bottom face of the block (where entity standing on the block below would be)
+    pub fn bottom_center(&self) -> Vec3 {
+        Vec3::new(self.x as f64 + 0.5, self.y as f64, self.z as f64 + 0.5)
+    }
+
+    /// Returns position moved by specified offset
+    pub fn offset(&self, x: i32, y: i32, z: i32) -> BlockPos {
+        BlockPos::new(self.x + x, self.y + y, self.z + z)
+    }
 }
\ No newline at end of file
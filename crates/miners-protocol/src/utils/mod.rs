@@ -1,2 +1,3 @@
+pub mod certificates;
 pub mod location;
 pub mod nbt;
\ No newline at end of file
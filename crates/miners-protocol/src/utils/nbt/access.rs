@@ -0,0 +1,237 @@
+//! Typed access to NBT values
+//!
+//! # Example
+//! ```rs
+//! let height = element.get_i32("height");
+//! let names = compound.get_list::<&str>("names").unwrap_or_default();
+//! ```
+
+use super::{NBTType, NBTCompound};
+
+/// Value which can be borrowed from NBT tag of matching type
+pub trait NBTValue<'a>: Sized {
+    /// Returns value if the tag has matching type (numbers are not converted)
+    fn from_nbt(nbt: &'a NBTType) -> Option<Self>;
+}
+
+macro_rules! impl_nbt_value {
+    ($type:ty, $variant:ident, $value:ident => $convert:expr) => {
+        impl<'a> NBTValue<'a> for $type {
+            fn from_nbt(nbt: &'a NBTType) -> Option<Self> {
+                match nbt {
+                    NBTType::$variant($value) => Some($convert),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_nbt_value!(i8, Byte, value => *value);
+impl_nbt_value!(bool, Byte, value => *value != 0);
+impl_nbt_value!(i16, Short, value => *value);
+impl_nbt_value!(i32, Int, value => *value);
+impl_nbt_value!(i64, Long, value => *value);
+impl_nbt_value!(f32, Float, value => *value);
+impl_nbt_value!(f64, Double, value => *value);
+impl_nbt_value!(&'a str, String, value => value.as_str());
+impl_nbt_value!(&'a [i8], ByteArray, value => value.as_slice());
+impl_nbt_value!(&'a [i32], IntArray, value => value.as_slice());
+impl_nbt_value!(&'a [i64], LongArray, value => value.as_slice());
+impl_nbt_value!(&'a [NBTType], List, value => value.as_slice());
+impl_nbt_value!(&'a NBTCompound, Compound, value => value);
+
+impl<'a> NBTValue<'a> for &'a NBTType {
+    fn from_nbt(nbt: &'a NBTType) -> Option<Self> {
+        Some(nbt)
+    }
+}
+
+impl NBTType {
+    /// Returns value of the tag if it has matching type
+    pub fn value<'a, T: NBTValue<'a>>(&'a self) -> Option<T> {
+        T::from_nbt(self)
+    }
+
+    pub fn as_compound(&self) -> Option<&NBTCompound> {
+        self.value()
+    }
+
+    pub fn as_list(&self) -> Option<&[NBTType]> {
+        self.value()
+    }
+}
+
+impl NBTCompound {
+    pub fn get(&self, key: &str) -> Option<&NBTType> {
+        self.data.get(key)
+    }
+
+    pub fn get_mut(&mut self, key: &str) -> Option<&mut NBTType> {
+        self.data.get_mut(key)
+    }
+
+    /// Inserts entry (existing entry keeps its position), returns previous value
+    pub fn insert(&mut self, key: impl Into<String>, value: NBTType) -> Option<NBTType> {
+        self.data.insert(key.into(), value)
+    }
+
+    /// Removes entry preserving order of the other entries
+    pub fn remove(&mut self, key: &str) -> Option<NBTType> {
+        self.data.shift_remove(key)
+    }
+
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.data.contains_key(key)
+    }
+
+    pub fn len(&self) -> usize {
+        self.data.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    /// Iterates over entries in insertion order
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &NBTType)> {
+        self.data.iter().map(|(key, value)| (key.as_str(), value))
+    }
+
+    /// Returns value of entry if it has matching type
+    pub fn get_value<'a, T: NBTValue<'a>>(&'a self, key: &str) -> Option<T> {
+        T::from_nbt(self.data.get(key)?)
+    }
+
+    pub fn get_i8(&self, key: &str) -> Option<i8> {
+        self.get_value(key)
+    }
+
+    /// Returns byte entry as boolean
+    pub fn get_bool(&self, key: &str) -> Option<bool> {
+        self.get_value(key)
+    }
+
+    pub fn get_i16(&self, key: &str) -> Option<i16> {
+        self.get_value(key)
+    }
+
+    pub fn get_i32(&self, key: &str) -> Option<i32> {
+        self.get_value(key)
+    }
+
+    pub fn get_i64(&self, key: &str) -> Option<i64> {
+        self.get_value(key)
+    }
+
+    pub fn get_f32(&self, key: &str) -> Option<f32> {
+        self.get_value(key)
+    }
+
+    pub fn get_f64(&self, key: &str) -> Option<f64> {
+        self.get_value(key)
+    }
+
+    pub fn get_str(&self, key: &str) -> Option<&str> {
+        self.get_value(key)
+    }
+
+    pub fn get_byte_array(&self, key: &str) -> Option<&[i8]> {
+        self.get_value(key)
+    }
+
+    pub fn get_int_array(&self, key: &str) -> Option<&[i32]> {
+        self.get_value(key)
+    }
+
+    pub fn get_long_array(&self, key: &str) -> Option<&[i64]> {
+        self.get_value(key)
+    }
+
+    pub fn get_compound(&self, key: &str) -> Option<&NBTCompound> {
+        self.get_value(key)
+    }
+
+    /// Returns elements of list entry (`None` if any element has different type)
+    ///
+    /// Empty lists are returned for any `T` since they don't store element type
+    pub fn get_list<'a, T: NBTValue<'a>>(&'a self, key: &str) -> Option<Vec<T>> {
+        let values: &[NBTType] = self.get_value(key)?;
+        values.iter().map(T::from_nbt).collect()
+    }
+}
+
+impl<K: Into<String>> FromIterator<(K, NBTType)> for NBTCompound {
+    fn from_iter<I: IntoIterator<Item = (K, NBTType)>>(iter: I) -> Self {
+        NBTCompound {
+            data: iter.into_iter().map(|(key, value)| (key.into(), value)).collect(),
+        }
+    }
+}
+
+impl<'a> IntoIterator for &'a NBTCompound {
+    type Item = (&'a String, &'a NBTType);
+    type IntoIter = indexmap::map::Iter<'a, String, NBTType>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.iter()
+    }
+}
+
+impl IntoIterator for NBTCompound {
+    type Item = (String, NBTType);
+    type IntoIter = indexmap::map::IntoIter<String, NBTType>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.data.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn typed_access() {
+        let nbt = NBTType::from_snbt(r#"{b:1b,s:2s,i:3,l:4L,f:0.5f,d:0.25d,str:"x",ba:[B;1B],ia:[I;2],la:[L;3L],c:{k:1},names:["a","b"],mixed:[[1],["a"]],empty:[]}"#).unwrap();
+        let compound = nbt.as_compound().unwrap();
+        assert_eq!(compound.get_i8("b"), Some(1));
+        assert_eq!(compound.get_bool("b"), Some(true));
+        assert_eq!(compound.get_i16("s"), Some(2));
+        assert_eq!(compound.get_i32("i"), Some(3));
+        assert_eq!(compound.get_i64("l"), Some(4));
+        assert_eq!(compound.get_f32("f"), Some(0.5));
+        assert_eq!(compound.get_f64("d"), Some(0.25));
+        assert_eq!(compound.get_str("str"), Some("x"));
+        assert_eq!(compound.get_byte_array("ba"), Some(&[1][..]));
+        assert_eq!(compound.get_int_array("ia"), Some(&[2][..]));
+        assert_eq!(compound.get_long_array("la"), Some(&[3][..]));
+        assert_eq!(compound.get_compound("c").and_then(|c| c.get_i32("k")), Some(1));
+        assert_eq!(compound.get_list::<&str>("names"), Some(vec!["a", "b"]));
+        assert_eq!(compound.get_list::<&[NBTType]>("mixed").map(|lists| lists.len()), Some(2));
+        assert_eq!(compound.get_list::<i32>("empty"), Some(vec![]));
+
+        // Numbers are not converted and missing entries are `None`
+        assert_eq!(compound.get_i32("b"), None);
+        assert_eq!(compound.get_f64("f"), None);
+        assert_eq!(compound.get_str("i"), None);
+        assert_eq!(compound.get_list::<i32>("names"), None);
+        assert_eq!(compound.get_list::<i32>("i"), None);
+        assert_eq!(compound.get_i32("missing"), None);
+        assert_eq!(NBTType::Int(1).as_compound(), None);
+        assert_eq!(NBTType::Int(1).value::<i32>(), Some(1));
+    }
+
+    #[test]
+    fn compound_order() {
+        let mut compound: NBTCompound = [("c", NBTType::Int(1)), ("a", NBTType::Int(2)), ("b", NBTType::Int(3))].into_iter().collect();
+        assert_eq!(compound.insert("a", NBTType::Int(4)), Some(NBTType::Int(2)));
+        assert_eq!(compound.insert("d", NBTType::Int(5)), None);
+        assert_eq!(compound.remove("c"), Some(NBTType::Int(1)));
+        assert_eq!(compound.remove("c"), None);
+        assert_eq!(compound.iter().map(|(key, _)| key).collect::<Vec<_>>(), vec!["a", "b", "d"]);
+        assert_eq!(compound.len(), 3);
+        assert!(compound.contains_key("d"));
+        assert!(!NBTCompound::new().contains_key("d") && NBTCompound::new().is_empty());
+    }
+}
\ No newline at end of file
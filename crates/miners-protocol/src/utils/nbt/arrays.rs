@@ -0,0 +1,40 @@
+//! Serde helpers for storing sequences as NBT arrays instead of lists
+//!
+//! # Example
+//! ```rs
+//! #[derive(Serialize, Deserialize)]
+//! struct Heightmaps {
+//!     #[serde(with = "nbt::arrays::long_array")]
+//!     motion_blocking: Vec<i64>,
+//! }
+//! ```
+//!
+//! Other serializers (e.g. JSON) store such fields as plain sequences
+
+/// Name of the newtype struct which is serialized as byte array
+pub(crate) const BYTE_ARRAY: &str = "$nbt:byte_array";
+/// Name of the newtype struct which is serialized as int array
+pub(crate) const INT_ARRAY: &str = "$nbt:int_array";
+/// Name of the newtype struct which is serialized as long array
+pub(crate) const LONG_ARRAY: &str = "$nbt:long_array";
+
+macro_rules! define_array {
+    ($module:ident, $name:ident, $type:ty, $doc:literal) => {
+        #[doc = $doc]
+        pub mod $module {
+            use serde::{Serializer, Deserialize, Deserializer};
+
+            pub fn serialize<S: Serializer>(values: &[$type], serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.serialize_newtype_struct(super::$name, values)
+            }
+
+            pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<$type>, D::Error> {
+                Vec::<$type>::deserialize(deserializer)
+            }
+        }
+    };
+}
+
+define_array!(byte_array, BYTE_ARRAY, i8, "Stores `Vec<i8>` as byte array");
+define_array!(int_array, INT_ARRAY, i32, "Stores `Vec<i32>` as int array");
+define_array!(long_array, LONG_ARRAY, i64, "Stores `Vec<i64>` as long array");
\ No newline at end of file
@@ -0,0 +1,387 @@
+//! Serde deserializer reading values from NBT
+//!
+//! Numeric tags are converted to the requested type (servers are not consistent about them, e.g. booleans are bytes),
+//! arrays can be read as sequences and enums are read from strings or compounds with the variant name as the only key.
+
+use std::vec;
+
+use indexmap::map;
+
+use serde::{de::{self, DeserializeOwned, IntoDeserializer, Visitor, SeqAccess, MapAccess, EnumAccess, VariantAccess}, forward_to_deserialize_any};
+
+use super::{NBTType, NBTError};
+
+/// Converts NBT into value
+///
+/// # Example
+/// ```rs
+/// #[derive(Deserialize)]
+/// struct DimensionType { min_y: i32, height: i32 }
+///
+/// let dimension_type: DimensionType = nbt::from_nbt(element)?;
+/// ```
+pub fn from_nbt<T: DeserializeOwned>(nbt: NBTType) -> Result<T, NBTError> {
+    T::deserialize(nbt)
+}
+
+impl NBTType {
+    /// Returns name of the tag type (used in error messages)
+    fn type_name(&self) -> &'static str {
+        match self {
+            NBTType::End => "end",
+            NBTType::Byte(_) => "byte",
+            NBTType::Short(_) => "short",
+            NBTType::Int(_) => "int",
+            NBTType::Long(_) => "long",
+            NBTType::Float(_) => "float",
+            NBTType::Double(_) => "double",
+            NBTType::ByteArray(_) => "byte array",
+            NBTType::String(_) => "string",
+            NBTType::List(_) => "list",
+            NBTType::Compound(_) => "compound",
+            NBTType::IntArray(_) => "int array",
+            NBTType::LongArray(_) => "long array",
+        }
+    }
+
+    fn invalid_type(&self, expected: &str) -> NBTError {
+        NBTError::new(format!("Expected {}, found {}", expected, self.type_name()))
+    }
+
+    /// Returns elements of list or array
+    fn into_elements(self) -> Result<Vec<NBTType>, NBTError> {
+        match self {
+            NBTType::List(values) => Ok(values),
+            NBTType::ByteArray(values) => Ok(values.into_iter().map(NBTType::Byte).collect()),
+            NBTType::IntArray(values) => Ok(values.into_iter().map(NBTType::Int).collect()),
+            NBTType::LongArray(values) => Ok(values.into_iter().map(NBTType::Long).collect()),
+            other => Err(other.invalid_type("list or array")),
+        }
+    }
+}
+
+/// Deserializes integer of any numeric tag (unsigned integers are read from signed tags of the same size)
+macro_rules! deserialize_integer {
+    ($method:ident, $visit:ident, $type:ty, $byte:ty, $short:ty, $int:ty, $long:ty) => {
+        fn $method<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+            match self {
+                NBTType::Byte(v) => visitor.$visit(v as $byte as $type),
+                NBTType::Short(v) => visitor.$visit(v as $short as $type),
+                NBTType::Int(v) => visitor.$visit(v as $int as $type),
+                NBTType::Long(v) => visitor.$visit(v as $long as $type),
+                NBTType::Float(v) => visitor.$visit(v as $type),
+                NBTType::Double(v) => visitor.$visit(v as $type),
+                other => Err(other.invalid_type("number")),
+            }
+        }
+    };
+}
+
+impl<'de> de::Deserializer<'de> for NBTType {
+    type Error = NBTError;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        match self {
+            NBTType::End => visitor.visit_unit(),
+            NBTType::Byte(v) => visitor.visit_i8(v),
+            NBTType::Short(v) => visitor.visit_i16(v),
+            NBTType::Int(v) => visitor.visit_i32(v),
+            NBTType::Long(v) => visitor.visit_i64(v),
+            NBTType::Float(v) => visitor.visit_f32(v),
+            NBTType::Double(v) => visitor.visit_f64(v),
+            NBTType::String(v) => visitor.visit_string(v),
+            NBTType::Compound(compound) => visitor.visit_map(CompoundAccess { entries: compound.data.into_iter(), value: None }),
+            other => visitor.visit_seq(ListAccess { values: other.into_elements()?.into_iter() }),
+        }
+    }
+
+    fn deserialize_bool<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        match self {
+            NBTType::Byte(v) => visitor.visit_bool(v != 0),
+            NBTType::Short(v) => visitor.visit_bool(v != 0),
+            NBTType::Int(v) => visitor.visit_bool(v != 0),
+            NBTType::Long(v) => visitor.visit_bool(v != 0),
+            other => Err(other.invalid_type("boolean")),
+        }
+    }
+
+    deserialize_integer!(deserialize_i8, visit_i64, i64, i8, i16, i32, i64);
+    deserialize_integer!(deserialize_i16, visit_i64, i64, i8, i16, i32, i64);
+    deserialize_integer!(deserialize_i32, visit_i64, i64, i8, i16, i32, i64);
+    deserialize_integer!(deserialize_i64, visit_i64, i64, i8, i16, i32, i64);
+    deserialize_integer!(deserialize_u8, visit_u64, u64, u8, u16, u32, u64);
+    deserialize_integer!(deserialize_u16, visit_u64, u64, u8, u16, u32, u64);
+    deserialize_integer!(deserialize_u32, visit_u64, u64, u8, u16, u32, u64);
+    deserialize_integer!(deserialize_u64, visit_u64, u64, u8, u16, u32, u64);
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        match self {
+            NBTType::Byte(v) => visitor.visit_f64(v as f64),
+            NBTType::Short(v) => visitor.visit_f64(v as f64),
+            NBTType::Int(v) => visitor.visit_f64(v as f64),
+            NBTType::Long(v) => visitor.visit_f64(v as f64),
+            NBTType::Float(v) => visitor.visit_f64(v as f64),
+            NBTType::Double(v) => visitor.visit_f64(v),
+            other => Err(other.invalid_type("number")),
+        }
+    }
+
+    fn deserialize_bytes<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        match self {
+            NBTType::ByteArray(values) => visitor.visit_byte_buf(values.into_iter().map(|v| v as u8).collect()),
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        match self {
+            NBTType::End => visitor.visit_none(),
+            other => visitor.visit_some(other),
+        }
+    }
+
+    fn deserialize_unit<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_unit_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, NBTError> {
+        visitor.visit_unit()
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(self, _name: &'static str, visitor: V) -> Result<V::Value, NBTError> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_seq<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        visitor.visit_seq(ListAccess { values: self.into_elements()?.into_iter() })
+    }
+
+    fn deserialize_tuple<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, NBTError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_tuple_struct<V: Visitor<'de>>(self, _name: &'static str, _len: usize, visitor: V) -> Result<V::Value, NBTError> {
+        self.deserialize_seq(visitor)
+    }
+
+    fn deserialize_map<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        match self {
+            NBTType::Compound(compound) => visitor.visit_map(CompoundAccess { entries: compound.data.into_iter(), value: None }),
+            other => Err(other.invalid_type("compound")),
+        }
+    }
+
+    fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, NBTError> {
+        self.deserialize_map(visitor)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, NBTError> {
+        match self {
+            NBTType::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            NBTType::Compound(compound) if compound.data.len() == 1 => {
+                let (variant, value) = compound.data.into_iter().next().unwrap();
+                visitor.visit_enum(VariantValue { variant, value })
+            },
+            other => Err(other.invalid_type("string or compound with a single key")),
+        }
+    }
+
+    fn deserialize_ignored_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, NBTError> {
+        visitor.visit_unit()
+    }
+
+    forward_to_deserialize_any! {
+        char str string identifier
+    }
+}
+
+impl<'de> IntoDeserializer<'de, NBTError> for NBTType {
+    type Deserializer = NBTType;
+
+    fn into_deserializer(self) -> NBTType {
+        self
+    }
+}
+
+/// Access to elements of list or array
+struct ListAccess {
+    values: vec::IntoIter<NBTType>,
+}
+
+impl<'de> SeqAccess<'de> for ListAccess {
+    type Error = NBTError;
+
+    fn next_element_seed<T: de::DeserializeSeed<'de>>(&mut self, seed: T) -> Result<Option<T::Value>, NBTError> {
+        self.values.next().map(|value| seed.deserialize(value)).transpose()
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.values.len())
+    }
+}
+
+/// Access to entries of compound
+struct CompoundAccess {
+    entries: map::IntoIter<String, NBTType>,
+    value: Option<NBTType>,
+}
+
+impl<'de> MapAccess<'de> for CompoundAccess {
+    type Error = NBTError;
+
+    fn next_key_seed<K: de::DeserializeSeed<'de>>(&mut self, seed: K) -> Result<Option<K::Value>, NBTError> {
+        let Some((key, value)) = self.entries.next() else {
+            return Ok(None);
+        };
+        self.value = Some(value);
+        seed.deserialize(key.into_deserializer()).map(Some)
+    }
+
+    fn next_value_seed<V: de::DeserializeSeed<'de>>(&mut self, seed: V) -> Result<V::Value, NBTError> {
+        let value = self.value.take().ok_or_else(|| NBTError::new("Compound value requested before its key"))?;
+        seed.deserialize(value)
+    }
+
+    fn size_hint(&self) -> Option<usize> {
+        Some(self.entries.len())
+    }
+}
+
+/// Enum variant stored as compound with the variant name as the only key
+struct VariantValue {
+    variant: String,
+    value: NBTType,
+}
+
+impl<'de> EnumAccess<'de> for VariantValue {
+    type Error = NBTError;
+    type Variant = NBTType;
+
+    fn variant_seed<V: de::DeserializeSeed<'de>>(self, seed: V) -> Result<(V::Value, NBTType), NBTError> {
+        let variant = seed.deserialize(self.variant.into_deserializer())?;
+        Ok((variant, self.value))
+    }
+}
+
+impl<'de> VariantAccess<'de> for NBTType {
+    type Error = NBTError;
+
+    fn unit_variant(self) -> Result<(), NBTError> {
+        Ok(())
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(self, seed: T) -> Result<T::Value, NBTError> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(self, _len: usize, visitor: V) -> Result<V::Value, NBTError> {
+        de::Deserializer::deserialize_seq(self, visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(self, _fields: &'static [&'static str], visitor: V) -> Result<V::Value, NBTError> {
+        de::Deserializer::deserialize_map(self, visitor)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{Serialize, Deserialize};
+
+    use super::*;
+    use crate::utils::nbt::{arrays, to_nbt};
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Chunk {
+        #[serde(rename = "DataVersion")]
+        data_version: i32,
+        status: Option<String>,
+        structures: Option<i64>,
+        heightmaps: Heightmaps,
+        sections: Vec<Section>,
+        unit: Shape,
+        newtype: Shape,
+        structure: Shape,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Heightmaps {
+        #[serde(with = "arrays::long_array")]
+        motion_blocking: Vec<i64>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    struct Section {
+        y: i8,
+        #[serde(with = "arrays::byte_array")]
+        sky_light: Vec<i8>,
+        #[serde(with = "arrays::int_array")]
+        biomes: Vec<i32>,
+    }
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Shape {
+        Empty,
+        Block(i32),
+        Box { size: i16 },
+    }
+
+    #[test]
+    fn round_trip() {
+        let chunk = Chunk {
+            data_version: 3120,
+            status: Some("full".to_string()),
+            structures: None,
+            heightmaps: Heightmaps { motion_blocking: vec![i64::MIN, 0, i64::MAX] },
+            sections: vec![
+                Section { y: -4, sky_light: vec![-1, 0, 15], biomes: vec![] },
+                Section { y: 0, sky_light: vec![], biomes: vec![1, 2] },
+            ],
+            unit: Shape::Empty,
+            newtype: Shape::Block(7),
+            structure: Shape::Box { size: -3 },
+        };
+
+        let nbt = to_nbt(&chunk).unwrap();
+        assert_eq!(nbt.into_snbt(), concat!(
+            r#"{DataVersion:3120,status:"full",heightmaps:{motion_blocking:[L;-9223372036854775808L,0L,9223372036854775807L]},"#,
+            r#"sections:[{y:-4b,sky_light:[B;-1B,0B,15B],biomes:[I;]},{y:0b,sky_light:[B;],biomes:[I;1,2]}],"#,
+            r#"unit:"Empty",newtype:{Block:7},structure:{Box:{size:-3s}}}"#,
+        ));
+
+        // Through the binary format
+        let bytes = nbt.to_bytes(Some("")).unwrap();
+        let (_, read) = NBTType::read_from(&mut bytes.as_slice()).unwrap();
+        assert_eq!(from_nbt::<Chunk>(read).unwrap(), chunk);
+    }
+
+    #[test]
+    fn numeric_conversions() {
+        // Numbers are converted to the requested type and arrays can be read as sequences
+        let nbt = NBTType::from_snbt("{y:1,sky_light:[1b,2b],biomes:[L;3L]}").unwrap();
+        assert_eq!(from_nbt::<Section>(nbt).unwrap(), Section { y: 1, sky_light: vec![1, 2], biomes: vec![3] });
+        assert!(from_nbt::<bool>(NBTType::Byte(1)).unwrap());
+    }
+
+    #[test]
+    fn deserialize_malformed() {
+        let error = |snbt: &str| from_nbt::<Section>(NBTType::from_snbt(snbt).unwrap()).unwrap_err().to_string();
+        assert_eq!(error("{y:'a',sky_light:[B;],biomes:[I;]}"), "Expected number, found string");
+        assert_eq!(error("{y:1,sky_light:1b,biomes:[I;]}"), "Expected list or array, found byte");
+        assert_eq!(error("{y:1,sky_light:[B;]}"), "missing field `biomes`");
+        assert_eq!(error("[]"), "Expected compound, found list");
+
+        let error = |snbt: &str| from_nbt::<Shape>(NBTType::from_snbt(snbt).unwrap()).unwrap_err().to_string();
+        assert_eq!(error("{Block:1,Box:{size:1s}}"), "Expected string or compound with a single key, found compound");
+        assert_eq!(error("{}"), "Expected string or compound with a single key, found compound");
+        assert_eq!(error("{Block:'a'}"), "Expected number, found string");
+        assert!(error("Sphere").starts_with("unknown variant `Sphere`"));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,503 @@
+use std::{fmt::Debug, fs, io::{self, Read, Write, Cursor}, path::Path};
+
+use flate2::{Compression, read::{GzDecoder, ZlibDecoder}, write::{GzEncoder, ZlibEncoder}};
+use indexmap::IndexMap;
+
+use crate::packet::RawPacket;
+
+pub mod access;
+pub mod arrays;
+pub mod de;
+pub mod path;
+pub mod ser;
+pub mod snbt;
+
+pub use self::{access::NBTValue, de::from_nbt, path::NBTPath, ser::to_nbt};
+
+/// Maximum depth of nested lists and compounds (same as vanilla)
+pub const MAX_DEPTH: usize = 512;
+
+/// Compression of NBT files
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NBTCompression {
+    None,
+    /// Used by `level.dat` and player data
+    Gzip,
+    /// Used by chunks in region files
+    Zlib,
+}
+
+/// Error of converting values to and from NBT
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NBTError {
+    pub message: String,
+}
+
+impl NBTError {
+    pub fn new(message: impl Into<String>) -> NBTError {
+        NBTError { message: message.into() }
+    }
+}
+
+impl std::fmt::Display for NBTError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for NBTError {}
+
+impl serde::ser::Error for NBTError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        NBTError::new(msg.to_string())
+    }
+}
+
+impl serde::de::Error for NBTError {
+    fn custom<T: std::fmt::Display>(msg: T) -> Self {
+        NBTError::new(msg.to_string())
+    }
+}
+
+/// Compound tag (entries keep insertion order)
+#[derive(Clone, Debug, PartialEq)]
+pub struct NBTCompound {
+    pub data: IndexMap<String, NBTType>
+}
+
+#[derive(Clone, PartialEq)]
+pub enum NBTType {
+    End,
+    Byte(i8),
+    Short(i16),
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    ByteArray(Vec<i8>),
+    String(String),
+    List(Vec<NBTType>),
+    Compound(NBTCompound),
+    IntArray(Vec<i32>),
+    LongArray(Vec<i64>),
+}
+
+impl Debug for NBTType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.into_snbt())
+    }
+}
+
+impl NBTCompound {
+    pub fn new() -> NBTCompound {
+        NBTCompound {
+            data: IndexMap::new()
+        }
+    }
+}
+
+impl NBTType {
+    pub fn from_packet(packet: &mut RawPacket) -> Result<NBTType, String> {
+        read_from_packet(packet, |reader| NBTType::read_from(reader).map(|(_, nbt)| nbt))
+    }
+
+    /// Reads NBT which may be absent (sent as a single `End` tag, e.g. in item slots)
+    pub fn from_packet_optional(packet: &mut RawPacket) -> Result<Option<NBTType>, String> {
+        read_from_packet(packet, |reader| {
+            let typeid = read_u8(reader)?;
+            if typeid == 0x00 {
+                return Ok(None);
+            }
+            let _name = read_modified_utf8(reader)?;
+            NBTType::read_payload(reader, typeid).map(Some)
+        })
+    }
+
+    pub fn from_packet_raw(packet: &mut RawPacket, typeid: u8) -> Result<NBTType, String> {
+        read_from_packet(packet, |reader| NBTType::read_payload(reader, typeid))
+    }
+
+    /// Returns id of the tag type
+    pub fn type_id(&self) -> u8 {
+        match self {
+            NBTType::End => 0x00,
+            NBTType::Byte(_) => 0x01,
+            NBTType::Short(_) => 0x02,
+            NBTType::Int(_) => 0x03,
+            NBTType::Long(_) => 0x04,
+            NBTType::Float(_) => 0x05,
+            NBTType::Double(_) => 0x06,
+            NBTType::ByteArray(_) => 0x07,
+            NBTType::String(_) => 0x08,
+            NBTType::List(_) => 0x09,
+            NBTType::Compound(_) => 0x0A,
+            NBTType::IntArray(_) => 0x0B,
+            NBTType::LongArray(_) => 0x0C,
+        }
+    }
+
+    // ====< Readers >====
+    /// Reads named root tag (used by files and by the network before 1.20.2), returns its name and value
+    pub fn read_from(reader: &mut impl Read) -> io::Result<(String, NBTType)> {
+        let typeid = read_u8(reader)?;
+        if typeid == 0x00 {
+            return Ok((String::new(), NBTType::End));
+        }
+        let name = read_modified_utf8(reader)?;
+        Ok((name, NBTType::read_payload(reader, typeid)?))
+    }
+
+    /// Reads root tag without a name (used by the network since 1.20.2)
+    pub fn read_nameless(reader: &mut impl Read) -> io::Result<NBTType> {
+        let typeid = read_u8(reader)?;
+        NBTType::read_payload(reader, typeid)
+    }
+
+    /// Reads payload of the tag with specified type
+    pub fn read_payload(reader: &mut impl Read, typeid: u8) -> io::Result<NBTType> {
+        NBTType::read_payload_at(reader, typeid, 0)
+    }
+
+    fn read_payload_at(reader: &mut impl Read, typeid: u8, depth: usize) -> io::Result<NBTType> {
+        if depth > MAX_DEPTH {
+            return Err(invalid_data(format!("NBT is nested deeper than {} levels", MAX_DEPTH)));
+        }
+
+        Ok(match typeid {
+            0x00 => NBTType::End,
+            0x01 => NBTType::Byte(read_u8(reader)? as i8),
+            0x02 => NBTType::Short(i16::from_be_bytes(read_array(reader)?)),
+            0x03 => NBTType::Int(i32::from_be_bytes(read_array(reader)?)),
+            0x04 => NBTType::Long(i64::from_be_bytes(read_array(reader)?)),
+            0x05 => NBTType::Float(f32::from_be_bytes(read_array(reader)?)),
+            0x06 => NBTType::Double(f64::from_be_bytes(read_array(reader)?)),
+            0x07 => {
+                let length = read_length(reader)?;
+                NBTType::ByteArray(read_bytes(reader, length)?.into_iter().map(|b| b as i8).collect())
+            },
+            0x08 => NBTType::String(read_modified_utf8(reader)?),
+            0x09 => {
+                let item_typeid = read_u8(reader)?;
+                let length = read_length(reader)?;
+                // End elements take no bytes, so their count wouldn't be limited by the input (same check as in vanilla)
+                if item_typeid == 0x00 && length > 0 {
+                    return Err(invalid_data(String::from("Missing type on ListTag")));
+                }
+                // List is not allocated upfront as the length is untrusted
+                let mut list = Vec::new();
+                for _ in 0..length {
+                    list.push(NBTType::read_payload_at(reader, item_typeid, depth + 1)?);
+                }
+                NBTType::List(list)
+            },
+            0x0A => {
+                let mut compound = NBTCompound::new();
+                loop {
+                    let typeid = read_u8(reader)?;
+                    if typeid == 0x00 {
+                        break;
+                    }
+                    let name = read_modified_utf8(reader)?;
+                    compound.data.insert(name, NBTType::read_payload_at(reader, typeid, depth + 1)?);
+                }
+                NBTType::Compound(compound)
+            },
+            0x0B => {
+                let length = read_length(reader)?;
+                let bytes = read_bytes(reader, length * 4)?;
+                NBTType::IntArray(bytes.chunks_exact(4).map(|b| i32::from_be_bytes([b[0], b[1], b[2], b[3]])).collect())
+            },
+            0x0C => {
+                let length = read_length(reader)?;
+                let bytes = read_bytes(reader, length * 8)?;
+                NBTType::LongArray(bytes.chunks_exact(8).map(|b| i64::from_be_bytes(b.try_into().unwrap())).collect())
+            },
+            _ => return Err(invalid_data(format!("Unknown NBT type: {}", typeid))),
+        })
+    }
+
+    /// Reads gzip compressed named root tag
+    pub fn read_gzip(reader: impl Read) -> io::Result<(String, NBTType)> {
+        NBTType::read_from(&mut GzDecoder::new(reader))
+    }
+
+    /// Reads zlib compressed named root tag
+    pub fn read_zlib(reader: impl Read) -> io::Result<(String, NBTType)> {
+        NBTType::read_from(&mut ZlibDecoder::new(reader))
+    }
+
+    /// Reads NBT file (e.g. `level.dat`), detecting its compression
+    pub fn read_file(path: impl AsRef<Path>) -> io::Result<(String, NBTType)> {
+        let data = fs::read(path)?;
+        match data.as_slice() {
+            [0x1F, 0x8B, ..] => NBTType::read_gzip(data.as_slice()),
+            [0x78, ..] => NBTType::read_zlib(data.as_slice()),
+            _ => NBTType::read_from(&mut data.as_slice()),
+        }
+    }
+
+    // ====< Writers >====
+    /// Writes root tag (with `name` or without it if `name` is `None`, as used by the network since 1.20.2)
+    ///
+    /// # Example
+    /// ```rs
+    /// let mut data = Vec::new();
+    /// nbt.write_to(&mut data, Some(""))?;
+    /// ```
+    pub fn write_to(&self, writer: &mut impl Write, name: Option<&str>) -> io::Result<()> {
+        writer.write_all(&[self.type_id()])?;
+        if let Some(name) = name {
+            write_modified_utf8(writer, name)?;
+        }
+        self.write_payload(writer)
+    }
+
+    /// Writes payload of the tag (without its type and name)
+    pub fn write_payload(&self, writer: &mut impl Write) -> io::Result<()> {
+        match self {
+            NBTType::End => Ok(()),
+            NBTType::Byte(value) => writer.write_all(&[*value as u8]),
+            NBTType::Short(value) => writer.write_all(&value.to_be_bytes()),
+            NBTType::Int(value) => writer.write_all(&value.to_be_bytes()),
+            NBTType::Long(value) => writer.write_all(&value.to_be_bytes()),
+            NBTType::Float(value) => writer.write_all(&value.to_be_bytes()),
+            NBTType::Double(value) => writer.write_all(&value.to_be_bytes()),
+            NBTType::ByteArray(values) => {
+                write_length(writer, values.len())?;
+                writer.write_all(&values.iter().map(|v| *v as u8).collect::<Vec<u8>>())
+            },
+            NBTType::String(value) => write_modified_utf8(writer, value),
+            NBTType::List(values) => {
+                let item_typeid = values.first().map_or(0x00, NBTType::type_id);
+                if values.iter().any(|value| value.type_id() != item_typeid) {
+                    return Err(io::Error::new(io::ErrorKind::InvalidInput, "NBT list contains values of different types"));
+                }
+                writer.write_all(&[item_typeid])?;
+                write_length(writer, values.len())?;
+                values.iter().try_for_each(|value| value.write_payload(writer))
+            },
+            NBTType::Compound(compound) => {
+                for (name, value) in &compound.data {
+                    value.write_to(writer, Some(name))?;
+                }
+                writer.write_all(&[0x00])
+            },
+            NBTType::IntArray(values) => {
+                write_length(writer, values.len())?;
+                writer.write_all(&values.iter().flat_map(|v| v.to_be_bytes()).collect::<Vec<u8>>())
+            },
+            NBTType::LongArray(values) => {
+                write_length(writer, values.len())?;
+                writer.write_all(&values.iter().flat_map(|v| v.to_be_bytes()).collect::<Vec<u8>>())
+            },
+        }
+    }
+
+    /// Returns root tag as bytes (see [`NBTType::write_to`])
+    pub fn to_bytes(&self, name: Option<&str>) -> io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        self.write_to(&mut data, name)?;
+        Ok(data)
+    }
+
+    /// Writes gzip compressed named root tag
+    pub fn write_gzip(&self, writer: impl Write, name: &str) -> io::Result<()> {
+        let mut encoder = GzEncoder::new(writer, Compression::default());
+        self.write_to(&mut encoder, Some(name))?;
+        encoder.finish().map(|_| ())
+    }
+
+    /// Writes zlib compressed named root tag
+    pub fn write_zlib(&self, writer: impl Write, name: &str) -> io::Result<()> {
+        let mut encoder = ZlibEncoder::new(writer, Compression::default());
+        self.write_to(&mut encoder, Some(name))?;
+        encoder.finish().map(|_| ())
+    }
+
+    /// Writes NBT file with named root tag
+    pub fn write_file(&self, path: impl AsRef<Path>, name: &str, compression: NBTCompression) -> io::Result<()> {
+        let mut data = Vec::new();
+        match compression {
+            NBTCompression::None => self.write_to(&mut data, Some(name))?,
+            NBTCompression::Gzip => self.write_gzip(&mut data, name)?,
+            NBTCompression::Zlib => self.write_zlib(&mut data, name)?,
+        }
+        fs::write(path, data)
+    }
+}
+
+/// Runs NBT reader on the packet data, removing read bytes from the packet
+fn read_from_packet<T>(packet: &mut RawPacket, read: impl FnOnce(&mut Cursor<&[u8]>) -> io::Result<T>) -> Result<T, String> {
+    let mut cursor = Cursor::new(packet.data.as_slice());
+    let result = read(&mut cursor).map_err(|e| e.to_string());
+    let read_bytes = cursor.position() as usize;
+    packet.data.drain(..read_bytes);
+    result
+}
+
+fn invalid_data(message: String) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn read_u8(reader: &mut impl Read) -> io::Result<u8> {
+    Ok(read_array::<1>(reader)?[0])
+}
+
+fn read_array<const N: usize>(reader: &mut impl Read) -> io::Result<[u8; N]> {
+    let mut buf = [0; N];
+    reader.read_exact(&mut buf)?;
+    Ok(buf)
+}
+
+/// Reads length of an array or a list (negative lengths are invalid)
+fn read_length(reader: &mut impl Read) -> io::Result<usize> {
+    let length = i32::from_be_bytes(read_array(reader)?);
+    usize::try_from(length).map_err(|_| invalid_data(format!("Negative NBT length: {}", length)))
+}
+
+fn write_length(writer: &mut impl Write, length: usize) -> io::Result<()> {
+    let length = i32::try_from(length).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "NBT array is too long"))?;
+    writer.write_all(&length.to_be_bytes())
+}
+
+/// Reads `n` bytes (without allocating all of them upfront, so invalid lengths don't exhaust memory)
+fn read_bytes(reader: &mut impl Read, n: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(n as u64).read_to_end(&mut bytes)?;
+    if bytes.len() != n {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "NBT data ended unexpectedly"));
+    }
+    Ok(bytes)
+}
+
+/// Reads string encoded with Java modified UTF-8 (prefixed with its length as unsigned short)
+pub fn read_modified_utf8(reader: &mut impl Read) -> io::Result<String> {
+    let length = u16::from_be_bytes(read_array(reader)?) as usize;
+    let bytes = read_bytes(reader, length)?;
+    decode_modified_utf8(&bytes).ok_or_else(|| invalid_data(String::from("Invalid modified UTF-8 string")))
+}
+
+/// Writes string encoded with Java modified UTF-8 (prefixed with its length as unsigned short)
+pub fn write_modified_utf8(writer: &mut impl Write, value: &str) -> io::Result<()> {
+    let bytes = encode_modified_utf8(value);
+    let length = u16::try_from(bytes.len()).map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "NBT string is too long"))?;
+    writer.write_all(&length.to_be_bytes())?;
+    writer.write_all(&bytes)
+}
+
+/// Encodes string with Java modified UTF-8
+///
+/// Differs from UTF-8 in encoding of null character (2 bytes) and supplementary characters (encoded surrogate pairs)
+pub fn encode_modified_utf8(value: &str) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(value.len());
+    for unit in value.encode_utf16() {
+        match unit {
+            0x0001..=0x007F => bytes.push(unit as u8),
+            0x0000 | 0x0080..=0x07FF => bytes.extend([0xC0 | (unit >> 6) as u8, 0x80 | (unit & 0x3F) as u8]),
+            _ => bytes.extend([0xE0 | (unit >> 12) as u8, 0x80 | ((unit >> 6) & 0x3F) as u8, 0x80 | (unit & 0x3F) as u8]),
+        }
+    }
+    bytes
+}
+
+/// Decodes string encoded with Java modified UTF-8 (`None` if it's invalid)
+pub fn decode_modified_utf8(bytes: &[u8]) -> Option<String> {
+    let mut units = Vec::with_capacity(bytes.len());
+    let mut iter = bytes.iter();
+    while let Some(&byte) = iter.next() {
+        let mut continuation = || iter.next().filter(|b| *b & 0xC0 == 0x80).map(|b| (*b & 0x3F) as u16);
+        units.push(match byte {
+            0x01..=0x7F => byte as u16,
+            0xC0..=0xDF => ((byte & 0x1F) as u16) << 6 | continuation()?,
+            0xE0..=0xEF => ((byte & 0x0F) as u16) << 12 | continuation()? << 6 | continuation()?,
+            _ => return None,
+        });
+    }
+    String::from_utf16(&units).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> NBTType {
+        let mut inner = NBTCompound::new();
+        inner.data.insert(String::from("name"), NBTType::String(String::from("Steve \0 \u{1F600} ż")));
+        inner.data.insert(String::from("empty"), NBTType::List(Vec::new()));
+
+        let mut root = NBTCompound::new();
+        root.data.insert(String::from("byte"), NBTType::Byte(-1));
+        root.data.insert(String::from("short"), NBTType::Short(300));
+        root.data.insert(String::from("int"), NBTType::Int(-70000));
+        root.data.insert(String::from("long"), NBTType::Long(i64::MIN));
+        root.data.insert(String::from("float"), NBTType::Float(0.5));
+        root.data.insert(String::from("double"), NBTType::Double(-1.25));
+        root.data.insert(String::from("bytes"), NBTType::ByteArray(vec![1, -2, 3]));
+        root.data.insert(String::from("ints"), NBTType::IntArray(vec![i32::MAX, 0]));
+        root.data.insert(String::from("longs"), NBTType::LongArray(vec![1, -1]));
+        root.data.insert(String::from("list"), NBTType::List(vec![NBTType::Compound(inner.clone()), NBTType::Compound(inner)]));
+        NBTType::Compound(root)
+    }
+
+    #[test]
+    fn modified_utf8() {
+        // Null is encoded with 2 bytes and supplementary characters as surrogate pairs (3 bytes each)
+        assert_eq!(encode_modified_utf8("a\0"), vec![0x61, 0xC0, 0x80]);
+        assert_eq!(encode_modified_utf8("\u{1F600}"), vec![0xED, 0xA0, 0xBD, 0xED, 0xB8, 0x80]);
+        assert_eq!(encode_modified_utf8("ż"), "ż".as_bytes());
+
+        for value in ["", "hello", "a\0b", "zażółć", "\u{1F600}\u{FFFF}"] {
+            assert_eq!(decode_modified_utf8(&encode_modified_utf8(value)).as_deref(), Some(value));
+        }
+
+        // Raw null, truncated sequence, invalid continuation and unpaired surrogate
+        assert_eq!(decode_modified_utf8(&[0x00]), None);
+        assert_eq!(decode_modified_utf8(&[0xC5]), None);
+        assert_eq!(decode_modified_utf8(&[0xC5, 0x41]), None);
+        assert_eq!(decode_modified_utf8(&[0xED, 0xA0, 0xBD]), None);
+    }
+
+    #[test]
+    fn write_read_round_trip() {
+        let nbt = sample();
+        let bytes = nbt.to_bytes(Some("root")).unwrap();
+        assert_eq!(NBTType::read_from(&mut bytes.as_slice()).unwrap(), (String::from("root"), nbt.clone()));
+
+        let mut packet = RawPacket::new(0, nbt.to_bytes(Some("")).unwrap());
+        assert_eq!(NBTType::from_packet(&mut packet).unwrap(), nbt);
+        assert!(packet.data.is_empty());
+
+        let mut compressed = Vec::new();
+        nbt.write_gzip(&mut compressed, "level").unwrap();
+        assert_eq!(NBTType::read_gzip(compressed.as_slice()).unwrap().1, nbt);
+    }
+
+    #[test]
+    fn read_end_list() {
+        // Empty list of End tags is valid, but End tags with elements are not
+        let empty = [0x09, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00];
+        assert_eq!(NBTType::read_from(&mut empty.as_slice()).unwrap().1, NBTType::List(Vec::new()));
+
+        let elements = [0x09, 0x00, 0x00, 0x00, 0x7F, 0xFF, 0xFF, 0xFF];
+        assert_eq!(NBTType::read_from(&mut elements.as_slice()).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn read_malformed() {
+        let bytes = sample().to_bytes(Some("root")).unwrap();
+        // Every truncation of valid NBT fails
+        for length in 1..bytes.len() {
+            assert!(NBTType::read_from(&mut &bytes[..length]).is_err());
+        }
+
+        // Unknown type, negative length and huge array length
+        assert!(NBTType::read_payload(&mut [].as_slice(), 0x0D).is_err());
+        assert!(NBTType::read_payload(&mut [0xFF, 0xFF, 0xFF, 0xFF].as_slice(), 0x07).is_err());
+        assert!(NBTType::read_payload(&mut [0x7F, 0xFF, 0xFF, 0xFF, 0x01].as_slice(), 0x0C).is_err());
+
+        // Nesting deeper than the limit
+        let nested = [0x09, 0x00, 0x00, 0x00, 0x01].repeat(MAX_DEPTH + 2);
+        let error = NBTType::read_payload(&mut nested.as_slice(), 0x09).unwrap_err();
+        assert!(error.to_string().contains("nested deeper"));
+    }
+}
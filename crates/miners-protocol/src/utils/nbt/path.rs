@@ -0,0 +1,229 @@
+//! Paths selecting values inside NBT (similar to paths used by vanilla `/data` command)
+//!
+//! Supported nodes:
+//! - `key` or `"quoted key"` - entry of compound
+//! - `key{filter}` - entry of compound if it matches filter
+//! - `{filter}` - root compound if it matches filter (only at the start)
+//! - `[index]` - element of list (negative index counts from the end)
+//! - `[]` - all elements of list
+//! - `[{filter}]` - compound elements of list which match filter
+//!
+//! # Example
+//! ```rs
+//! let height = codec.path("minecraft:dimension_type.value[0].element.height").and_then(NBTType::value::<i32>);
+//! let nether = codec.path(r#"minecraft:dimension_type.value[{name:"minecraft:the_nether"}].element"#);
+//! ```
+
+use std::str::FromStr;
+
+use super::{NBTType, NBTError, snbt::SnbtParser};
+
+/// Parsed NBT path
+#[derive(Debug, Clone, PartialEq)]
+pub struct NBTPath {
+    nodes: Vec<PathNode>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum PathNode {
+    RootFilter(NBTType),
+    Key(String),
+    KeyFilter(String, NBTType),
+    Index(i32),
+    AllElements,
+    ElementFilter(NBTType),
+}
+
+impl NBTPath {
+    pub fn parse(path: &str) -> Result<NBTPath, NBTError> {
+        let mut parser = SnbtParser { input: path, position: 0 };
+        let mut nodes = Vec::new();
+        loop {
+            nodes.push(read_node(&mut parser, nodes.is_empty())?);
+            match parser.peek() {
+                None => break,
+                Some('.') => {
+                    parser.next();
+                    if parser.peek().is_none() {
+                        return Err(parser.error("Expected path node"));
+                    }
+                },
+                Some('[' | '{') => {},
+                Some(_) => return Err(parser.error("Expected '.'")),
+            }
+        }
+        Ok(NBTPath { nodes })
+    }
+
+    /// Returns all values selected by the path
+    pub fn get<'a>(&self, nbt: &'a NBTType) -> Vec<&'a NBTType> {
+        self.nodes.iter().fold(vec![nbt], |values, node| {
+            values.into_iter().flat_map(|value| node.select(value)).collect()
+        })
+    }
+
+    /// Returns the first value selected by the path
+    pub fn get_first<'a>(&self, nbt: &'a NBTType) -> Option<&'a NBTType> {
+        self.get(nbt).into_iter().next()
+    }
+}
+
+impl FromStr for NBTPath {
+    type Err = NBTError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NBTPath::parse(s)
+    }
+}
+
+impl NBTType {
+    /// Returns the first value selected by path (`None` if nothing matches or the path is invalid)
+    ///
+    /// # Example
+    /// ```rs
+    /// let element = codec.path("minecraft:dimension_type.value[0].element");
+    /// ```
+    pub fn path(&self, path: &str) -> Option<&NBTType> {
+        NBTPath::parse(path).ok()?.get_first(self)
+    }
+}
+
+impl PathNode {
+    fn select<'a>(&self, value: &'a NBTType) -> Vec<&'a NBTType> {
+        match self {
+            PathNode::RootFilter(filter) => [value].into_iter().filter(|value| matches_filter(filter, value)).collect(),
+            PathNode::Key(key) => value.as_compound().and_then(|compound| compound.get(key)).into_iter().collect(),
+            PathNode::KeyFilter(key, filter) => {
+                value.as_compound().and_then(|compound| compound.get(key)).filter(|value| matches_filter(filter, value)).into_iter().collect()
+            },
+            PathNode::Index(index) => {
+                let Some(values) = value.as_list() else {
+                    return Vec::new();
+                };
+                let index = if *index < 0 { values.len() as i64 + *index as i64 } else { *index as i64 };
+                usize::try_from(index).ok().and_then(|index| values.get(index)).into_iter().collect()
+            },
+            PathNode::AllElements => value.as_list().unwrap_or_default().iter().collect(),
+            PathNode::ElementFilter(filter) => value.as_list().unwrap_or_default().iter().filter(|value| matches_filter(filter, value)).collect(),
+        }
+    }
+}
+
+/// Returns whether value matches filter (compounds match if they contain all filter entries
+/// and lists match if they contain all filter elements, same as in vanilla)
+fn matches_filter(filter: &NBTType, value: &NBTType) -> bool {
+    match (filter, value) {
+        (NBTType::Compound(filter), NBTType::Compound(value)) => {
+            filter.iter().all(|(key, filter)| value.get(key).is_some_and(|value| matches_filter(filter, value)))
+        },
+        (NBTType::Compound(_), _) => false,
+        (NBTType::List(filter), NBTType::List(values)) => {
+            if filter.is_empty() {
+                return values.is_empty();
+            }
+            filter.iter().all(|filter| values.iter().any(|value| matches_filter(filter, value)))
+        },
+        (NBTType::List(_), _) => false,
+        _ => filter == value,
+    }
+}
+
+/// Returns whether character ends unquoted key
+fn ends_key(c: char) -> bool {
+    c.is_whitespace() || matches!(c, '.' | '[' | ']' | '{' | '}' | '"' | '\'')
+}
+
+fn read_node(parser: &mut SnbtParser, root: bool) -> Result<PathNode, NBTError> {
+    match parser.peek() {
+        Some('{') if root => parser.read_compound(0).map(PathNode::RootFilter),
+        Some('{') => Err(parser.error("Filters can only follow keys")),
+        Some('[') => {
+            parser.next();
+            let node = match parser.peek() {
+                Some(']') => PathNode::AllElements,
+                Some('{') => PathNode::ElementFilter(parser.read_compound(0)?),
+                _ => {
+                    let start = parser.position;
+                    while parser.peek().is_some_and(|c| c == '-' || c.is_ascii_digit()) {
+                        parser.next();
+                    }
+                    let index = parser.input[start..parser.position].parse().map_err(|_| {
+                        parser.position = start;
+                        parser.error("Expected list index")
+                    })?;
+                    PathNode::Index(index)
+                },
+            };
+            parser.expect(']')?;
+            Ok(node)
+        },
+        Some('"' | '\'') => {
+            let key = parser.read_quoted()?;
+            read_key_filter(parser, key)
+        },
+        _ => {
+            let start = parser.position;
+            while parser.peek().is_some_and(|c| !ends_key(c)) {
+                parser.next();
+            }
+            if start == parser.position {
+                return Err(parser.error("Expected path node"));
+            }
+            let key = parser.input[start..parser.position].to_string();
+            read_key_filter(parser, key)
+        },
+    }
+}
+
+fn read_key_filter(parser: &mut SnbtParser, key: String) -> Result<PathNode, NBTError> {
+    match parser.peek() {
+        Some('{') => Ok(PathNode::KeyFilter(key, parser.read_compound(0)?)),
+        _ => Ok(PathNode::Key(key)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> NBTType {
+        NBTType::from_snbt(r#"{a:{b:[1,2,3]},"with space":{x:5b},entities:[{id:"cow",age:1},{id:"pig",age:2},{id:"cow",age:3}],empty:[]}"#).unwrap()
+    }
+
+    fn select(path: &str) -> Vec<NBTType> {
+        NBTPath::parse(path).unwrap().get(&sample()).into_iter().cloned().collect()
+    }
+
+    #[test]
+    fn select_values() {
+        assert_eq!(select("a.b[0]"), vec![NBTType::Int(1)]);
+        assert_eq!(select("a.b[-1]"), vec![NBTType::Int(3)]);
+        assert_eq!(select("a.b[]"), vec![NBTType::Int(1), NBTType::Int(2), NBTType::Int(3)]);
+        assert_eq!(select(r#""with space".x"#), vec![NBTType::Byte(5)]);
+        assert_eq!(select("'with space'.x"), vec![NBTType::Byte(5)]);
+        assert_eq!(select(r#"entities[{id:"cow"}].age"#), vec![NBTType::Int(1), NBTType::Int(3)]);
+        assert_eq!(select("entities[].id"), select("entities[{}].id"));
+        assert_eq!(select("a{b:[2]}.b[1]"), vec![NBTType::Int(2)]);
+        assert_eq!(select("{empty:[]}.a.b[2]"), vec![NBTType::Int(3)]);
+
+        // Nothing matches
+        assert!(select("a.b[3]").is_empty());
+        assert!(select("a.b[-4]").is_empty());
+        assert!(select("a{b:[4]}").is_empty());
+        assert!(select("a.b.c").is_empty());
+        assert!(select("a[0]").is_empty());
+        assert!(select("empty[0]").is_empty());
+
+        assert_eq!(sample().path("entities[1].id"), Some(&NBTType::String("pig".to_string())));
+        assert_eq!(sample().path("entities[1"), None);
+    }
+
+    #[test]
+    fn parse_malformed() {
+        for path in ["", "a.", "a[", "a[x]", "a[0", "a[0]{b:1}", "{a:1}{b:2}", "a..b", "a b", "a.{b:1}"] {
+            assert!(NBTPath::parse(path).is_err(), "{} should be rejected", path);
+        }
+        assert_eq!(NBTPath::parse("a[0]{b:1}").unwrap_err().to_string(), "Filters can only follow keys at position 4");
+        assert_eq!(NBTPath::parse("a[x]").unwrap_err().to_string(), "Expected list index at position 2");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,429 @@
+//! Serde serializer converting values into NBT
+//!
+//! Structs and maps become compounds (`None` fields are omitted), sequences become lists,
+//! enums become strings (unit variants) or compounds with the variant name as the only key.
+//! Unsigned integers are stored in signed tags of the same size.
+
+use serde::{Serialize, ser::{self, Impossible}};
+
+use super::{NBTType, NBTCompound, NBTError, arrays::{BYTE_ARRAY, INT_ARRAY, LONG_ARRAY}};
+
+/// Converts value into NBT
+///
+/// # Example
+/// ```rs
+/// #[derive(Serialize)]
+/// struct Display { #[serde(rename = "Name")] name: String }
+///
+/// let nbt = nbt::to_nbt(&Display { name: String::from("Sword") })?;
+/// ```
+pub fn to_nbt<T: Serialize + ?Sized>(value: &T) -> Result<NBTType, NBTError> {
+    match value.serialize(NBTSerializer)? {
+        NBTType::End => Err(NBTError::new("Root value can't be empty")),
+        nbt => Ok(nbt),
+    }
+}
+
+/// Serializer producing [`NBTType`]
+///
+/// Empty values (`None` and `()`) are serialized as [`NBTType::End`], which is skipped in compounds
+pub struct NBTSerializer;
+
+impl ser::Serializer for NBTSerializer {
+    type Ok = NBTType;
+    type Error = NBTError;
+    type SerializeSeq = ListSerializer;
+    type SerializeTuple = ListSerializer;
+    type SerializeTupleStruct = ListSerializer;
+    type SerializeTupleVariant = VariantSerializer<ListSerializer>;
+    type SerializeMap = CompoundSerializer;
+    type SerializeStruct = CompoundSerializer;
+    type SerializeStructVariant = VariantSerializer<CompoundSerializer>;
+
+    fn serialize_bool(self, v: bool) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Byte(v as i8))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Byte(v))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Short(v))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Int(v))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Long(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Byte(v as i8))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Short(v as i16))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Int(v as i32))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Long(v as i64))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Float(v))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Double(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<NBTType, NBTError> {
+        Ok(NBTType::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<NBTType, NBTError> {
+        Ok(NBTType::String(v.to_string()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<NBTType, NBTError> {
+        Ok(NBTType::ByteArray(v.iter().map(|b| *b as i8).collect()))
+    }
+
+    fn serialize_none(self) -> Result<NBTType, NBTError> {
+        Ok(NBTType::End)
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<NBTType, NBTError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<NBTType, NBTError> {
+        Ok(NBTType::End)
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Compound(NBTCompound::new()))
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<NBTType, NBTError> {
+        Ok(NBTType::String(variant.to_string()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, name: &'static str, value: &T) -> Result<NBTType, NBTError> {
+        let value = value.serialize(self)?;
+        match name {
+            BYTE_ARRAY => into_array(value, name, |v| match v { NBTType::Byte(v) => Some(v), _ => None }).map(NBTType::ByteArray),
+            INT_ARRAY => into_array(value, name, |v| match v { NBTType::Int(v) => Some(v), _ => None }).map(NBTType::IntArray),
+            LONG_ARRAY => into_array(value, name, |v| match v { NBTType::Long(v) => Some(v), _ => None }).map(NBTType::LongArray),
+            _ => Ok(value),
+        }
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, variant: &'static str, value: &T) -> Result<NBTType, NBTError> {
+        let mut compound = NBTCompound::new();
+        compound.data.insert(variant.to_string(), value.serialize(self)?);
+        Ok(NBTType::Compound(compound))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<ListSerializer, NBTError> {
+        Ok(ListSerializer { values: Vec::with_capacity(len.unwrap_or_default()) })
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<ListSerializer, NBTError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_struct(self, _name: &'static str, len: usize) -> Result<ListSerializer, NBTError> {
+        self.serialize_seq(Some(len))
+    }
+
+    fn serialize_tuple_variant(self, _name: &'static str, _index: u32, variant: &'static str, len: usize) -> Result<VariantSerializer<ListSerializer>, NBTError> {
+        Ok(VariantSerializer { variant, inner: self.serialize_seq(Some(len))? })
+    }
+
+    fn serialize_map(self, _len: Option<usize>) -> Result<CompoundSerializer, NBTError> {
+        Ok(CompoundSerializer { compound: NBTCompound::new(), key: None })
+    }
+
+    fn serialize_struct(self, _name: &'static str, len: usize) -> Result<CompoundSerializer, NBTError> {
+        self.serialize_map(Some(len))
+    }
+
+    fn serialize_struct_variant(self, _name: &'static str, _index: u32, variant: &'static str, len: usize) -> Result<VariantSerializer<CompoundSerializer>, NBTError> {
+        Ok(VariantSerializer { variant, inner: self.serialize_map(Some(len))? })
+    }
+}
+
+/// Converts list into array using `convert` for its elements
+fn into_array<T>(value: NBTType, name: &str, convert: impl Fn(NBTType) -> Option<T>) -> Result<Vec<T>, NBTError> {
+    let NBTType::List(values) = value else {
+        return Err(NBTError::new(format!("Value of {} is not a sequence", name)));
+    };
+    values.into_iter().map(|v| convert(v).ok_or_else(|| NBTError::new(format!("Invalid element of {}", name)))).collect()
+}
+
+/// Serializer of sequences into lists (all elements must have the same type)
+pub struct ListSerializer {
+    values: Vec<NBTType>,
+}
+
+impl ListSerializer {
+    fn push<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NBTError> {
+        let value = value.serialize(NBTSerializer)?;
+        if value.type_id() == 0x00 {
+            return Err(NBTError::new("Lists can't contain empty values"));
+        }
+        if self.values.first().is_some_and(|first| first.type_id() != value.type_id()) {
+            return Err(NBTError::new("Lists can't contain values of different types"));
+        }
+        self.values.push(value);
+        Ok(())
+    }
+}
+
+impl ser::SerializeSeq for ListSerializer {
+    type Ok = NBTType;
+    type Error = NBTError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NBTError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<NBTType, NBTError> {
+        Ok(NBTType::List(self.values))
+    }
+}
+
+impl ser::SerializeTuple for ListSerializer {
+    type Ok = NBTType;
+    type Error = NBTError;
+
+    fn serialize_element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NBTError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<NBTType, NBTError> {
+        Ok(NBTType::List(self.values))
+    }
+}
+
+impl ser::SerializeTupleStruct for ListSerializer {
+    type Ok = NBTType;
+    type Error = NBTError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NBTError> {
+        self.push(value)
+    }
+
+    fn end(self) -> Result<NBTType, NBTError> {
+        Ok(NBTType::List(self.values))
+    }
+}
+
+/// Serializer of maps and structs into compounds
+pub struct CompoundSerializer {
+    compound: NBTCompound,
+    key: Option<String>,
+}
+
+impl CompoundSerializer {
+    fn insert<T: Serialize + ?Sized>(&mut self, key: String, value: &T) -> Result<(), NBTError> {
+        let value = value.serialize(NBTSerializer)?;
+        // Empty values (e.g. `None`) are omitted
+        if value.type_id() != 0x00 {
+            self.compound.data.insert(key, value);
+        }
+        Ok(())
+    }
+}
+
+impl ser::SerializeMap for CompoundSerializer {
+    type Ok = NBTType;
+    type Error = NBTError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), NBTError> {
+        self.key = Some(key.serialize(KeySerializer)?);
+        Ok(())
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NBTError> {
+        let key = self.key.take().ok_or_else(|| NBTError::new("Map value serialized before its key"))?;
+        self.insert(key, value)
+    }
+
+    fn end(self) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Compound(self.compound))
+    }
+}
+
+impl ser::SerializeStruct for CompoundSerializer {
+    type Ok = NBTType;
+    type Error = NBTError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), NBTError> {
+        self.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<NBTType, NBTError> {
+        Ok(NBTType::Compound(self.compound))
+    }
+}
+
+/// Serializer of enum variants with data (wraps the data in compound with the variant name as the only key)
+pub struct VariantSerializer<S> {
+    variant: &'static str,
+    inner: S,
+}
+
+impl<S> VariantSerializer<S> {
+    fn wrap(variant: &str, value: NBTType) -> NBTType {
+        let mut compound = NBTCompound::new();
+        compound.data.insert(variant.to_string(), value);
+        NBTType::Compound(compound)
+    }
+}
+
+impl ser::SerializeTupleVariant for VariantSerializer<ListSerializer> {
+    type Ok = NBTType;
+    type Error = NBTError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), NBTError> {
+        self.inner.push(value)
+    }
+
+    fn end(self) -> Result<NBTType, NBTError> {
+        Ok(Self::wrap(self.variant, NBTType::List(self.inner.values)))
+    }
+}
+
+impl ser::SerializeStructVariant for VariantSerializer<CompoundSerializer> {
+    type Ok = NBTType;
+    type Error = NBTError;
+
+    fn serialize_field<T: Serialize + ?Sized>(&mut self, key: &'static str, value: &T) -> Result<(), NBTError> {
+        self.inner.insert(key.to_string(), value)
+    }
+
+    fn end(self) -> Result<NBTType, NBTError> {
+        Ok(Self::wrap(self.variant, NBTType::Compound(self.inner.compound)))
+    }
+}
+
+/// Serializer of map keys (only strings and chars are allowed)
+struct KeySerializer;
+
+fn key_error() -> NBTError {
+    NBTError::new("Compound keys must be strings")
+}
+
+impl ser::Serializer for KeySerializer {
+    type Ok = String;
+    type Error = NBTError;
+    type SerializeSeq = Impossible<String, NBTError>;
+    type SerializeTuple = Impossible<String, NBTError>;
+    type SerializeTupleStruct = Impossible<String, NBTError>;
+    type SerializeTupleVariant = Impossible<String, NBTError>;
+    type SerializeMap = Impossible<String, NBTError>;
+    type SerializeStruct = Impossible<String, NBTError>;
+    type SerializeStructVariant = Impossible<String, NBTError>;
+
+    fn serialize_str(self, v: &str) -> Result<String, NBTError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_char(self, v: char) -> Result<String, NBTError> {
+        Ok(v.to_string())
+    }
+
+    fn serialize_unit_variant(self, _name: &'static str, _index: u32, variant: &'static str) -> Result<String, NBTError> {
+        Ok(variant.to_string())
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(self, _name: &'static str, value: &T) -> Result<String, NBTError> {
+        value.serialize(self)
+    }
+
+    fn serialize_bool(self, _v: bool) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_i8(self, _v: i8) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_i16(self, _v: i16) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_i32(self, _v: i32) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_i64(self, _v: i64) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_u8(self, _v: u8) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_u16(self, _v: u16) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_u32(self, _v: u32) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_u64(self, _v: u64) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_f32(self, _v: f32) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_f64(self, _v: f64) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_bytes(self, _v: &[u8]) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_none(self) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_some<T: Serialize + ?Sized>(self, _value: &T) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_unit(self) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(self, _name: &'static str, _index: u32, _variant: &'static str, _value: &T) -> Result<String, NBTError> { Err(key_error()) }
+    fn serialize_seq(self, _len: Option<usize>) -> Result<Self::SerializeSeq, NBTError> { Err(key_error()) }
+    fn serialize_tuple(self, _len: usize) -> Result<Self::SerializeTuple, NBTError> { Err(key_error()) }
+    fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeTupleStruct, NBTError> { Err(key_error()) }
+    fn serialize_tuple_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeTupleVariant, NBTError> { Err(key_error()) }
+    fn serialize_map(self, _len: Option<usize>) -> Result<Self::SerializeMap, NBTError> { Err(key_error()) }
+    fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self::SerializeStruct, NBTError> { Err(key_error()) }
+    fn serialize_struct_variant(self, _name: &'static str, _index: u32, _variant: &'static str, _len: usize) -> Result<Self::SerializeStructVariant, NBTError> { Err(key_error()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use serde::Serialize;
+
+    use super::*;
+    use crate::utils::nbt::arrays;
+
+    #[derive(Serialize)]
+    struct Item {
+        id: String,
+        #[serde(rename = "Count")]
+        count: u8,
+        tag: Option<BTreeMap<String, i32>>,
+        #[serde(with = "arrays::int_array")]
+        ints: Vec<i32>,
+    }
+
+    #[derive(Serialize)]
+    enum Shape {
+        Empty,
+        Block(i32),
+        Pair(i8, i8),
+        Box { size: i16 },
+    }
+
+    #[test]
+    fn serialize() {
+        // `None` fields are omitted and unsigned integers wrap around
+        let item = Item { id: "minecraft:stone".to_string(), count: 200, tag: None, ints: vec![1, 2] };
+        assert_eq!(to_nbt(&item).unwrap().into_snbt(), r#"{id:"minecraft:stone",Count:-56b,ints:[I;1,2]}"#);
+
+        let tag = BTreeMap::from([("Damage".to_string(), 3)]);
+        let item = Item { id: "minecraft:diamond_sword".to_string(), count: 1, tag: Some(tag), ints: vec![] };
+        assert_eq!(to_nbt(&item).unwrap().into_snbt(), r#"{id:"minecraft:diamond_sword",Count:1b,tag:{Damage:3},ints:[I;]}"#);
+
+        assert_eq!(to_nbt(&Shape::Empty).unwrap(), NBTType::String("Empty".to_string()));
+        assert_eq!(to_nbt(&Shape::Block(1)).unwrap().into_snbt(), "{Block:1}");
+        assert_eq!(to_nbt(&Shape::Pair(1, 2)).unwrap().into_snbt(), "{Pair:[1b,2b]}");
+        assert_eq!(to_nbt(&Shape::Box { size: 2 }).unwrap().into_snbt(), "{Box:{size:2s}}");
+        assert_eq!(to_nbt(&(true, 'c')).unwrap_err().to_string(), "Lists can't contain values of different types");
+    }
+
+    #[test]
+    fn serialize_malformed() {
+        assert_eq!(to_nbt(&vec![Some(1), None]).unwrap_err().to_string(), "Lists can't contain empty values");
+        assert_eq!(to_nbt(&None::<i32>).unwrap_err().to_string(), "Root value can't be empty");
+        assert_eq!(to_nbt(&BTreeMap::from([(1, 2)])).unwrap_err().to_string(), "Compound keys must be strings");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,489 @@
+//! Stringified NBT (SNBT) used in commands and config files
+//!
+//! # Example
+//! ```rs
+//! let nbt = NBTType::from_snbt(r#"{display:{Name:'{"text":"Sword"}'},Damage:3,Tags:[I;1,2]}"#)?;
+//! client.send_command(&format!("give @s diamond_sword{}", nbt.into_snbt()));
+//! ```
+
+use std::str::FromStr;
+
+use super::{NBTType, NBTCompound, NBTError, MAX_DEPTH};
+
+/// Indentation used by pretty printed SNBT
+const INDENT: &str = "    ";
+
+impl NBTType {
+    /// Parses SNBT (e.g. `{Count:1b,tag:{Damage:3}}`)
+    ///
+    /// Numbers without suffix are ints (or doubles if they contain a dot), `true` and `false` are bytes
+    /// and anything else which is not quoted is a string
+    pub fn from_snbt(input: &str) -> Result<NBTType, NBTError> {
+        let mut parser = SnbtParser { input, position: 0 };
+        let value = parser.read_value(0)?;
+        parser.skip_whitespace();
+        if parser.position < input.len() {
+            return Err(parser.error("Trailing data"));
+        }
+        Ok(value)
+    }
+
+    /// Converts the NBT type into String NBT (SNBT)
+    pub fn into_snbt(&self) -> String {
+        let mut output = String::new();
+        self.write_snbt(&mut output, None, 0);
+        output
+    }
+
+    /// Converts the NBT type into indented multiline String NBT (SNBT)
+    pub fn into_snbt_pretty(&self) -> String {
+        let mut output = String::new();
+        self.write_snbt(&mut output, Some(INDENT), 0);
+        output
+    }
+
+    fn write_snbt(&self, output: &mut String, indent: Option<&str>, depth: usize) {
+        match self {
+            NBTType::End => {},
+            NBTType::Byte(value) => output.push_str(&format!("{}b", value)),
+            NBTType::Short(value) => output.push_str(&format!("{}s", value)),
+            NBTType::Int(value) => output.push_str(&value.to_string()),
+            NBTType::Long(value) => output.push_str(&format!("{}L", value)),
+            NBTType::Float(value) => output.push_str(&format!("{}f", value)),
+            NBTType::Double(value) => output.push_str(&format!("{}d", value)),
+            NBTType::String(value) => output.push_str(&quote(value)),
+            NBTType::ByteArray(values) => write_array(output, "B", values.iter().map(|v| format!("{}B", v)), indent.is_some()),
+            NBTType::IntArray(values) => write_array(output, "I", values.iter().map(|v| v.to_string()), indent.is_some()),
+            NBTType::LongArray(values) => write_array(output, "L", values.iter().map(|v| format!("{}L", v)), indent.is_some()),
+            NBTType::List(values) => {
+                let nested = values.iter().any(|value| matches!(value, NBTType::List(_) | NBTType::Compound(_)));
+                match indent {
+                    Some(indent) if nested => {
+                        output.push('[');
+                        for (i, value) in values.iter().enumerate() {
+                            output.push_str(if i == 0 { "\n" } else { ",\n" });
+                            push_indent(output, indent, depth + 1);
+                            value.write_snbt(output, Some(indent), depth + 1);
+                        }
+                        output.push('\n');
+                        push_indent(output, indent, depth);
+                        output.push(']');
+                    },
+                    _ => {
+                        output.push('[');
+                        for (i, value) in values.iter().enumerate() {
+                            if i > 0 {
+                                output.push_str(if indent.is_some() { ", " } else { "," });
+                            }
+                            value.write_snbt(output, indent, depth + 1);
+                        }
+                        output.push(']');
+                    },
+                }
+            },
+            NBTType::Compound(compound) => {
+                output.push('{');
+                for (i, (key, value)) in compound.data.iter().enumerate() {
+                    if i > 0 {
+                        output.push(',');
+                    }
+                    if let Some(indent) = indent {
+                        output.push('\n');
+                        push_indent(output, indent, depth + 1);
+                    }
+                    output.push_str(&quote_key(key));
+                    output.push_str(if indent.is_some() { ": " } else { ":" });
+                    value.write_snbt(output, indent, depth + 1);
+                }
+                if let (Some(indent), false) = (indent, compound.data.is_empty()) {
+                    output.push('\n');
+                    push_indent(output, indent, depth);
+                }
+                output.push('}');
+            },
+        }
+    }
+}
+
+impl FromStr for NBTType {
+    type Err = NBTError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        NBTType::from_snbt(s)
+    }
+}
+
+fn push_indent(output: &mut String, indent: &str, depth: usize) {
+    for _ in 0..depth {
+        output.push_str(indent);
+    }
+}
+
+fn write_array(output: &mut String, prefix: &str, values: impl Iterator<Item = String>, pretty: bool) {
+    output.push('[');
+    output.push_str(prefix);
+    output.push(';');
+    for (i, value) in values.enumerate() {
+        output.push_str(match (i, pretty) {
+            (0, true) => " ",
+            (0, false) => "",
+            (_, true) => ", ",
+            (_, false) => ",",
+        });
+        output.push_str(&value);
+    }
+    output.push(']');
+}
+
+/// Returns whether character can be used in unquoted strings
+fn is_unquoted_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.' | '+')
+}
+
+/// Quotes string (uses single quotes if it contains double quotes but no single quotes)
+pub fn quote(value: &str) -> String {
+    let quote = if value.contains('"') && !value.contains('\'') { '\'' } else { '"' };
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push(quote);
+    for c in value.chars() {
+        if c == quote || c == '\\' {
+            output.push('\\');
+        }
+        output.push(c);
+    }
+    output.push(quote);
+    output
+}
+
+/// Quotes compound key only if it contains characters which are not allowed in unquoted strings
+fn quote_key(key: &str) -> String {
+    if !key.is_empty() && key.chars().all(is_unquoted_char) {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+pub(super) struct SnbtParser<'a> {
+    pub(super) input: &'a str,
+    pub(super) position: usize,
+}
+
+impl<'a> SnbtParser<'a> {
+    pub(super) fn error(&self, message: &str) -> NBTError {
+        NBTError::new(format!("{} at position {}", message, self.position))
+    }
+
+    pub(super) fn peek(&self) -> Option<char> {
+        self.input[self.position..].chars().next()
+    }
+
+    pub(super) fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += c.len_utf8();
+        Some(c)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.next();
+        }
+    }
+
+    pub(super) fn expect(&mut self, expected: char) -> Result<(), NBTError> {
+        self.skip_whitespace();
+        match self.peek() {
+            Some(c) if c == expected => {
+                self.next();
+                Ok(())
+            },
+            _ => Err(self.error(&format!("Expected '{}'", expected))),
+        }
+    }
+
+    /// Skips element separator, returns whether there was one
+    fn read_separator(&mut self) -> bool {
+        self.skip_whitespace();
+        if self.peek() == Some(',') {
+            self.next();
+            self.skip_whitespace();
+            return true;
+        }
+        false
+    }
+
+    fn read_value(&mut self, depth: usize) -> Result<NBTType, NBTError> {
+        if depth > MAX_DEPTH {
+            return Err(self.error("NBT is nested too deeply"));
+        }
+
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => self.read_compound(depth),
+            Some('[') => self.read_list_or_array(depth),
+            Some('"' | '\'') => self.read_quoted().map(NBTType::String),
+            Some(_) => {
+                let start = self.position;
+                let literal = self.read_unquoted();
+                if literal.is_empty() {
+                    self.position = start;
+                    return Err(self.error("Expected value"));
+                }
+                Ok(parse_literal(literal))
+            },
+            None => Err(self.error("Expected value")),
+        }
+    }
+
+    fn read_unquoted(&mut self) -> &'a str {
+        let start = self.position;
+        while self.peek().is_some_and(is_unquoted_char) {
+            self.next();
+        }
+        &self.input[start..self.position]
+    }
+
+    pub(super) fn read_quoted(&mut self) -> Result<String, NBTError> {
+        let quote = self.next().ok_or_else(|| self.error("Expected quote"))?;
+        let mut value = String::new();
+        loop {
+            match self.next() {
+                Some('\\') => match self.next() {
+                    Some(c) if c == quote || c == '\\' => value.push(c),
+                    _ => return Err(self.error("Invalid escape sequence")),
+                },
+                Some(c) if c == quote => return Ok(value),
+                Some(c) => value.push(c),
+                None => return Err(self.error("Unclosed quoted string")),
+            }
+        }
+    }
+
+    fn read_key(&mut self) -> Result<String, NBTError> {
+        self.skip_whitespace();
+        let start = self.position;
+        let key = match self.peek() {
+            Some('"' | '\'') => self.read_quoted()?,
+            _ => self.read_unquoted().to_string(),
+        };
+        // Empty keys are rejected even if quoted (same as in vanilla)
+        if key.is_empty() {
+            self.position = start;
+            return Err(self.error("Expected key"));
+        }
+        Ok(key)
+    }
+
+    pub(super) fn read_compound(&mut self, depth: usize) -> Result<NBTType, NBTError> {
+        self.expect('{')?;
+        let mut compound = NBTCompound::new();
+        self.skip_whitespace();
+        while self.peek() != Some('}') {
+            let key = self.read_key()?;
+            self.expect(':')?;
+            let value = self.read_value(depth + 1)?;
+            compound.data.insert(key, value);
+            if !self.read_separator() {
+                break;
+            }
+        }
+        self.expect('}')?;
+        Ok(NBTType::Compound(compound))
+    }
+
+    fn read_list_or_array(&mut self, depth: usize) -> Result<NBTType, NBTError> {
+        self.expect('[')?;
+        let rest = &self.input[self.position..];
+        let mut prefix = rest.chars();
+        if let (Some(kind @ ('B' | 'I' | 'L')), Some(';')) = (prefix.next(), prefix.next()) {
+            self.position += 2;
+            return self.read_array(kind, depth);
+        }
+
+        let mut values: Vec<NBTType> = Vec::new();
+        self.skip_whitespace();
+        while self.peek() != Some(']') {
+            let value = self.read_value(depth + 1)?;
+            if values.first().is_some_and(|first| first.type_id() != value.type_id()) {
+                return Err(self.error("Lists can't contain values of different types"));
+            }
+            values.push(value);
+            if !self.read_separator() {
+                break;
+            }
+        }
+        self.expect(']')?;
+        Ok(NBTType::List(values))
+    }
+
+    /// Reads elements of `[B;]`, `[I;]` or `[L;]` array, each of them must have type of the array (e.g. `1b` in `[B;]`)
+    fn read_array(&mut self, kind: char, depth: usize) -> Result<NBTType, NBTError> {
+        let mut array = match kind {
+            'B' => NBTType::ByteArray(Vec::new()),
+            'I' => NBTType::IntArray(Vec::new()),
+            _ => NBTType::LongArray(Vec::new()),
+        };
+        self.skip_whitespace();
+        while self.peek() != Some(']') {
+            let start = self.position;
+            let value = self.read_value(depth + 1)?;
+            match (&mut array, value) {
+                (NBTType::ByteArray(values), NBTType::Byte(value)) => values.push(value),
+                (NBTType::IntArray(values), NBTType::Int(value)) => values.push(value),
+                (NBTType::LongArray(values), NBTType::Long(value)) => values.push(value),
+                (array, value) => {
+                    self.position = start;
+                    return Err(self.error(&format!("Can't insert {} into {}", tag_name(value.type_id()), tag_name(array.type_id()))));
+                },
+            }
+            if !self.read_separator() {
+                break;
+            }
+        }
+        self.expect(']')?;
+        Ok(array)
+    }
+}
+
+/// Returns name of the tag type used in error messages (e.g. `TAG_Int_Array`)
+fn tag_name(type_id: u8) -> &'static str {
+    match type_id {
+        0x00 => "TAG_End",
+        0x01 => "TAG_Byte",
+        0x02 => "TAG_Short",
+        0x03 => "TAG_Int",
+        0x04 => "TAG_Long",
+        0x05 => "TAG_Float",
+        0x06 => "TAG_Double",
+        0x07 => "TAG_Byte_Array",
+        0x08 => "TAG_String",
+        0x09 => "TAG_List",
+        0x0A => "TAG_Compound",
+        0x0B => "TAG_Int_Array",
+        _ => "TAG_Long_Array",
+    }
+}
+
+/// Parses unquoted literal (numbers with optional type suffix, booleans or strings)
+fn parse_literal(literal: &str) -> NBTType {
+    match literal {
+        "true" => return NBTType::Byte(1),
+        "false" => return NBTType::Byte(0),
+        _ => {},
+    }
+
+    let (number, suffix) = match literal.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&literal[..i], Some(c.to_ascii_lowercase())),
+        _ => (literal, None),
+    };
+    let parsed = match suffix {
+        Some('b') if is_integer(number) => number.parse().ok().map(NBTType::Byte),
+        Some('s') if is_integer(number) => number.parse().ok().map(NBTType::Short),
+        Some('l') if is_integer(number) => number.parse().ok().map(NBTType::Long),
+        Some('f') if is_decimal(number) => number.parse().ok().map(NBTType::Float),
+        Some('d') if is_decimal(number) => number.parse().ok().map(NBTType::Double),
+        None if is_integer(number) => number.parse().ok().map(NBTType::Int),
+        None if number.contains('.') && is_decimal(number) => number.parse().ok().map(NBTType::Double),
+        _ => None,
+    };
+    // Numbers out of range are strings (same as in vanilla)
+    parsed.unwrap_or_else(|| NBTType::String(literal.to_string()))
+}
+
+/// Returns whether literal matches `[-+]?(0|[1-9][0-9]*)`
+fn is_integer(literal: &str) -> bool {
+    let digits = literal.strip_prefix(['-', '+']).unwrap_or(literal);
+    !digits.is_empty() && digits.bytes().all(|b| b.is_ascii_digit()) && (digits == "0" || !digits.starts_with('0'))
+}
+
+/// Returns whether literal matches `[-+]?([0-9]+[.]?|[0-9]*[.][0-9]+)(e[-+]?[0-9]+)?`
+fn is_decimal(literal: &str) -> bool {
+    let unsigned = literal.strip_prefix(['-', '+']).unwrap_or(literal);
+    let (mantissa, exponent) = match unsigned.find(['e', 'E']) {
+        Some(i) => (&unsigned[..i], Some(&unsigned[i + 1..])),
+        None => (unsigned, None),
+    };
+    let (whole, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    let valid_mantissa = digits(whole) && digits(fraction) && !(whole.is_empty() && fraction.is_empty());
+    let valid_exponent = exponent.is_none_or(|exponent| {
+        let exponent = exponent.strip_prefix(['-', '+']).unwrap_or(exponent);
+        !exponent.is_empty() && digits(exponent)
+    });
+    valid_mantissa && valid_exponent
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_print_round_trip() {
+        for snbt in [
+            r#"{Count:1b,id:"minecraft:diamond_sword",tag:{Damage:3,display:{Name:'{"text":"Sword"}'}}}"#,
+            r#"{a:1s,b:-2L,c:0.5f,d:1.25d,e:[I;1,-2],f:[B;1B,-128B],g:[L;5L],h:[],i:[[1],[2,3]],j:[{}],k:[B;]}"#,
+            r#"{"with space":"a\\b",single:'"',quote:"'\""}"#,
+        ] {
+            let nbt = NBTType::from_snbt(snbt).unwrap();
+            assert_eq!(nbt.into_snbt(), snbt);
+            assert_eq!(NBTType::from_snbt(&nbt.into_snbt_pretty()).unwrap(), nbt);
+        }
+    }
+
+    #[test]
+    fn parse_literals() {
+        assert_eq!(parse_literal("true"), NBTType::Byte(1));
+        assert_eq!(parse_literal("12"), NBTType::Int(12));
+        assert_eq!(parse_literal("-12B"), NBTType::Byte(-12));
+        assert_eq!(parse_literal("1.5"), NBTType::Double(1.5));
+        assert_eq!(parse_literal("1e3f"), NBTType::Float(1000.0));
+        assert_eq!(parse_literal(".5d"), NBTType::Double(0.5));
+        // Numbers out of range or with leading zeros are strings
+        assert_eq!(parse_literal("300b"), NBTType::String(String::from("300b")));
+        assert_eq!(parse_literal("012"), NBTType::String(String::from("012")));
+        assert_eq!(parse_literal("minecraft.stone"), NBTType::String(String::from("minecraft.stone")));
+    }
+
+    #[test]
+    fn parse_malformed() {
+        for snbt in [
+            "",
+            "{",
+            "{a:}",
+            "{a 1}",
+            "{a:1,,b:2}",
+            "[1,2b]",
+            "[B;1,300]",
+            "[B;1b,300b]",
+            "[I;1,a]",
+            "[I;1b]",
+            "[I;[1]]",
+            "[L;1]",
+            "{\"\":1}",
+            "{'':1}",
+            "\"unclosed",
+            "'\\n'",
+            "{a:1} b",
+        ] {
+            assert!(NBTType::from_snbt(snbt).is_err(), "{} should be rejected", snbt);
+        }
+
+        let nested = format!("{}{}", "[".repeat(MAX_DEPTH + 2), "]".repeat(MAX_DEPTH + 2));
+        assert!(NBTType::from_snbt(&nested).is_err());
+
+        assert_eq!(NBTType::from_snbt("[B;1b,2]").unwrap_err().to_string(), "Can't insert TAG_Int into TAG_Byte_Array at position 6");
+        assert_eq!(NBTType::from_snbt("[L; 1L, 'a']").unwrap_err().to_string(), "Can't insert TAG_String into TAG_Long_Array at position 8");
+        assert_eq!(NBTType::from_snbt("{a:1, '':2}").unwrap_err().to_string(), "Expected key at position 6");
+    }
+
+    #[test]
+    fn print() {
+        // Empty keys are quoted (same as in vanilla, even though they can't be parsed back)
+        let mut compound = NBTCompound::new();
+        compound.data.insert(String::new(), NBTType::ByteArray(vec![1, -2]));
+        compound.data.insert("b".to_string(), NBTType::LongArray(vec![3]));
+        let nbt = NBTType::Compound(compound);
+        assert_eq!(nbt.into_snbt(), r#"{"":[B;1B,-2B],b:[L;3L]}"#);
+        assert_eq!(nbt.into_snbt_pretty(), "{\n    \"\": [B; 1B, -2B],\n    b: [L; 3L]\n}");
+    }
+}
\ No newline at end of file
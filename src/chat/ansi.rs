@@ -0,0 +1,183 @@
+use crate::chat::{component::{FormattedChatMessage, ComponentContent}, translate::{Language, FormatPart, parse_format}};
+
+/// Named chat colors with their legacy codes and RGB values
+pub const NAMED_COLORS: [(&str, char, u32); 16] = [
+    ("black", '0', 0x000000),
+    ("dark_blue", '1', 0x0000AA),
+    ("dark_green", '2', 0x00AA00),
+    ("dark_aqua", '3', 0x00AAAA),
+    ("dark_red", '4', 0xAA0000),
+    ("dark_purple", '5', 0xAA00AA),
+    ("gold", '6', 0xFFAA00),
+    ("gray", '7', 0xAAAAAA),
+    ("dark_gray", '8', 0x555555),
+    ("blue", '9', 0x5555FF),
+    ("green", 'a', 0x55FF55),
+    ("aqua", 'b', 0x55FFFF),
+    ("red", 'c', 0xFF5555),
+    ("light_purple", 'd', 0xFF55FF),
+    ("yellow", 'e', 0xFFFF55),
+    ("white", 'f', 0xFFFFFF),
+];
+
+/// ANSI foreground codes of named colors (in the same order as [`NAMED_COLORS`])
+const ANSI_CODES: [u8; 16] = [30, 34, 32, 36, 31, 35, 33, 37, 90, 94, 92, 96, 91, 95, 93, 97];
+
+/// How hex colors are rendered in the terminal (named colors always use standard 16 color codes)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColorMode {
+    /// 24-bit colors (`ESC[38;2;r;g;bm`)
+    TrueColor,
+    /// Closest color from 256 color palette (`ESC[38;5;nm`)
+    Ansi256,
+}
+
+impl ColorMode {
+    /// Detects color mode from `COLORTERM` environment variable
+    pub fn detect() -> ColorMode {
+        match std::env::var("COLORTERM") {
+            Ok(value) if value.contains("truecolor") || value.contains("24bit") => ColorMode::TrueColor,
+            _ => ColorMode::Ansi256,
+        }
+    }
+}
+
+/// Resolved style of the component (after inheriting from its parents)
+#[derive(Debug, Clone, Default)]
+struct AnsiStyle {
+    color: Option<String>,
+    bold: bool,
+    italic: bool,
+    underlined: bool,
+    strikethrough: bool,
+}
+
+impl AnsiStyle {
+    /// Applies style of the component on top of the parent style
+    fn inherit(&self, component: &FormattedChatMessage) -> AnsiStyle {
+        AnsiStyle {
+            color: component.color.clone().or_else(|| self.color.clone()),
+            bold: component.bold.unwrap_or(self.bold),
+            italic: component.italic.unwrap_or(self.italic),
+            underlined: component.underlined.unwrap_or(self.underlined),
+            strikethrough: component.strikethrough.unwrap_or(self.strikethrough),
+        }
+    }
+
+    /// Returns escape sequence that switches terminal to this style
+    fn escape(&self, mode: ColorMode) -> String {
+        let mut codes = vec![String::from("0")]; // Reset previous style first
+        if self.bold {
+            codes.push(String::from("1"));
+        }
+        if self.italic {
+            codes.push(String::from("3"));
+        }
+        if self.underlined {
+            codes.push(String::from("4"));
+        }
+        if self.strikethrough {
+            codes.push(String::from("9"));
+        }
+        //? Obfuscated text has no terminal equivalent, so it is rendered as is
+        if let Some(color) = self.color.as_deref().and_then(|color| color_code(color, mode)) {
+            codes.push(color);
+        }
+        format!("\x1b[{}m", codes.join(";"))
+    }
+}
+
+/// Returns SGR foreground code of the color (named or `#rrggbb`)
+fn color_code(color: &str, mode: ColorMode) -> Option<String> {
+    if let Some(index) = NAMED_COLORS.iter().position(|(name, _, _)| *name == color) {
+        return Some(ANSI_CODES[index].to_string());
+    }
+
+    let rgb = u32::from_str_radix(color.strip_prefix('#')?, 16).ok()?;
+    let (r, g, b) = ((rgb >> 16) as u8, (rgb >> 8) as u8, rgb as u8);
+    Some(match mode {
+        ColorMode::TrueColor => format!("38;2;{};{};{}", r, g, b),
+        ColorMode::Ansi256 => format!("38;5;{}", ansi256(r, g, b)),
+    })
+}
+
+/// Finds the closest color in the 256 color palette (6x6x6 color cube or grayscale ramp)
+fn ansi256(r: u8, g: u8, b: u8) -> u8 {
+    // Grayscale colors are better represented by the grayscale ramp
+    if r == g && g == b {
+        return match r {
+            0..=7 => 16,
+            249..=255 => 231,
+            v => 232 + ((v as u16 - 8) * 24 / 241) as u8,
+        };
+    }
+
+    let level = |v: u8| -> u8 {
+        match v {
+            0..=47 => 0,
+            48..=114 => 1,
+            v => ((v as u16 - 35) / 40) as u8,
+        }
+    };
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+impl FormattedChatMessage {
+    /// Renders component to ANSI colored terminal text (using built-in translations and detected color mode)
+    pub fn to_ansi(&self) -> String {
+        self.to_ansi_with(Language::builtin(), ColorMode::detect())
+    }
+
+    /// Renders component to ANSI colored terminal text
+    pub fn to_ansi_with(&self, language: &Language, mode: ColorMode) -> String {
+        let mut result = String::new();
+        self.render_ansi(&AnsiStyle::default(), language, mode, &mut result);
+        result.push_str("\x1b[0m");
+        result
+    }
+
+    fn render_ansi(&self, parent: &AnsiStyle, language: &Language, mode: ColorMode, out: &mut String) {
+        let style = parent.inherit(self);
+        let escape = style.escape(mode);
+
+        match &self.content {
+            ComponentContent::Translatable { key, with } => {
+                // Arguments are rendered as components, so they keep their own style
+                let format_string = language.get(key).unwrap_or(key);
+                match parse_format(format_string, with.len()) {
+                    Some(parts) => {
+                        for part in parts {
+                            match part {
+                                FormatPart::Literal(text) => {
+                                    out.push_str(&escape);
+                                    out.push_str(&text);
+                                },
+                                FormatPart::Argument(index) => with[index].render_ansi(&style, language, mode, out),
+                            }
+                        }
+                    },
+                    None => {
+                        out.push_str(&escape);
+                        out.push_str(key);
+                    }
+                }
+            },
+            _ => {
+                // Other contents have no children of their own, so plain rendering of a childless copy is enough
+                let content = FormattedChatMessage {
+                    content: self.content.clone(),
+                    ..Default::default()
+                };
+                let text = content.to_plain_with(language);
+                if !text.is_empty() {
+                    out.push_str(&escape);
+                    out.push_str(&text);
+                }
+            }
+        }
+
+        for child in &self.extra {
+            child.render_ansi(&style, language, mode, out);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,120 @@
+use miners_protocol::utils::nbt::{self, NBTType, NBTCompound, NBTError};
+use serde::Deserialize;
+
+use crate::chat::component::{FormattedChatMessage, ComponentContent};
+
+/// Chat type (entry of `minecraft:chat_type` registry) which describes how player messages are decorated
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatType {
+    pub name: String,
+    pub chat: ChatDecoration,
+    pub narration: ChatDecoration,
+}
+
+/// Decoration of the message (translation key with its parameters and style)
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChatDecoration {
+    pub translation_key: String,
+    pub parameters: Vec<ChatParameter>,
+    /// Style applied to the whole decorated message (only style fields of the component are set)
+    pub style: FormattedChatMessage,
+}
+
+/// Parameter of chat decoration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChatParameter {
+    Sender,
+    Target,
+    Content,
+}
+
+impl ChatParameter {
+    pub fn from_name(name: &str) -> Option<ChatParameter> {
+        match name {
+            "sender" => Some(ChatParameter::Sender),
+            "target" => Some(ChatParameter::Target),
+            "content" => Some(ChatParameter::Content),
+            _ => None,
+        }
+    }
+}
+
+impl ChatDecoration {
+    /// Decorates message content (e.g. `chat.type.text` with `Steve` and `hi` results in `<Steve> hi`)
+    pub fn decorate(&self, content: &FormattedChatMessage, sender: &FormattedChatMessage, target: Option<&FormattedChatMessage>) -> FormattedChatMessage {
+        let with = self.parameters.iter().map(|parameter| match parameter {
+            ChatParameter::Sender => sender.clone(),
+            ChatParameter::Target => target.cloned().unwrap_or_default(),
+            ChatParameter::Content => content.clone(),
+        }).collect();
+
+        FormattedChatMessage {
+            content: ComponentContent::Translatable { key: self.translation_key.clone(), with },
+            ..self.style.clone()
+        }
+    }
+}
+
+/// Chat decoration as stored in registry codec
+#[derive(Deserialize)]
+struct RawChatDecoration {
+    translation_key: String,
+    #[serde(default)]
+    parameters: Vec<String>,
+    #[serde(default)]
+    style: RawChatStyle,
+}
+
+/// Style of chat decoration as stored in registry codec
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct RawChatStyle {
+    color: Option<String>,
+    font: Option<String>,
+    insertion: Option<String>,
+    bold: Option<bool>,
+    italic: Option<bool>,
+    underlined: Option<bool>,
+    strikethrough: Option<bool>,
+    obfuscated: Option<bool>,
+}
+
+impl From<RawChatDecoration> for ChatDecoration {
+    fn from(raw: RawChatDecoration) -> Self {
+        let style = raw.style;
+        ChatDecoration {
+            translation_key: raw.translation_key,
+            parameters: raw.parameters.iter().filter_map(|name| ChatParameter::from_name(name)).collect(),
+            style: FormattedChatMessage {
+                color: style.color,
+                font: style.font,
+                insertion: style.insertion,
+                bold: style.bold,
+                italic: style.italic,
+                underlined: style.underlined,
+                strikethrough: style.strikethrough,
+                obfuscated: style.obfuscated,
+                ..Default::default()
+            },
+        }
+    }
+}
+
+/// Chat type as stored in registry codec
+#[derive(Deserialize)]
+struct RawChatType {
+    chat: RawChatDecoration,
+    narration: RawChatDecoration,
+}
+
+impl ChatType {
+    /// Parses chat type from registry entry element (compound with `chat` and `narration` decorations)
+    pub fn from_nbt(name: String, element: &NBTCompound) -> Result<ChatType, NBTError> {
+        let raw: RawChatType = nbt::from_nbt(NBTType::Compound(element.clone()))?;
+        Ok(ChatType {
+            name,
+            chat: raw.chat.into(),
+            narration: raw.narration.into(),
+        })
+    }
+}
\ No newline at end of file
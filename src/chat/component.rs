@@ -0,0 +1,659 @@
+use serde_json::{Value, Map};
+
+use crate::chat::translate::Language;
+
+/// Parsed version of formatted chat message (text component, like the one you pass to `/tellraw`)
+///
+/// Consists of content (text, translation, score, ...), style and child components (`extra`)
+/// which inherit the style of their parent.
+///
+/// # Example
+/// ```rs
+/// let message = FormattedChatMessage::from_json(r#"{"translate":"chat.type.text","with":["Steve","hi"]}"#)?;
+/// let json = message.to_json();
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FormattedChatMessage {
+    pub content: ComponentContent,
+
+    // Style (`None` means that value is inherited from the parent)
+    pub bold: Option<bool>,
+    pub italic: Option<bool>,
+    pub underlined: Option<bool>,
+    pub strikethrough: Option<bool>,
+    pub obfuscated: Option<bool>,
+    /// Font resource location (e.g. `minecraft:uniform`)
+    pub font: Option<String>,
+    /// Named color (e.g. `red`) or hex color (e.g. `#ff0000`)
+    pub color: Option<String>,
+    /// Text inserted into chat input when the component is shift-clicked
+    pub insertion: Option<String>,
+    pub click_event: Option<ClickEvent>,
+    pub hover_event: Option<HoverEvent>,
+
+    pub extra: Vec<FormattedChatMessage>,
+}
+
+/// Content of the text component
+#[derive(Debug, Clone, PartialEq)]
+pub enum ComponentContent {
+    Text(String),
+    /// Translatable text (`translate` key with `with` arguments)
+    Translatable {
+        key: String,
+        with: Vec<FormattedChatMessage>,
+    },
+    /// Score of the entity (`value` is resolved by the server)
+    Score {
+        name: String,
+        objective: String,
+        value: Option<String>,
+    },
+    /// Entity selector (resolved by the server)
+    Selector {
+        selector: String,
+        separator: Option<Box<FormattedChatMessage>>,
+    },
+    /// Name of the key bound to given action (e.g. `key.jump`)
+    Keybind(String),
+    /// NBT value (resolved by the server)
+    Nbt {
+        path: String,
+        interpret: bool,
+        separator: Option<Box<FormattedChatMessage>>,
+        source: NbtSource,
+    },
+}
+
+impl Default for ComponentContent {
+    fn default() -> Self {
+        ComponentContent::Text(String::new())
+    }
+}
+
+/// Source of NBT component data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NbtSource {
+    /// Block coordinates (e.g. `~ ~1 ~`)
+    Block(String),
+    /// Entity selector
+    Entity(String),
+    /// Command storage id
+    Storage(String),
+}
+
+/// Action performed when the component is clicked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClickEvent {
+    pub action: ClickAction,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ClickAction {
+    OpenUrl,
+    OpenFile,
+    RunCommand,
+    SuggestCommand,
+    ChangePage,
+    CopyToClipboard,
+}
+
+impl ClickAction {
+    pub fn name(&self) -> &'static str {
+        match self {
+            ClickAction::OpenUrl => "open_url",
+            ClickAction::OpenFile => "open_file",
+            ClickAction::RunCommand => "run_command",
+            ClickAction::SuggestCommand => "suggest_command",
+            ClickAction::ChangePage => "change_page",
+            ClickAction::CopyToClipboard => "copy_to_clipboard",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<ClickAction> {
+        match name {
+            "open_url" => Some(ClickAction::OpenUrl),
+            "open_file" => Some(ClickAction::OpenFile),
+            "run_command" => Some(ClickAction::RunCommand),
+            "suggest_command" => Some(ClickAction::SuggestCommand),
+            "change_page" => Some(ClickAction::ChangePage),
+            "copy_to_clipboard" => Some(ClickAction::CopyToClipboard),
+            _ => None,
+        }
+    }
+}
+
+/// Tooltip shown when the component is hovered
+#[derive(Debug, Clone, PartialEq)]
+pub enum HoverEvent {
+    ShowText(Box<FormattedChatMessage>),
+    ShowItem {
+        id: String,
+        count: i32,
+        /// Item NBT (as SNBT)
+        tag: Option<String>,
+    },
+    ShowEntity {
+        /// Entity type (e.g. `minecraft:pig`)
+        kind: String,
+        /// Entity UUID (as string)
+        id: String,
+        name: Option<Box<FormattedChatMessage>>,
+    },
+}
+
+/// Error returned when text component can't be parsed
+#[derive(Debug)]
+pub enum ComponentError {
+    Json(serde_json::Error),
+    Invalid(String),
+}
+
+impl From<serde_json::Error> for ComponentError {
+    fn from(e: serde_json::Error) -> Self {
+        ComponentError::Json(e)
+    }
+}
+
+impl FormattedChatMessage {
+    /// Creates component with plain text and no style
+    pub fn from_plain(text: String) -> Self {
+        FormattedChatMessage {
+            content: ComponentContent::Text(text),
+            ..Default::default()
+        }
+    }
+
+    /// Creates translatable component
+    pub fn translatable(key: String, with: Vec<FormattedChatMessage>) -> Self {
+        FormattedChatMessage {
+            content: ComponentContent::Translatable { key, with },
+            ..Default::default()
+        }
+    }
+
+    /// Parses component from JSON string
+    pub fn from_json(json: &str) -> Result<Self, ComponentError> {
+        let value: Value = serde_json::from_str(json)?;
+        Self::from_value(&value)
+    }
+
+    /// Parses component from JSON value
+    ///
+    /// Accepts all forms used by vanilla: plain strings (and other primitives), arrays and objects
+    pub fn from_value(value: &Value) -> Result<Self, ComponentError> {
+        match value {
+            Value::String(text) => Ok(Self::from_plain(text.clone())),
+            Value::Number(n) => Ok(Self::from_plain(n.to_string())),
+            Value::Bool(b) => Ok(Self::from_plain(b.to_string())),
+            Value::Array(values) => {
+                // First element is the parent, others are appended to its children
+                let mut values = values.iter();
+                let mut component = Self::from_value(values.next().ok_or_else(|| ComponentError::Invalid(String::from("Empty component array")))?)?;
+                for value in values {
+                    component.extra.push(Self::from_value(value)?);
+                }
+                Ok(component)
+            },
+            Value::Object(object) => Self::from_object(object),
+            Value::Null => Err(ComponentError::Invalid(String::from("Component can't be null"))),
+        }
+    }
+
+    fn from_object(object: &Map<String, Value>) -> Result<Self, ComponentError> {
+        let content = if let Some(text) = object.get("text") {
+            ComponentContent::Text(primitive_to_string(text)?)
+        } else if let Some(key) = object.get("translate") {
+            let with = match object.get("with") {
+                Some(Value::Array(args)) => args.iter().map(Self::from_value).collect::<Result<Vec<_>, _>>()?,
+                Some(_) => return Err(ComponentError::Invalid(String::from("Translation arguments must be an array"))),
+                None => Vec::new(),
+            };
+            ComponentContent::Translatable {
+                key: primitive_to_string(key)?,
+                with,
+            }
+        } else if let Some(score) = object.get("score") {
+            ComponentContent::Score {
+                name: get_string(score, "name")?,
+                objective: get_string(score, "objective")?,
+                value: score.get("value").map(primitive_to_string).transpose()?,
+            }
+        } else if let Some(selector) = object.get("selector") {
+            ComponentContent::Selector {
+                selector: primitive_to_string(selector)?,
+                separator: parse_separator(object)?,
+            }
+        } else if let Some(keybind) = object.get("keybind") {
+            ComponentContent::Keybind(primitive_to_string(keybind)?)
+        } else if let Some(path) = object.get("nbt") {
+            let source = if let Some(block) = object.get("block") {
+                NbtSource::Block(primitive_to_string(block)?)
+            } else if let Some(entity) = object.get("entity") {
+                NbtSource::Entity(primitive_to_string(entity)?)
+            } else if let Some(storage) = object.get("storage") {
+                NbtSource::Storage(primitive_to_string(storage)?)
+            } else {
+                return Err(ComponentError::Invalid(String::from("NBT component without source")));
+            };
+            ComponentContent::Nbt {
+                path: primitive_to_string(path)?,
+                interpret: object.get("interpret").and_then(Value::as_bool).unwrap_or(false),
+                separator: parse_separator(object)?,
+                source,
+            }
+        } else {
+            return Err(ComponentError::Invalid(format!("Unknown component content: {}", Value::Object(object.clone()))));
+        };
+
+        let click_event = match object.get("clickEvent") {
+            Some(event) => {
+                let action = get_string(event, "action")?;
+                Some(ClickEvent {
+                    action: ClickAction::from_name(&action).ok_or_else(|| ComponentError::Invalid(format!("Unknown click action: {}", action)))?,
+                    value: get_string(event, "value")?,
+                })
+            },
+            None => None,
+        };
+
+        let hover_event = match object.get("hoverEvent") {
+            Some(event) => Some(HoverEvent::from_value(event)?),
+            None => None,
+        };
+
+        let extra = match object.get("extra") {
+            Some(Value::Array(extra)) => extra.iter().map(Self::from_value).collect::<Result<Vec<_>, _>>()?,
+            Some(_) => return Err(ComponentError::Invalid(String::from("Extra must be an array"))),
+            None => Vec::new(),
+        };
+
+        Ok(FormattedChatMessage {
+            content,
+            bold: object.get("bold").and_then(Value::as_bool),
+            italic: object.get("italic").and_then(Value::as_bool),
+            underlined: object.get("underlined").and_then(Value::as_bool),
+            strikethrough: object.get("strikethrough").and_then(Value::as_bool),
+            obfuscated: object.get("obfuscated").and_then(Value::as_bool),
+            font: object.get("font").and_then(Value::as_str).map(String::from),
+            color: object.get("color").and_then(Value::as_str).map(String::from),
+            insertion: object.get("insertion").and_then(Value::as_str).map(String::from),
+            click_event,
+            hover_event,
+            extra,
+        })
+    }
+
+    /// Serializes component to JSON value
+    pub fn to_value(&self) -> Value {
+        let mut object = Map::new();
+        match &self.content {
+            ComponentContent::Text(text) => {
+                object.insert("text".into(), Value::String(text.clone()));
+            },
+            ComponentContent::Translatable { key, with } => {
+                object.insert("translate".into(), Value::String(key.clone()));
+                if !with.is_empty() {
+                    object.insert("with".into(), Value::Array(with.iter().map(Self::to_value).collect()));
+                }
+            },
+            ComponentContent::Score { name, objective, value } => {
+                let mut score = Map::new();
+                score.insert("name".into(), Value::String(name.clone()));
+                score.insert("objective".into(), Value::String(objective.clone()));
+                if let Some(value) = value {
+                    score.insert("value".into(), Value::String(value.clone()));
+                }
+                object.insert("score".into(), Value::Object(score));
+            },
+            ComponentContent::Selector { selector, separator } => {
+                object.insert("selector".into(), Value::String(selector.clone()));
+                if let Some(separator) = separator {
+                    object.insert("separator".into(), separator.to_value());
+                }
+            },
+            ComponentContent::Keybind(keybind) => {
+                object.insert("keybind".into(), Value::String(keybind.clone()));
+            },
+            ComponentContent::Nbt { path, interpret, separator, source } => {
+                object.insert("nbt".into(), Value::String(path.clone()));
+                if *interpret {
+                    object.insert("interpret".into(), Value::Bool(true));
+                }
+                if let Some(separator) = separator {
+                    object.insert("separator".into(), separator.to_value());
+                }
+                let (key, value) = match source {
+                    NbtSource::Block(v) => ("block", v),
+                    NbtSource::Entity(v) => ("entity", v),
+                    NbtSource::Storage(v) => ("storage", v),
+                };
+                object.insert(key.into(), Value::String(value.clone()));
+            },
+        }
+
+        for (key, value) in [
+            ("bold", self.bold),
+            ("italic", self.italic),
+            ("underlined", self.underlined),
+            ("strikethrough", self.strikethrough),
+            ("obfuscated", self.obfuscated),
+        ] {
+            if let Some(value) = value {
+                object.insert(key.into(), Value::Bool(value));
+            }
+        }
+        for (key, value) in [
+            ("font", &self.font),
+            ("color", &self.color),
+            ("insertion", &self.insertion),
+        ] {
+            if let Some(value) = value {
+                object.insert(key.into(), Value::String(value.clone()));
+            }
+        }
+
+        if let Some(click_event) = &self.click_event {
+            let mut event = Map::new();
+            event.insert("action".into(), Value::String(click_event.action.name().into()));
+            event.insert("value".into(), Value::String(click_event.value.clone()));
+            object.insert("clickEvent".into(), Value::Object(event));
+        }
+        if let Some(hover_event) = &self.hover_event {
+            object.insert("hoverEvent".into(), hover_event.to_value());
+        }
+
+        if !self.extra.is_empty() {
+            object.insert("extra".into(), Value::Array(self.extra.iter().map(Self::to_value).collect()));
+        }
+
+        Value::Object(object)
+    }
+
+    /// Serializes component to JSON string
+    pub fn to_json(&self) -> String {
+        self.to_value().to_string()
+    }
+
+    /// Serializes component to "stable" JSON which is hashed in message signatures (same as vanilla `toStableJson`)
+    ///
+    /// Keys are sorted, there is no whitespace and translation arguments without any style are written as plain strings
+    pub fn to_stable_json(&self) -> String {
+        let mut json = String::new();
+        write_stable_json(&stable_value(self.to_value()), &mut json);
+        json
+    }
+
+    /// Returns text of this component and all its children without any formatting
+    ///
+    /// Translatable components are resolved using built-in translations (see [`Language`])
+    pub fn to_plain(&self) -> String {
+        self.to_plain_with(Language::builtin())
+    }
+}
+
+impl HoverEvent {
+    fn from_value(value: &Value) -> Result<Self, ComponentError> {
+        let action = get_string(value, "action")?;
+        // Modern format uses `contents`, legacy one uses `value` (text component or SNBT string)
+        let contents = value.get("contents");
+        let legacy = value.get("value");
+
+        match action.as_str() {
+            "show_text" => {
+                let text = contents.or(legacy).ok_or_else(|| ComponentError::Invalid(String::from("Hover event without contents")))?;
+                Ok(HoverEvent::ShowText(Box::new(FormattedChatMessage::from_value(text)?)))
+            },
+            "show_item" => match contents {
+                Some(Value::String(id)) => Ok(HoverEvent::ShowItem { id: id.clone(), count: 1, tag: None }),
+                Some(contents) => Ok(HoverEvent::ShowItem {
+                    id: get_string(contents, "id")?,
+                    count: contents.get("count").and_then(Value::as_i64).unwrap_or(1) as i32,
+                    tag: contents.get("tag").map(primitive_to_string).transpose()?,
+                }),
+                None => Ok(HoverEvent::ShowItem {
+                    id: String::new(),
+                    count: 1,
+                    tag: Some(legacy_to_string(legacy)?), // Whole item as SNBT
+                }),
+            },
+            "show_entity" => match contents {
+                Some(contents) => Ok(HoverEvent::ShowEntity {
+                    kind: get_string(contents, "type")?,
+                    id: primitive_to_string(contents.get("id").ok_or_else(|| ComponentError::Invalid(String::from("Entity without id")))?)?,
+                    name: contents.get("name").map(FormattedChatMessage::from_value).transpose()?.map(Box::new),
+                }),
+                None => Ok(HoverEvent::ShowEntity {
+                    kind: String::new(),
+                    id: legacy_to_string(legacy)?, // Whole entity as SNBT
+                    name: None,
+                }),
+            },
+            _ => Err(ComponentError::Invalid(format!("Unknown hover action: {}", action))),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        let mut event = Map::new();
+        match self {
+            HoverEvent::ShowText(text) => {
+                event.insert("action".into(), Value::String("show_text".into()));
+                event.insert("contents".into(), text.to_value());
+            },
+            HoverEvent::ShowItem { id, count, tag } => {
+                event.insert("action".into(), Value::String("show_item".into()));
+                let mut contents = Map::new();
+                contents.insert("id".into(), Value::String(id.clone()));
+                if *count != 1 {
+                    contents.insert("count".into(), Value::from(*count));
+                }
+                if let Some(tag) = tag {
+                    contents.insert("tag".into(), Value::String(tag.clone()));
+                }
+                event.insert("contents".into(), Value::Object(contents));
+            },
+            HoverEvent::ShowEntity { kind, id, name } => {
+                event.insert("action".into(), Value::String("show_entity".into()));
+                let mut contents = Map::new();
+                contents.insert("type".into(), Value::String(kind.clone()));
+                contents.insert("id".into(), Value::String(id.clone()));
+                if let Some(name) = name {
+                    contents.insert("name".into(), name.to_value());
+                }
+                event.insert("contents".into(), Value::Object(contents));
+            },
+        }
+        Value::Object(event)
+    }
+}
+
+impl std::str::FromStr for FormattedChatMessage {
+    type Err = ComponentError;
+
+    fn from_str(json: &str) -> Result<Self, Self::Err> {
+        Self::from_json(json)
+    }
+}
+
+impl serde::Serialize for FormattedChatMessage {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value().serialize(serializer)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for FormattedChatMessage {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let value = Value::deserialize(deserializer)?;
+        Self::from_value(&value).map_err(|e| serde::de::Error::custom(format!("{:?}", e)))
+    }
+}
+
+/// Converts JSON primitive (string, number or bool) to string
+fn primitive_to_string(value: &Value) -> Result<String, ComponentError> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        Value::Number(n) => Ok(n.to_string()),
+        Value::Bool(b) => Ok(b.to_string()),
+        _ => Err(ComponentError::Invalid(format!("Expected primitive value, got: {}", value))),
+    }
+}
+
+fn get_string(value: &Value, key: &str) -> Result<String, ComponentError> {
+    value.get(key)
+        .ok_or_else(|| ComponentError::Invalid(format!("Missing field: {}", key)))
+        .and_then(primitive_to_string)
+}
+
+fn parse_separator(object: &Map<String, Value>) -> Result<Option<Box<FormattedChatMessage>>, ComponentError> {
+    Ok(object.get("separator").map(FormattedChatMessage::from_value).transpose()?.map(Box::new))
+}
+
+/// Legacy hover `value` is a component which contains SNBT as text
+fn legacy_to_string(legacy: Option<&Value>) -> Result<String, ComponentError> {
+    let legacy = legacy.ok_or_else(|| ComponentError::Invalid(String::from("Hover event without contents")))?;
+    Ok(FormattedChatMessage::from_value(legacy)?.to_plain())
+}
+
+
+/// Converts translation arguments which are plain text components into strings (vanilla unwraps them when parsing)
+fn stable_value(value: Value) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(object.into_iter().map(|(key, value)| {
+            let value = match (key.as_str(), stable_value(value)) {
+                ("with", Value::Array(arguments)) => Value::Array(arguments.into_iter().map(|argument| match argument {
+                    Value::Object(mut argument) if argument.len() == 1 && argument.get("text").is_some_and(Value::is_string) => argument.remove("text").unwrap(),
+                    argument => argument,
+                }).collect()),
+                (_, value) => value,
+            };
+            (key, value)
+        }).collect()),
+        Value::Array(values) => Value::Array(values.into_iter().map(stable_value).collect()),
+        value => value,
+    }
+}
+
+/// Writes JSON with sorted keys and escaping used by Gson
+fn write_stable_json(value: &Value, json: &mut String) {
+    match value {
+        Value::Object(object) => {
+            let mut entries = object.iter().collect::<Vec<_>>();
+            entries.sort_by_key(|(key, _)| *key);
+            json.push('{');
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                write_stable_string(key, json);
+                json.push(':');
+                write_stable_json(value, json);
+            }
+            json.push('}');
+        },
+        Value::Array(values) => {
+            json.push('[');
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    json.push(',');
+                }
+                write_stable_json(value, json);
+            }
+            json.push(']');
+        },
+        Value::String(value) => write_stable_string(value, json),
+        value => json.push_str(&value.to_string()),
+    }
+}
+
+fn write_stable_string(value: &str, json: &mut String) {
+    json.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\t' => json.push_str("\\t"),
+            '\u{8}' => json.push_str("\\b"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\u{c}' => json.push_str("\\f"),
+            '\u{0}'..='\u{1f}' | '\u{2028}' | '\u{2029}' => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn text(text: &str) -> FormattedChatMessage {
+        FormattedChatMessage::from_plain(text.to_string())
+    }
+
+    #[test]
+    fn parse_roots() {
+        assert_eq!(FormattedChatMessage::from_json(r#""hello""#).unwrap(), text("hello"));
+        assert_eq!(FormattedChatMessage::from_json("12").unwrap(), text("12"));
+        assert_eq!(FormattedChatMessage::from_json("true").unwrap(), text("true"));
+
+        // First element of array is the parent of the others
+        let message = FormattedChatMessage::from_json(r#"[{"text":"a","bold":true},"b",["c","d"]]"#).unwrap();
+        assert_eq!(message.content, ComponentContent::Text("a".to_string()));
+        assert_eq!(message.bold, Some(true));
+        assert_eq!(message.extra, vec![text("b"), FormattedChatMessage { extra: vec![text("d")], ..text("c") }]);
+        assert_eq!(message.to_plain(), "abcd");
+    }
+
+    #[test]
+    fn parse_translate() {
+        let message = FormattedChatMessage::from_json(r#"{"translate":"chat.type.text","with":["Steve",{"text":"hi","color":"red"}]}"#).unwrap();
+        assert_eq!(message.content, ComponentContent::Translatable {
+            key: "chat.type.text".to_string(),
+            with: vec![text("Steve"), FormattedChatMessage { color: Some("red".to_string()), ..text("hi") }],
+        });
+        assert_eq!(message.to_plain(), "<Steve> hi");
+        assert_eq!(message.to_stable_json(), r#"{"translate":"chat.type.text","with":["Steve",{"color":"red","text":"hi"}]}"#);
+        assert_eq!(FormattedChatMessage::from_json(&message.to_json()).unwrap(), message);
+
+        let message = FormattedChatMessage::from_json(r#"{"translate":"unknown.key"}"#).unwrap();
+        assert_eq!(message.content, ComponentContent::Translatable { key: "unknown.key".to_string(), with: vec![] });
+        assert_eq!(message.to_plain(), "unknown.key");
+    }
+
+    #[test]
+    fn parse_events() {
+        let message = FormattedChatMessage::from_json(concat!(
+            r#"{"text":"","clickEvent":{"action":"run_command","value":"/help"},"#,
+            r#""hoverEvent":{"action":"show_text","value":"legacy"},"extra":[{"text":"x","italic":false}]}"#,
+        )).unwrap();
+        assert_eq!(message.click_event, Some(ClickEvent { action: ClickAction::RunCommand, value: "/help".to_string() }));
+        assert_eq!(message.hover_event, Some(HoverEvent::ShowText(Box::new(text("legacy")))));
+        assert_eq!(message.extra[0].italic, Some(false));
+    }
+
+    #[test]
+    fn parse_malformed() {
+        for json in [
+            "",
+            "{",
+            r#"{"text":"a""#,
+            "null",
+            "[]",
+            "{}",
+            r#"{"bold":true}"#,
+            r#"{"text":{}}"#,
+            r#"{"translate":"a","with":"b"}"#,
+            r#"{"translate":"a","with":[null]}"#,
+            r#"{"text":"a","extra":"b"}"#,
+            r#"{"text":"a","clickEvent":{"action":"hack","value":""}}"#,
+            r#"{"text":"a","hoverEvent":{"action":"show_text"}}"#,
+            r#"{"nbt":"a"}"#,
+        ] {
+            assert!(FormattedChatMessage::from_json(json).is_err(), "{} should be rejected", json);
+        }
+        assert!(matches!(FormattedChatMessage::from_json("{"), Err(ComponentError::Json(_))));
+        assert!(matches!(FormattedChatMessage::from_json("[]"), Err(ComponentError::Invalid(_))));
+    }
+}
\ No newline at end of file
@@ -0,0 +1,169 @@
+use crate::chat::{ansi::NAMED_COLORS, component::{FormattedChatMessage, ComponentContent}};
+
+/// Character which starts legacy formatting codes
+pub const LEGACY_CODE_CHAR: char = '§';
+
+/// Style of currently parsed legacy text segment
+#[derive(Debug, Clone, Default, PartialEq)]
+struct LegacyStyle {
+    color: Option<String>,
+    bold: bool,
+    italic: bool,
+    underlined: bool,
+    strikethrough: bool,
+    obfuscated: bool,
+}
+
+impl LegacyStyle {
+    /// Creates component with this style
+    fn component(&self, text: String) -> FormattedChatMessage {
+        let flag = |value: bool| if value { Some(true) } else { None };
+        FormattedChatMessage {
+            content: ComponentContent::Text(text),
+            color: self.color.clone(),
+            bold: flag(self.bold),
+            italic: flag(self.italic),
+            underlined: flag(self.underlined),
+            strikethrough: flag(self.strikethrough),
+            obfuscated: flag(self.obfuscated),
+            ..Default::default()
+        }
+    }
+}
+
+impl FormattedChatMessage {
+    /// Parses legacy `§`-formatted text (e.g. from MOTD or plugin messages) into component
+    ///
+    /// # Example
+    /// ```rs
+    /// let message = FormattedChatMessage::from_legacy("§aHello §lworld");
+    /// ```
+    pub fn from_legacy(text: &str) -> FormattedChatMessage {
+        Self::from_legacy_with(text, LEGACY_CODE_CHAR)
+    }
+
+    /// Parses legacy formatted text using custom code character (e.g. `&`)
+    ///
+    /// Supports colors (`0-9`, `a-f`), formats (`k-o`), reset (`r`) and hex colors (`§x§r§r§g§g§b§b`).
+    /// Unknown codes are kept in text. Returned component has empty text and segments as its children.
+    pub fn from_legacy_with(text: &str, code_char: char) -> FormattedChatMessage {
+        let mut extra = Vec::new();
+        let mut style = LegacyStyle::default();
+        let mut segment = String::new();
+        let chars = text.chars().collect::<Vec<char>>();
+        let mut i = 0;
+
+        while i < chars.len() {
+            if chars[i] != code_char || i + 1 >= chars.len() {
+                segment.push(chars[i]);
+                i += 1;
+                continue;
+            }
+
+            let code = chars[i + 1].to_ascii_lowercase();
+            let new_style = match code {
+                'x' => match parse_hex_color(&chars[i + 2..], code_char) {
+                    Some(color) => {
+                        i += 12; // Skip six `§<digit>` pairs
+                        Some(LegacyStyle { color: Some(color), ..Default::default() })
+                    },
+                    None => None,
+                },
+                'r' => Some(LegacyStyle::default()),
+                'k' => Some(LegacyStyle { obfuscated: true, ..style.clone() }),
+                'l' => Some(LegacyStyle { bold: true, ..style.clone() }),
+                'm' => Some(LegacyStyle { strikethrough: true, ..style.clone() }),
+                'n' => Some(LegacyStyle { underlined: true, ..style.clone() }),
+                'o' => Some(LegacyStyle { italic: true, ..style.clone() }),
+                // Color codes reset formatting (same as vanilla)
+                code => NAMED_COLORS.iter()
+                    .find(|(_, c, _)| *c == code)
+                    .map(|(name, _, _)| LegacyStyle { color: Some(name.to_string()), ..Default::default() }),
+            };
+
+            match new_style {
+                Some(new_style) => {
+                    if !segment.is_empty() {
+                        extra.push(style.component(std::mem::take(&mut segment)));
+                    }
+                    style = new_style;
+                },
+                None => {
+                    segment.push(chars[i]);
+                    segment.push(chars[i + 1]);
+                },
+            }
+            i += 2;
+        }
+
+        if !segment.is_empty() {
+            extra.push(style.component(segment));
+        }
+
+        FormattedChatMessage {
+            content: ComponentContent::Text(String::new()),
+            extra,
+            ..Default::default()
+        }
+    }
+}
+
+/// Parses hex color from `§r§r§g§g§b§b` (the part after `§x`)
+fn parse_hex_color(chars: &[char], code_char: char) -> Option<String> {
+    if chars.len() < 12 {
+        return None;
+    }
+
+    let mut color = String::from("#");
+    for pair in chars[..12].chunks(2) {
+        if pair[0] != code_char || !pair[1].is_ascii_hexdigit() {
+            return None;
+        }
+        color.push(pair[1].to_ascii_uppercase());
+    }
+    Some(color)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn segments(text: &str) -> Vec<(String, Option<String>, Option<bool>)> {
+        FormattedChatMessage::from_legacy(text).extra.into_iter()
+            .map(|segment| (segment.to_plain(), segment.color, segment.bold))
+            .collect()
+    }
+
+    fn segment(text: &str, color: Option<&str>, bold: Option<bool>) -> (String, Option<String>, Option<bool>) {
+        (text.to_string(), color.map(String::from), bold)
+    }
+
+    #[test]
+    fn parse_codes() {
+        assert_eq!(segments("plain"), vec![segment("plain", None, None)]);
+        assert_eq!(segments("§aHello §lworld"), vec![segment("Hello ", Some("green"), None), segment("world", Some("green"), Some(true))]);
+        // Color code resets formatting, reset code resets color too
+        assert_eq!(segments("§la§cb§rc"), vec![segment("a", None, Some(true)), segment("b", Some("red"), None), segment("c", None, None)]);
+        assert_eq!(segments("§L§Cx"), vec![segment("x", Some("red"), None)]);
+        // Unknown codes and code character at the end are kept
+        assert_eq!(segments("a§zb§"), vec![segment("a§zb§", None, None)]);
+        assert_eq!(segments("§"), vec![segment("§", None, None)]);
+        assert!(segments("§a§l").is_empty());
+
+        let message = FormattedChatMessage::from_legacy_with("&7[&oi&7]", '&');
+        assert_eq!(message.to_plain(), "[i]");
+        assert_eq!(message.extra[1].italic, Some(true));
+        assert_eq!(message.extra[1].color.as_deref(), Some("gray"));
+    }
+
+    #[test]
+    fn parse_hex_colors() {
+        assert_eq!(segments("§x§f§f§0§0§a§Bred"), vec![segment("red", Some("#FF00AB"), None)]);
+        // Hex color resets formatting
+        assert_eq!(segments("§l§x§1§2§3§4§5§6a"), vec![segment("a", Some("#123456"), None)]);
+        // Truncated or invalid hex colors are kept in text and the following codes are applied on their own
+        assert_eq!(segments("§x§f§fa"), vec![segment("§x", None, None), segment("a", Some("white"), None)]);
+        assert_eq!(segments("§x§f§f§0§0§a§g"), vec![segment("§x", None, None), segment("§g", Some("green"), None)]);
+        assert_eq!(segments("§x"), vec![segment("§x", None, None)]);
+    }
+}
\ No newline at end of file
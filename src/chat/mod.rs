@@ -0,0 +1,8 @@
+//! Chat related utilities which are not tied to any packet handler
+
+pub mod ansi;
+pub mod chat_type;
+pub mod component;
+pub mod legacy;
+pub mod signing;
+pub mod translate;
\ No newline at end of file
@@ -0,0 +1,225 @@
+use std::collections::HashMap;
+
+use miners_protocol::{utils::certificates::{PlayerCertificates, ProfilePublicKey}, packet::RawPacket};
+use sha2::{Sha256, Digest};
+
+use crate::chat::component::FormattedChatMessage;
+
+/// Maximum number of entries in last seen messages list
+pub const MAX_LAST_SEEN_ENTRIES: usize = 5;
+/// Number of received messages after which acknowledgment is sent to the server (same as vanilla)
+pub const ACKNOWLEDGMENT_THRESHOLD: usize = 64;
+
+/// Single entry of last seen messages list (last message signature of given player)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LastSeenEntry {
+    pub profile_id: u128,
+    pub signature: Vec<u8>,
+}
+
+impl LastSeenEntry {
+    /// Writes entry to the packet (UUID + length prefixed signature)
+    pub fn write_to(&self, packet: &mut RawPacket) {
+        packet.write_uuid(self.profile_id);
+        packet.write_byte_array(&self.signature);
+    }
+}
+
+/// List of last seen messages (one per player, the most recent one first)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LastSeenMessages {
+    pub entries: Vec<LastSeenEntry>,
+}
+
+impl LastSeenMessages {
+    /// Adds entry to the front of the list, removing older entries of the same player
+    pub fn push(&mut self, entry: LastSeenEntry) {
+        self.entries.retain(|e| e.profile_id != entry.profile_id);
+        self.entries.insert(0, entry);
+        self.entries.truncate(MAX_LAST_SEEN_ENTRIES);
+    }
+
+    /// Writes the list to the packet (VarInt length + entries)
+    pub fn write_to(&self, packet: &mut RawPacket) {
+        packet.write_varint(self.entries.len() as i32);
+        for entry in &self.entries {
+            entry.write_to(packet);
+        }
+    }
+
+    /// Feeds the list into message body hash
+    fn update_hash(&self, hasher: &mut Sha256) {
+        for entry in &self.entries {
+            hasher.update([b'F']);
+            // UUID is written most significant bits first, which is exactly what `write_uuid` does
+            let mut uuid = RawPacket::empty(0);
+            uuid.write_uuid(entry.profile_id);
+            hasher.update(&uuid.data);
+            hasher.update(&entry.signature);
+        }
+    }
+}
+
+/// Acknowledgment of received messages (sent with every chat message and in Message Acknowledgment packet)
+#[derive(Debug, Clone, Default)]
+pub struct MessageAcknowledgment {
+    pub last_seen: LastSeenMessages,
+    pub last_received: Option<LastSeenEntry>,
+}
+
+impl MessageAcknowledgment {
+    /// Writes acknowledgment to the packet
+    pub fn write_to(&self, packet: &mut RawPacket) {
+        self.last_seen.write_to(packet);
+        if let Some(last_received) = &self.last_received {
+            packet.write_bool(true);
+            last_received.write_to(packet);
+        } else {
+            packet.write_bool(false);
+        }
+    }
+}
+
+/// Result of incoming message signature verification
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VerificationStatus {
+    /// Signature is valid and message is properly chained with previous messages of the sender
+    Verified,
+    /// Message is not signed or the sender's public key is unknown (so it can't be verified)
+    Unsigned,
+    /// Signature doesn't match, key has expired or the message chain is broken
+    Invalid,
+}
+
+/// State of the secure chat session
+///
+/// Keeps player certificates, signature chain of sent messages and last seen messages
+/// as well as public keys and message chains of other players
+#[derive(Debug, Clone, Default)]
+pub struct ChatSession {
+    pub certificates: Option<PlayerCertificates>,
+    /// Header signature of the last message sent by this client (used to chain messages)
+    pub previous_signature: Option<Vec<u8>>,
+    pub last_seen: LastSeenMessages,
+    pub last_received: Option<LastSeenEntry>,
+    pub pending_acknowledgments: usize,
+
+    /// Public keys of other players (received in Player Info packet)
+    pub player_keys: HashMap<u128, ProfilePublicKey>,
+    /// Last header signature received from each player
+    pub player_chains: HashMap<u128, Vec<u8>>,
+}
+
+impl ChatSession {
+    pub fn new(certificates: Option<PlayerCertificates>) -> ChatSession {
+        ChatSession {
+            certificates,
+            ..Default::default()
+        }
+    }
+
+    /// Signs outgoing message and advances the signature chain
+    ///
+    /// Returns `None` if there are no certificates (message will be sent unsigned)
+    pub fn sign_message(&mut self, sender: u128, message: &str, timestamp: u64, salt: i64) -> Option<Vec<u8>> {
+        let certificates = self.certificates.as_ref()?;
+        if certificates.is_expired() {
+            log::warn!(target: "miners-client", "Player certificates have expired, message will not be accepted by the server");
+        }
+
+        let body_hash = message_body_hash(message, None, timestamp, salt, &self.last_seen);
+        let data = message_header_data(self.previous_signature.as_deref(), sender, &body_hash);
+        let signature = certificates.sign(&data);
+
+        self.previous_signature = Some(signature.clone());
+        Some(signature)
+    }
+
+    /// Records received player message
+    ///
+    /// Returns `true` if acknowledgment should be sent to the server
+    pub fn on_message_received(&mut self, sender: u128, signature: Vec<u8>) -> bool {
+        let entry = LastSeenEntry {
+            profile_id: sender,
+            signature,
+        };
+        self.last_seen.push(entry.clone());
+        self.last_received = Some(entry);
+        self.pending_acknowledgments += 1;
+
+        self.pending_acknowledgments > ACKNOWLEDGMENT_THRESHOLD
+    }
+
+    /// Verifies signature of incoming message header and its linkage with previous message of the sender
+    ///
+    /// Chain is advanced regardless of the result, so single broken message doesn't invalidate following ones
+    pub fn verify_message(&mut self, sender: u128, previous_signature: Option<&[u8]>, header_signature: &[u8], body_hash: &[u8]) -> VerificationStatus {
+        if header_signature.is_empty() {
+            return VerificationStatus::Unsigned;
+        }
+
+        // Check if message follows the last one we have seen from this player
+        let chain_valid = match (previous_signature, self.player_chains.get(&sender)) {
+            (Some(previous), Some(last)) => previous == last.as_slice(),
+            // Chain can't be restarted once we have seen it (otherwise messages could be spliced)
+            (None, Some(_)) => false,
+            (_, None) => true, // First message we see from this player
+        };
+        self.player_chains.insert(sender, header_signature.to_vec());
+
+        let Some(public_key) = self.player_keys.get(&sender) else {
+            return VerificationStatus::Unsigned;
+        };
+
+        let data = message_header_data(previous_signature, sender, body_hash);
+        if !chain_valid || public_key.is_expired() || !public_key.verify(&data, header_signature) {
+            log::debug!(target: "miners-client", "Invalid message signature from {:x} (chain valid: {})", sender, chain_valid);
+            return VerificationStatus::Invalid;
+        }
+
+        VerificationStatus::Verified
+    }
+
+    /// Forgets public key and message chain of the player (when player leaves)
+    pub fn remove_player(&mut self, uuid: u128) {
+        self.player_keys.remove(&uuid);
+        self.player_chains.remove(&uuid);
+    }
+
+    /// Returns current acknowledgment and resets pending acknowledgments counter
+    pub fn acknowledge(&mut self) -> MessageAcknowledgment {
+        self.pending_acknowledgments = 0;
+        MessageAcknowledgment {
+            last_seen: self.last_seen.clone(),
+            last_received: self.last_received.take(),
+        }
+    }
+}
+
+/// Computes SHA-256 hash of the message body (same as vanilla `MessageBody#hash`)
+///
+/// `timestamp` is in milliseconds, but only seconds are hashed.
+/// `decorated` content is hashed as "stable" JSON (see [`FormattedChatMessage::to_stable_json`])
+pub fn message_body_hash(message: &str, decorated: Option<&FormattedChatMessage>, timestamp: u64, salt: i64, last_seen: &LastSeenMessages) -> Vec<u8> {
+    let mut hasher = Sha256::new();
+    hasher.update(salt.to_be_bytes());
+    hasher.update((timestamp / 1000).to_be_bytes());
+    hasher.update(message.as_bytes());
+    hasher.update([b'F']);
+    if let Some(decorated) = decorated {
+        hasher.update(decorated.to_stable_json().as_bytes());
+    }
+    last_seen.update_hash(&mut hasher);
+    hasher.finalize().to_vec()
+}
+
+/// Builds data that is signed in message header (previous signature + sender UUID + body hash)
+pub fn message_header_data(previous_signature: Option<&[u8]>, sender: u128, body_hash: &[u8]) -> Vec<u8> {
+    let mut data = RawPacket::empty(0);
+    if let Some(previous_signature) = previous_signature {
+        data.write_bytes(previous_signature.to_vec());
+    }
+    data.write_uuid(sender);
+    data.write_bytes(body_hash.to_vec());
+    data.data
+}
@@ -0,0 +1,184 @@
+use std::{collections::HashMap, sync::OnceLock};
+
+use crate::chat::component::{FormattedChatMessage, ComponentContent};
+
+/// Translations used when no language file is loaded (the most common chat, join and death messages)
+const BUILTIN_TRANSLATIONS: &[(&str, &str)] = &[
+    ("chat.type.text", "<%s> %s"),
+    ("chat.type.text.narrate", "%s says %s"),
+    ("chat.type.announcement", "[%s] %s"),
+    ("chat.type.emote", "* %s %s"),
+    ("chat.type.admin", "[%s: %s]"),
+    ("chat.type.team.text", "%s <%s> %s"),
+    ("chat.type.team.sent", "-> %s <%s> %s"),
+    ("commands.message.display.incoming", "%s whispers to you: %s"),
+    ("commands.message.display.outgoing", "You whisper to %s: %s"),
+    ("multiplayer.player.joined", "%s joined the game"),
+    ("multiplayer.player.joined.renamed", "%s (formerly known as %s) joined the game"),
+    ("multiplayer.player.left", "%s left the game"),
+    ("death.attack.generic", "%1$s died"),
+    ("death.attack.mob", "%1$s was slain by %2$s"),
+    ("death.attack.player", "%1$s was slain by %2$s"),
+    ("death.attack.arrow", "%1$s was shot by %2$s"),
+    ("death.attack.explosion", "%1$s blew up"),
+    ("death.attack.fall", "%1$s hit the ground too hard"),
+    ("death.fell.accident.generic", "%1$s fell from a high place"),
+    ("death.attack.outOfWorld", "%1$s fell out of the world"),
+    ("death.attack.drown", "%1$s drowned"),
+    ("death.attack.lava", "%1$s tried to swim in lava"),
+    ("death.attack.inFire", "%1$s went up in flames"),
+    ("death.attack.onFire", "%1$s burned to death"),
+    ("death.attack.starve", "%1$s starved to death"),
+];
+
+/// Language used to resolve translation keys (loaded from `en_us.json`-like file)
+///
+/// # Example
+/// ```rs
+/// let language = Language::from_file("en_us.json")?;
+/// let text = message.to_plain_with(&language);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Language {
+    pub translations: HashMap<String, String>,
+}
+
+impl Default for Language {
+    /// Language with built-in translations of the most common messages
+    fn default() -> Self {
+        Language {
+            translations: BUILTIN_TRANSLATIONS.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+        }
+    }
+}
+
+impl Language {
+    /// Parses language from JSON (flat object of `key: format` pairs)
+    ///
+    /// Built-in translations are kept for keys missing in the file
+    pub fn from_json(json: &str) -> Result<Language, serde_json::Error> {
+        let translations: HashMap<String, String> = serde_json::from_str(json)?;
+        let mut language = Language::default();
+        language.translations.extend(translations);
+        Ok(language)
+    }
+
+    /// Loads language from JSON file (e.g. `en_us.json` from the game assets)
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> std::io::Result<Language> {
+        let json = std::fs::read_to_string(path)?;
+        Self::from_json(&json).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+    }
+
+    /// Returns built-in language shared by all components rendered without explicit language
+    pub fn builtin() -> &'static Language {
+        static BUILTIN: OnceLock<Language> = OnceLock::new();
+        BUILTIN.get_or_init(Language::default)
+    }
+
+    /// Returns format string of the key
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.translations.get(key).map(String::as_str)
+    }
+
+    /// Translates key substituting arguments
+    ///
+    /// Unknown keys are used as the format string and invalid formats are rendered as the format string itself (same as vanilla)
+    pub fn translate(&self, key: &str, args: &[String]) -> String {
+        let format_string = self.get(key).unwrap_or(key);
+        format(format_string, args).unwrap_or_else(|| format_string.to_string())
+    }
+}
+
+/// Part of parsed format string
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FormatPart {
+    Literal(String),
+    /// Index of the argument (0-based)
+    Argument(usize),
+}
+
+/// Parses Java-like format string into literal parts and argument references
+///
+/// Supports `%s` (next argument), `%1$s` (positional argument) and `%%` (literal percent sign).
+/// Returns `None` if the format is invalid or refers to missing argument (`args_count` is the number of arguments).
+pub fn parse_format(format_string: &str, args_count: usize) -> Option<Vec<FormatPart>> {
+    let mut parts = Vec::new();
+    let mut literal = String::new();
+    let mut chars = format_string.chars().peekable();
+    let mut next_index = 0;
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            literal.push(c);
+            continue;
+        }
+
+        // Optional argument index (`1$`)
+        let mut digits = String::new();
+        while let Some(d) = chars.peek().filter(|d| d.is_ascii_digit()) {
+            digits.push(*d);
+            chars.next();
+        }
+        let index = if digits.is_empty() {
+            None
+        } else {
+            if chars.next() != Some('$') {
+                return None;
+            }
+            Some(digits.parse::<usize>().ok()?.checked_sub(1)?)
+        };
+
+        match chars.next()? {
+            '%' if index.is_none() => literal.push('%'),
+            's' => {
+                let index = index.unwrap_or_else(|| {
+                    next_index += 1;
+                    next_index - 1
+                });
+                if index >= args_count {
+                    return None;
+                }
+                if !literal.is_empty() {
+                    parts.push(FormatPart::Literal(std::mem::take(&mut literal)));
+                }
+                parts.push(FormatPart::Argument(index));
+            },
+            _ => return None,
+        }
+    }
+
+    if !literal.is_empty() {
+        parts.push(FormatPart::Literal(literal));
+    }
+    Some(parts)
+}
+
+/// Substitutes arguments into Java-like format string (see [`parse_format`])
+pub fn format(format_string: &str, args: &[String]) -> Option<String> {
+    let parts = parse_format(format_string, args.len())?;
+    Some(parts.into_iter().map(|part| match part {
+        FormatPart::Literal(text) => text,
+        FormatPart::Argument(index) => args[index].clone(),
+    }).collect())
+}
+
+impl FormattedChatMessage {
+    /// Renders component (with all its children) to plain text, resolving translation keys using `language`
+    pub fn to_plain_with(&self, language: &Language) -> String {
+        let mut result = match &self.content {
+            ComponentContent::Text(text) => text.clone(),
+            ComponentContent::Translatable { key, with } => {
+                let args = with.iter().map(|arg| arg.to_plain_with(language)).collect::<Vec<String>>();
+                language.translate(key, &args)
+            },
+            ComponentContent::Score { value, .. } => value.clone().unwrap_or_default(),
+            ComponentContent::Selector { selector, .. } => selector.clone(),
+            ComponentContent::Keybind(keybind) => language.get(keybind).unwrap_or(keybind).to_string(),
+            ComponentContent::Nbt { path, .. } => path.clone(),
+        };
+        for child in &self.extra {
+            result.push_str(&child.to_plain_with(language));
+        }
+        result
+    }
+}
@@ -1,8 +1,8 @@
-use std::{collections::BTreeMap, sync::{Arc, Mutex, RwLock, RwLockWriteGuard, RwLockReadGuard}, time::Duration};
+use std::{collections::BTreeMap, sync::{Arc, Mutex, RwLock, RwLockWriteGuard, RwLockReadGuard}, time::{Duration, Instant}};
 
-use miners_protocol::{RawMinecraftSocket, LoginConfig, packet::RawPacket};
+use miners_protocol::{RawMinecraftSocket, LoginConfig, packet::{RawPacket, IntoPacket}, utils::{certificates::PlayerCertificates, location::Vec3}};
 
-use crate::{events::{ClientEventDispatcher, ClientEvent, basic::SpawnEvent}, handlers::register_all_handlers};
+use crate::{chat::{signing::ChatSession, translate::Language, chat_type::ChatType}, registry::{Registries, DimensionType}, players::{PlayerList, TabList}, entities::Entities, player::{PlayerState, rotation_towards}, physics::{self, Controls}, world::World, pathfinder::{Pathfinder, PathfinderAction, PathFoundEvent, PathFailedEvent, GoalReachedEvent, PlayerActionPacket, UseItemOnPacket, goals::Goal}, commands::CommandTree, requests::PendingRequests, handlers::blocks::BlockChangeEvent, handlers::suggestions::{Suggestion, CommandSuggestionsRequestPacket, CommandSuggestionsResponse}, events::{ClientEventDispatcher, ClientEvent, basic::SpawnEvent}, handlers::register_all_handlers};
 
 /// Minecraft client, used to connect to the server and handle events as well as packets
 /// It is passed to event handlers as `ClientMutLock` (which is just `Arc<RwLock<MinecraftClient>>`)
@@ -25,7 +25,25 @@ pub struct MinecraftClient {
     pub socket: RawMinecraftSocket,
     pub username: String,
     pub uuid: u128,
+    /// Network id of the player's entity
+    pub entity_id: i32,
 
+    pub(crate) chat_session: ChatSession,
+    pub(crate) language: Arc<Language>,
+    pub(crate) registries: Registries,
+    /// Name of the current dimension type (e.g. `minecraft:overworld`)
+    pub(crate) dimension_type: String,
+    pub(crate) commands: Option<CommandTree>,
+    pub(crate) player_list: PlayerList,
+    pub(crate) tab_list: TabList,
+    pub(crate) entities: Entities,
+    pub(crate) world: World,
+    pub(crate) player: PlayerState,
+    pub(crate) controls: Controls,
+    pub(crate) pathfinder: Pathfinder,
+    /// Sequence number of the last block action (acknowledged by the server)
+    pub(crate) block_sequence: i32,
+    pub(crate) pending_requests: PendingRequests,
     pub(crate) event_dispatcher: ClientEventDispatcher, 
     pub(crate) client_packet_handlers: BTreeMap<i32, Vec<Arc<Mutex<dyn ClientPacketHandler + Send + Sync + 'static>>>>,
 }
@@ -37,6 +55,16 @@ pub struct ClientConfig {
     pub username: String,
     pub host: String,
     pub port: u16,
+    /// Player certificates used for signing chat messages
+    /// (load them with `PlayerCertificates::from_json` or `PlayerCertificates::from_file`)
+    pub certificates: Option<PlayerCertificates>,
+    /// UUID of the profile (required by servers that verify player certificates)
+    ///
+    /// Uses the layout of `RawPacket::read_uuid` (most significant long in the low 64 bits),
+    /// so a UUID parsed with `u128::from_str_radix` needs its halves swapped (`uuid.rotate_left(64)`)
+    pub uuid: Option<u128>,
+    /// Language used for rendering translatable messages (load it with `Language::from_file("en_us.json")`)
+    pub language: Language,
 }
 
 impl Default for ClientConfig {
@@ -45,12 +73,21 @@ impl Default for ClientConfig {
             username: String::from("miners_client"),
             host: String::from("localhost"),
             port: 25565,
+            certificates: None,
+            uuid: None,
+            language: Language::default(),
         }
     }
 }
 
 pub type ClientMutLock = Arc<RwLock<MinecraftClient>>;
 
+/// Duration of a single game tick (the game runs at 20 ticks per second)
+pub const TICK_DURATION: Duration = Duration::from_millis(50);
+
+/// Default time to wait for the response to a request
+pub const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(5);
+
 impl Into<u128> for MinecraftClient {
     fn into(self) -> u128 {
         self.uuid
@@ -64,14 +101,41 @@ impl MinecraftClient {
             username: client_config.username.clone(),
             host: client_config.host.clone(),
             port: client_config.port,
+            certificates: client_config.certificates.clone(),
+            uuid: client_config.uuid,
         }).unwrap();
 
         let uuid = socket.uuid;
+        let (registries, dimension_type, dimension_name, entity_id) = match &socket.login_play {
+            Some(login_play) => (Registries::from_codec(&login_play.nbt_registry_codec), login_play.dimension_type.clone(), login_play.dimension_name.clone(), login_play.id),
+            None => (Registries::default(), String::new(), String::new(), -1),
+        };
+        let mut world = World {
+            version: miners_data::Version::from_protocol(socket.protocol_version),
+            biome_names: registries.biomes.entries.iter().map(|entry| (entry.id, entry.name.clone())).collect(),
+            ..World::default()
+        };
+        world.set_dimension(&dimension_name, registries.dimension_types.get_by_name(&dimension_type));
         let mut mc = MinecraftClient {
             socket,
             username: client_config.username,
             uuid,
+            entity_id,
 
+            chat_session: ChatSession::new(client_config.certificates),
+            language: Arc::new(client_config.language),
+            registries,
+            dimension_type,
+            commands: None,
+            player_list: PlayerList::default(),
+            tab_list: TabList::default(),
+            entities: Entities::default(),
+            world,
+            player: PlayerState::default(),
+            controls: Controls::default(),
+            pathfinder: Pathfinder::default(),
+            block_sequence: 0,
+            pending_requests: PendingRequests::new(),
             event_dispatcher: ClientEventDispatcher::new(),
             client_packet_handlers: BTreeMap::new(),
         };
@@ -81,6 +145,132 @@ impl MinecraftClient {
         mc
     }
 
+    /// Returns command tree of the server (`None` if it hasn't been received yet)
+    pub fn commands(&self) -> Option<&CommandTree> {
+        self.commands.as_ref()
+    }
+
+    /// Returns chat type with specified network id (from `minecraft:chat_type` registry)
+    pub fn chat_type(&self, id: i32) -> Option<&ChatType> {
+        self.registries.chat_types.get(id)
+    }
+
+    /// Returns registries sent by the server (dimension types, biomes and chat types)
+    pub fn registries(&self) -> &Registries {
+        &self.registries
+    }
+
+    /// Returns type of the current dimension (`None` if the server didn't send it)
+    pub fn dimension(&self) -> Option<&DimensionType> {
+        self.registries.dimension_types.get_by_name(&self.dimension_type)
+    }
+
+    /// Returns list of players on the server (the tab list)
+    pub fn player_list(&self) -> &PlayerList {
+        &self.player_list
+    }
+
+    /// Returns header and footer of the tab list
+    pub fn tab_list(&self) -> &TabList {
+        &self.tab_list
+    }
+
+    /// Returns position of player's feet (zero until the server sends it)
+    pub fn position(&self) -> Vec3 {
+        self.player.position
+    }
+
+    /// Returns yaw and pitch of the player (in degrees)
+    pub fn rotation(&self) -> (f32, f32) {
+        (self.player.yaw, self.player.pitch)
+    }
+
+    /// Returns whether the player stands on the ground
+    pub fn on_ground(&self) -> bool {
+        self.player.on_ground
+    }
+
+    /// Returns velocity of the player (in blocks per tick)
+    pub fn velocity(&self) -> Vec3 {
+        self.player.velocity
+    }
+
+    /// Returns movement controls (keys pressed by the player)
+    pub fn controls(&self) -> &Controls {
+        &self.controls
+    }
+
+    /// Returns movement controls which can be changed to move the player (applied in the next tick)
+    /// 
+    /// # Example
+    /// ```rs
+    /// let mut client = client.wl();
+    /// client.controls_mut().forward = true;
+    /// client.controls_mut().sprint = true;
+    /// ```
+    pub fn controls_mut(&mut self) -> &mut Controls {
+        &mut self.controls
+    }
+
+    /// Starts walking to the goal (path is computed in the next tick)
+    /// 
+    /// # Example
+    /// ```rs
+    /// client.goto(GoalNear { position: BlockPos::new(100, 64, -20), radius: 2.0 });
+    /// client.once(|_, _: &GoalReachedEvent| println!("Arrived!"));
+    /// ```
+    pub fn goto(&mut self, goal: impl Goal + 'static) {
+        self.pathfinder.set_goal(Box::new(goal));
+    }
+
+    /// Stops walking to the current goal
+    pub fn stop_pathfinding(&mut self) {
+        self.pathfinder.stop();
+        self.controls.clear();
+    }
+
+    /// Returns pathfinder (current goal, path and settings)
+    pub fn pathfinder(&self) -> &Pathfinder {
+        &self.pathfinder
+    }
+
+    /// Returns pathfinder which can be used to change its settings
+    pub fn pathfinder_mut(&mut self) -> &mut Pathfinder {
+        &mut self.pathfinder
+    }
+
+    /// Rotates the player (sent to the server in the next tick)
+    pub fn set_rotation(&mut self, yaw: f32, pitch: f32) {
+        self.player.yaw = yaw;
+        self.player.pitch = pitch;
+    }
+
+    /// Rotates the player to look at the point (sent to the server in the next tick)
+    pub fn look_at(&mut self, target: Vec3) {
+        let (yaw, pitch) = rotation_towards(self.player.eye_position(), target);
+        self.set_rotation(yaw, pitch);
+    }
+
+    /// Returns chunks loaded by the client
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Returns mutable world (e.g. to replace physics of block states)
+    pub fn world_mut(&mut self) -> &mut World {
+        &mut self.world
+    }
+
+    /// Returns entities tracked by the client (other players, mobs, items, etc.)
+    pub fn entities(&self) -> &Entities {
+        &self.entities
+    }
+
+    /// Returns language used for rendering translatable messages
+    pub fn language(&self) -> Arc<Language> {
+        self.language.clone()
+    }
+
     /// Register new event handler that can be called only once (must be `Send + Sync` as it runs in a separate thread)
     pub fn once<E: ClientEvent + Send + Sync + 'static, F: Fn(ClientMutLock, &E) + Send + Sync + 'static>(&mut self, handler: F) {
         self.event_dispatcher.register_handler_once(handler);
@@ -123,6 +313,47 @@ impl MinecraftClient {
         }
     }
 
+    /// Sends request packet and waits for the matching response (blocking)
+    /// 
+    /// `build` receives transaction id which must be included in the request.
+    /// Returns `None` if the response doesn't arrive before `timeout`.
+    /// 
+    /// **Warning:** This must not be called from packet handlers (they block receiving of packets)
+    pub fn request<P: IntoPacket, F: FnOnce(i32) -> P>(_self: &ClientMutLock, response_id: i32, timeout: Duration, build: F) -> Option<RawPacket> {
+        let (transaction_id, receiver) = {
+            let mut _self = _self.write().unwrap();
+            let (transaction_id, receiver) = _self.pending_requests.register(response_id);
+            _self.socket.send_packet(build(transaction_id)).ok()?;
+            (transaction_id, receiver)
+        }; // Unlock, so response can be handled
+
+        match receiver.recv_timeout(timeout) {
+            Ok(packet) => Some(packet),
+            Err(_) => {
+                log::debug!(target: "miners-client", "Request {} timed out", transaction_id);
+                _self.write().unwrap().pending_requests.cancel(response_id, transaction_id);
+                None
+            }
+        }
+    }
+
+    /// Requests tab-completion of the text (e.g. `/tp Ste`) and waits for suggestions (blocking)
+    /// 
+    /// Returns empty list if server doesn't respond within [`DEFAULT_REQUEST_TIMEOUT`]
+    pub fn complete(_self: &ClientMutLock, text: &str) -> Vec<Suggestion> {
+        Self::complete_with_timeout(_self, text, DEFAULT_REQUEST_TIMEOUT)
+    }
+
+    /// Same as [`complete`](MinecraftClient::complete), but with custom timeout
+    pub fn complete_with_timeout(_self: &ClientMutLock, text: &str, timeout: Duration) -> Vec<Suggestion> {
+        let response = Self::request(_self, 0x0E, timeout, |transaction_id| CommandSuggestionsRequestPacket {
+            transaction_id,
+            text: text.to_string(),
+        });
+
+        response.map(|packet| CommandSuggestionsResponse::from(packet).suggestions).unwrap_or_default()
+    }
+
     /// Disconnects from the server and emits `DisconnectEvent`
     pub fn disconnect(&mut self) {
         // TODO: Stop all threads and stuff
@@ -130,39 +361,84 @@ impl MinecraftClient {
         self.emit(crate::events::basic::DisconnectEvent);
     }
 
-    /// Starts listening for packets and dispatching events (blocking)
+    /// Runs single game tick (simulates physics and sends movement packets)
+    pub fn tick(&mut self) {
+        let actions = self.pathfinder.tick(&mut self.player, &mut self.controls, &self.world, &self.entities);
+        for action in actions {
+            self.handle_pathfinder_action(action);
+        }
+        physics::simulate(&mut self.player, &self.controls, &self.world);
+
+        for packet in self.player.command_packets(self.entity_id) {
+            self.socket.send_packet(packet).ok();
+        }
+        if let Some(packet) = self.player.movement_packet() {
+            self.socket.send_packet(packet).ok();
+        }
+    }
+
+    fn handle_pathfinder_action(&mut self, action: PathfinderAction) {
+        match action {
+            PathfinderAction::PathFound(path) => self.emit(PathFoundEvent { path }),
+            PathfinderAction::PathFailed(reason) => self.emit(PathFailedEvent { reason }),
+            PathfinderAction::GoalReached => self.emit(GoalReachedEvent),
+            PathfinderAction::StartDigging(position) | PathfinderAction::FinishDigging(position) | PathfinderAction::BreakInstantly(position) => {
+                let status = if matches!(action, PathfinderAction::FinishDigging(_)) { 2 } else { 0 };
+                self.block_sequence += 1;
+                if !matches!(action, PathfinderAction::StartDigging(_)) {
+                    // Block is broken right away, the server state is restored if the server disagrees
+                    if let Some(old) = self.world.predict_block_state(position, 0, self.block_sequence).filter(|old| *old != 0) {
+                        self.emit(BlockChangeEvent { pos: position, old, new: 0 });
+                    }
+                }
+                self.socket.send_packet(PlayerActionPacket {
+                    status,
+                    position,
+                    face: crate::pathfinder::BlockFace::Top,
+                    sequence: self.block_sequence,
+                }).ok();
+            },
+            PathfinderAction::Place { against, face } => {
+                self.block_sequence += 1;
+                self.socket.send_packet(UseItemOnPacket {
+                    hand: 0,
+                    position: against,
+                    face,
+                    cursor: Vec3::new(0.5, 0.5, 0.5),
+                    inside_block: false,
+                    sequence: self.block_sequence,
+                }).ok();
+            },
+        }
+    }
+
+    /// Starts listening for packets, dispatching events and running game ticks (blocking)
     pub fn start(mut self) {
         register_all_handlers(&mut self);
         let _self = Arc::new(RwLock::new(self));
+        let mut next_tick = Instant::now();
         loop {
             // Dispatch events
             ClientEventDispatcher::dispatch_all(_self.clone());
 
+            // Run game tick (skip missed ticks instead of running them all at once)
+            if Instant::now() >= next_tick {
+                _self.write().unwrap().tick();
+                next_tick = Instant::max(next_tick + TICK_DURATION, Instant::now());
+            }
+
             // Handle packets
-            let packet = {
-                loop {
-                    let mut _self = _self.read().unwrap();
-                    match _self.socket.expect_packet() {
-                        Ok(packet) => break Ok(packet),
-                        Err(e) => {
-                            println!("{:?}", e);
-                            if e.get_text() != "No data to read" {
-                                log::error!(target: "miners-client", "Error receiving packet: {:?}", e);
-                                break Err(e);
-                            }
-                        }
+            let packet = _self.read().unwrap().socket.expect_packet();
+            match packet {
+                Ok(packet) => MinecraftClient::handle_packet(_self.clone(), packet),
+                Err(e) => {
+                    println!("{:?}", e);
+                    if e.get_text() != "No data to read" {
+                        log::error!(target: "miners-client", "Error receiving packet: {:?}", e);
+                        break;
                     }
-                    drop(_self); // Unlock mutex
                     std::thread::sleep(Duration::from_millis(10));
                 }
-            };
-
-            if let Ok(packet) = packet {
-                let _self = _self.clone();
-                MinecraftClient::handle_packet(_self, packet);
-            } else {
-                log::error!(target: "miners-client", "Error receiving packet: {:?}", packet);
-                break;
             }
         }
     }
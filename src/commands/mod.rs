@@ -0,0 +1,514 @@
+//! Brigadier command tree (sent by the server in Commands packet)
+//!
+//! The tree can be used to discover commands supported by the server,
+//! validate commands before sending them and find arguments which must be signed.
+
+use miners_protocol::packet::RawPacket;
+
+/// Graph of command nodes (as sent by the server)
+#[derive(Debug, Clone)]
+pub struct CommandTree {
+    pub nodes: Vec<CommandNode>,
+    pub root: usize,
+}
+
+/// Single node of the command tree
+#[derive(Debug, Clone)]
+pub struct CommandNode {
+    pub kind: CommandNodeKind,
+    /// Whether command can be executed when it ends at this node
+    pub executable: bool,
+    /// Indices of child nodes
+    pub children: Vec<usize>,
+    /// Index of the node this node redirects to (e.g. `/execute run` redirects to root)
+    pub redirect: Option<usize>,
+    /// Suggestions type (e.g. `minecraft:ask_server`)
+    pub suggestions: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub enum CommandNodeKind {
+    Root,
+    Literal(String),
+    Argument {
+        name: String,
+        parser: ArgumentParser,
+    },
+}
+
+/// Behavior of `brigadier:string` parser
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StringKind {
+    SingleWord,
+    QuotablePhrase,
+    GreedyPhrase,
+}
+
+/// Parser of the argument node with its properties
+#[derive(Debug, Clone, PartialEq)]
+pub enum ArgumentParser {
+    Bool,
+    Float { min: Option<f32>, max: Option<f32> },
+    Double { min: Option<f64>, max: Option<f64> },
+    Integer { min: Option<i32>, max: Option<i32> },
+    Long { min: Option<i64>, max: Option<i64> },
+    String(StringKind),
+    Entity { single: bool, players_only: bool },
+    ScoreHolder { allow_multiple: bool },
+    ResourceOrTag { registry: String },
+    Resource { registry: String },
+    /// Any other parser that has no properties (e.g. `minecraft:block_pos`)
+    Other(String),
+}
+
+/// Names of parsers without properties, indexed by parser id (1.19.x)
+const PARSER_NAMES: &[&str] = &[
+    "brigadier:bool", "brigadier:float", "brigadier:double", "brigadier:integer", "brigadier:long", "brigadier:string",
+    "minecraft:entity", "minecraft:game_profile", "minecraft:block_pos", "minecraft:column_pos", "minecraft:vec3",
+    "minecraft:vec2", "minecraft:block_state", "minecraft:block_predicate", "minecraft:item_stack",
+    "minecraft:item_predicate", "minecraft:color", "minecraft:component", "minecraft:message", "minecraft:nbt_compound_tag",
+    "minecraft:nbt_tag", "minecraft:nbt_path", "minecraft:objective", "minecraft:objective_criteria", "minecraft:operation",
+    "minecraft:particle", "minecraft:angle", "minecraft:rotation", "minecraft:scoreboard_slot", "minecraft:score_holder",
+    "minecraft:swizzle", "minecraft:team", "minecraft:item_slot", "minecraft:resource_location", "minecraft:mob_effect",
+    "minecraft:function", "minecraft:entity_anchor", "minecraft:int_range", "minecraft:float_range",
+    "minecraft:item_enchantment", "minecraft:entity_summon", "minecraft:dimension", "minecraft:time",
+    "minecraft:resource_or_tag", "minecraft:resource", "minecraft:template_mirror", "minecraft:template_rotation",
+    "minecraft:uuid",
+];
+
+impl ArgumentParser {
+    /// Reads parser id and its properties from the packet
+    ///
+    /// Returns `None` for unknown parsers, as their properties can't be skipped
+    pub fn read_from(packet: &mut RawPacket) -> Option<ArgumentParser> {
+        let id = packet.read_varint();
+        let parser = match id {
+            0 => ArgumentParser::Bool,
+            1 => {
+                let flags = packet.read_byte();
+                let min = if flags & 0x01 != 0 { Some(packet.read_float()) } else { None };
+                let max = if flags & 0x02 != 0 { Some(packet.read_float()) } else { None };
+                ArgumentParser::Float { min, max }
+            },
+            2 => {
+                let flags = packet.read_byte();
+                let min = if flags & 0x01 != 0 { Some(packet.read_double()) } else { None };
+                let max = if flags & 0x02 != 0 { Some(packet.read_double()) } else { None };
+                ArgumentParser::Double { min, max }
+            },
+            3 => {
+                let flags = packet.read_byte();
+                let min = if flags & 0x01 != 0 { Some(packet.read_int()) } else { None };
+                let max = if flags & 0x02 != 0 { Some(packet.read_int()) } else { None };
+                ArgumentParser::Integer { min, max }
+            },
+            4 => {
+                let flags = packet.read_byte();
+                let min = if flags & 0x01 != 0 { Some(packet.read_long()) } else { None };
+                let max = if flags & 0x02 != 0 { Some(packet.read_long()) } else { None };
+                ArgumentParser::Long { min, max }
+            },
+            5 => ArgumentParser::String(match packet.read_varint() {
+                0 => StringKind::SingleWord,
+                1 => StringKind::QuotablePhrase,
+                2 => StringKind::GreedyPhrase,
+                _ => return None,
+            }),
+            6 => {
+                let flags = packet.read_byte();
+                ArgumentParser::Entity {
+                    single: flags & 0x01 != 0,
+                    players_only: flags & 0x02 != 0,
+                }
+            },
+            29 => ArgumentParser::ScoreHolder { allow_multiple: packet.read_byte() & 0x01 != 0 },
+            43 => ArgumentParser::ResourceOrTag { registry: packet.read_string() },
+            44 => ArgumentParser::Resource { registry: packet.read_string() },
+            _ => ArgumentParser::Other(PARSER_NAMES.get(usize::try_from(id).ok()?)?.to_string()),
+        };
+        Some(parser)
+    }
+
+    /// Returns identifier of the parser (e.g. `brigadier:integer`)
+    pub fn name(&self) -> &str {
+        match self {
+            ArgumentParser::Bool => "brigadier:bool",
+            ArgumentParser::Float { .. } => "brigadier:float",
+            ArgumentParser::Double { .. } => "brigadier:double",
+            ArgumentParser::Integer { .. } => "brigadier:integer",
+            ArgumentParser::Long { .. } => "brigadier:long",
+            ArgumentParser::String(_) => "brigadier:string",
+            ArgumentParser::Entity { .. } => "minecraft:entity",
+            ArgumentParser::ScoreHolder { .. } => "minecraft:score_holder",
+            ArgumentParser::ResourceOrTag { .. } => "minecraft:resource_or_tag",
+            ArgumentParser::Resource { .. } => "minecraft:resource",
+            ArgumentParser::Other(name) => name,
+        }
+    }
+
+    /// Whether argument must be signed when sending the command (it contains player's message)
+    pub fn is_signed(&self) -> bool {
+        self.name() == "minecraft:message"
+    }
+
+    /// Number of space separated words this parser consumes (`None` means the rest of the input)
+    fn word_count(&self) -> Option<usize> {
+        match self.name() {
+            "brigadier:string" if *self == ArgumentParser::String(StringKind::GreedyPhrase) => None,
+            "minecraft:message" => None,
+            "minecraft:block_pos" | "minecraft:vec3" => Some(3),
+            "minecraft:column_pos" | "minecraft:vec2" | "minecraft:rotation" => Some(2),
+            _ => Some(1),
+        }
+    }
+
+    /// Checks if the value can be parsed by this parser
+    ///
+    /// Only simple parsers are fully validated, complex ones (selectors, NBT, ...) are accepted as is
+    fn validate(&self, value: &str) -> bool {
+        fn in_range<T: PartialOrd>(value: T, min: Option<T>, max: Option<T>) -> bool {
+            min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max)
+        }
+
+        match self {
+            ArgumentParser::Bool => value == "true" || value == "false",
+            ArgumentParser::Float { min, max } => value.parse::<f32>().is_ok_and(|v| in_range(v, *min, *max)),
+            ArgumentParser::Double { min, max } => value.parse::<f64>().is_ok_and(|v| in_range(v, *min, *max)),
+            ArgumentParser::Integer { min, max } => value.parse::<i32>().is_ok_and(|v| in_range(v, *min, *max)),
+            ArgumentParser::Long { min, max } => value.parse::<i64>().is_ok_and(|v| in_range(v, *min, *max)),
+            ArgumentParser::String(StringKind::SingleWord) => !value.starts_with('"'),
+            _ => !value.is_empty(),
+        }
+    }
+}
+
+/// Argument of the parsed command
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedArgument {
+    pub name: String,
+    pub value: String,
+    pub parser: ArgumentParser,
+}
+
+/// Command parsed using the command tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParsedCommand {
+    /// Indices of nodes visited while parsing (literals and arguments)
+    pub path: Vec<usize>,
+    pub arguments: Vec<ParsedArgument>,
+}
+
+impl ParsedCommand {
+    /// Returns arguments which must be signed (name, value)
+    pub fn signed_arguments(&self) -> Vec<(String, String)> {
+        self.arguments.iter()
+            .filter(|a| a.parser.is_signed())
+            .map(|a| (a.name.clone(), a.value.clone()))
+            .collect()
+    }
+}
+
+/// Error returned when command doesn't match the command tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandError {
+    /// No command node matches the input at given position
+    Unknown { position: usize, input: String },
+    /// Command is incomplete (it doesn't end at executable node)
+    Incomplete,
+}
+
+impl CommandTree {
+    /// Checks that root, children and redirects of all nodes point to existing nodes
+    ///
+    /// Trees received from the server must be checked before use, as lookups of invalid nodes panic
+    pub fn is_valid(&self) -> bool {
+        let exists = |index: &usize| *index < self.nodes.len();
+        exists(&self.root) && self.nodes.iter().all(|node| node.children.iter().all(exists) && node.redirect.as_ref().is_none_or(exists))
+    }
+
+    /// Returns root node of the tree
+    pub fn root(&self) -> &CommandNode {
+        &self.nodes[self.root]
+    }
+
+    /// Returns names of all top level commands (e.g. `tp`, `msg`)
+    pub fn command_names(&self) -> Vec<String> {
+        self.root().children.iter()
+            .filter_map(|i| match &self.nodes[*i].kind {
+                CommandNodeKind::Literal(name) => Some(name.clone()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Checks if the server supports command with given name
+    pub fn has_command(&self, name: &str) -> bool {
+        self.command_names().iter().any(|n| n == name)
+    }
+
+    /// Parses command (without leading `/`) against the tree
+    pub fn parse(&self, command: &str) -> Result<ParsedCommand, CommandError> {
+        let mut parsed = ParsedCommand {
+            path: Vec::new(),
+            arguments: Vec::new(),
+        };
+        let mut furthest = 0;
+        if self.parse_node(self.root, command, 0, &mut parsed, &mut furthest) {
+            Ok(parsed)
+        } else if furthest >= command.len() {
+            Err(CommandError::Incomplete)
+        } else {
+            Err(CommandError::Unknown {
+                position: furthest,
+                input: command[furthest..].to_string(),
+            })
+        }
+    }
+
+    /// Tries to parse input starting at `cursor` using children of the node (literals first, like brigadier does)
+    fn parse_node(&self, index: usize, input: &str, cursor: usize, parsed: &mut ParsedCommand, furthest: &mut usize) -> bool {
+        *furthest = (*furthest).max(cursor);
+        let node = &self.nodes[index];
+
+        // Whole input has been consumed
+        if cursor >= input.len() {
+            return node.executable;
+        }
+
+        // Redirected nodes continue from the target's children (only if node has no children of its own)
+        let children = match node.redirect {
+            Some(redirect) if node.children.is_empty() => &self.nodes[redirect].children,
+            _ => &node.children,
+        };
+
+        let literals = children.iter().filter(|i| matches!(self.nodes[**i].kind, CommandNodeKind::Literal(_)));
+        let arguments = children.iter().filter(|i| matches!(self.nodes[**i].kind, CommandNodeKind::Argument { .. }));
+        for child in literals.chain(arguments) {
+            let Some(end) = self.match_node(*child, input, cursor) else {
+                continue;
+            };
+
+            // Save state so it can be restored if this branch fails
+            let (path_len, arguments_len) = (parsed.path.len(), parsed.arguments.len());
+            parsed.path.push(*child);
+            if let CommandNodeKind::Argument { name, parser } = &self.nodes[*child].kind {
+                parsed.arguments.push(ParsedArgument {
+                    name: name.clone(),
+                    value: input[cursor..end].to_string(),
+                    parser: parser.clone(),
+                });
+            }
+
+            // Skip separator
+            let next = if end < input.len() { end + 1 } else { end };
+            if (end == input.len() || input[end..].starts_with(' ')) && self.parse_node(*child, input, next, parsed, furthest) {
+                return true;
+            }
+
+            parsed.path.truncate(path_len);
+            parsed.arguments.truncate(arguments_len);
+        }
+
+        false
+    }
+
+    /// Returns end of the input matched by the node (if it matches)
+    fn match_node(&self, index: usize, input: &str, cursor: usize) -> Option<usize> {
+        match &self.nodes[index].kind {
+            CommandNodeKind::Root => None,
+            CommandNodeKind::Literal(literal) => {
+                let end = cursor + literal.len();
+                (input[cursor..].starts_with(literal.as_str()) && (end == input.len() || input[end..].starts_with(' '))).then_some(end)
+            },
+            CommandNodeKind::Argument { parser, .. } => {
+                let end = match parser.word_count() {
+                    None => input.len(),
+                    Some(count) => {
+                        let mut end = cursor;
+                        for i in 0..count {
+                            if i > 0 {
+                                if end >= input.len() {
+                                    return None;
+                                }
+                                end += 1; // Separator
+                            }
+                            end = read_word(input, end);
+                        }
+                        end
+                    }
+                };
+                (end > cursor && parser.validate(&input[cursor..end])).then_some(end)
+            },
+        }
+    }
+}
+
+/// Reads single word starting at `cursor`, spaces inside quotes and brackets (selectors, NBT, JSON) are kept
+fn read_word(input: &str, cursor: usize) -> usize {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in input[cursor..].char_indices() {
+        match (quote, c) {
+            (Some(_), _) if escaped => escaped = false,
+            (Some(_), '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {},
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '[' | '{' | '(') => depth += 1,
+            (None, ']' | '}' | ')') => depth -= 1,
+            (None, ' ') if depth <= 0 => return cursor + i,
+            _ => {},
+        }
+    }
+    input.len()
+}
+
+impl CommandTree {
+    /// Reads the tree from Commands packet
+    ///
+    /// Returns `None` if any argument node uses unknown parser (rest of the packet can't be read)
+    pub fn read(packet: &mut RawPacket) -> Option<CommandTree> {
+        let count = packet.read_varint();
+        let mut nodes = Vec::new();
+        for _ in 0..count {
+            let flags = packet.read_byte();
+            let children_count = packet.read_varint();
+            let mut children = Vec::new();
+            for _ in 0..children_count {
+                children.push(packet.read_varint() as usize);
+            }
+
+            let redirect = if flags & 0x08 != 0 {
+                Some(packet.read_varint() as usize)
+            } else {
+                None
+            };
+
+            let kind = match flags & 0x03 {
+                1 => CommandNodeKind::Literal(packet.read_string()),
+                2 => {
+                    let name = packet.read_string();
+                    let parser = ArgumentParser::read_from(packet)?;
+                    CommandNodeKind::Argument { name, parser }
+                },
+                _ => CommandNodeKind::Root,
+            };
+
+            let suggestions = if flags & 0x10 != 0 {
+                Some(packet.read_string())
+            } else {
+                None
+            };
+
+            nodes.push(CommandNode {
+                kind,
+                executable: flags & 0x04 != 0,
+                children,
+                redirect,
+                suggestions,
+            });
+        }
+        let root = packet.read_varint() as usize;
+
+        Some(CommandTree {
+            nodes,
+            root,
+        })
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Writes node to the packet (`parser` is id followed by its properties)
+    fn write_node(packet: &mut RawPacket, flags: u8, children: &[i32], redirect: Option<i32>, name: Option<&str>, parser: &[u8]) {
+        packet.write_byte(flags);
+        packet.write_varint(children.len() as i32);
+        for child in children {
+            packet.write_varint(*child);
+        }
+        if let Some(redirect) = redirect {
+            packet.write_varint(redirect);
+        }
+        if let Some(name) = name {
+            packet.write_string(name);
+        }
+        packet.write_bytes(parser.to_vec());
+    }
+
+    /// Root with `/tp <targets>`, `/say <message>` and `/execute run` redirecting to root
+    fn tree_packet() -> RawPacket {
+        let mut packet = RawPacket::empty(0x0F);
+        packet.write_varint(7);
+        write_node(&mut packet, 0x00, &[1, 3, 5], None, None, &[]);
+        write_node(&mut packet, 0x01, &[2], None, Some("tp"), &[]);
+        write_node(&mut packet, 0x02 | 0x04, &[], None, Some("targets"), &[6, 0x00]);
+        write_node(&mut packet, 0x01, &[4], None, Some("say"), &[]);
+        write_node(&mut packet, 0x02 | 0x04, &[], None, Some("message"), &[18]);
+        write_node(&mut packet, 0x01, &[6], None, Some("execute"), &[]);
+        write_node(&mut packet, 0x01 | 0x08, &[], Some(0), Some("run"), &[]);
+        packet.write_varint(0);
+        packet
+    }
+
+    #[test]
+    fn parse_tree() {
+        let tree = CommandTree::read(&mut tree_packet()).unwrap();
+        assert!(tree.is_valid());
+        assert_eq!(tree.command_names(), vec!["tp", "say", "execute"]);
+        assert!(matches!(&tree.nodes[2].kind, CommandNodeKind::Argument { name, parser: ArgumentParser::Entity { single: false, players_only: false } } if name == "targets"));
+        assert!(tree.nodes[4].executable);
+        assert_eq!(tree.nodes[6].redirect, Some(0));
+    }
+
+    #[test]
+    fn parse_commands() {
+        let tree = CommandTree::read(&mut tree_packet()).unwrap();
+
+        // Spaces inside selectors belong to the argument
+        let parsed = tree.parse("tp @e[type=cow, limit=1]").unwrap();
+        assert_eq!(parsed.path, vec![1, 2]);
+        assert_eq!(parsed.arguments[0].value, "@e[type=cow, limit=1]");
+        assert!(parsed.signed_arguments().is_empty());
+
+        // Redirect continues from the root
+        let parsed = tree.parse("execute run say hello there").unwrap();
+        assert_eq!(parsed.path, vec![5, 6, 3, 4]);
+        assert_eq!(parsed.signed_arguments(), vec![("message".to_string(), "hello there".to_string())]);
+
+        assert_eq!(tree.parse("tp"), Err(CommandError::Incomplete));
+        assert_eq!(tree.parse("execute run"), Err(CommandError::Incomplete));
+        assert_eq!(tree.parse("tpa Steve"), Err(CommandError::Unknown { position: 0, input: "tpa Steve".to_string() }));
+        assert_eq!(tree.parse("execute run kill"), Err(CommandError::Unknown { position: 12, input: "kill".to_string() }));
+    }
+
+    #[test]
+    fn parse_malformed() {
+        // Unknown parser id
+        let mut packet = RawPacket::empty(0x0F);
+        packet.write_varint(2);
+        write_node(&mut packet, 0x00, &[1], None, None, &[]);
+        write_node(&mut packet, 0x02, &[], None, Some("value"), &[48, 0x01]);
+        packet.write_varint(0);
+        assert!(CommandTree::read(&mut packet).is_none());
+
+        // Unknown string kind
+        let mut packet = RawPacket::empty(0x0F);
+        packet.write_varint(2);
+        write_node(&mut packet, 0x00, &[1], None, None, &[]);
+        write_node(&mut packet, 0x02, &[], None, Some("value"), &[5, 3]);
+        packet.write_varint(0);
+        assert!(CommandTree::read(&mut packet).is_none());
+
+        // Redirect to nonexistent node
+        let mut packet = RawPacket::empty(0x0F);
+        packet.write_varint(2);
+        write_node(&mut packet, 0x00, &[1], None, None, &[]);
+        write_node(&mut packet, 0x01 | 0x08, &[], Some(5), Some("run"), &[]);
+        packet.write_varint(0);
+        assert!(!CommandTree::read(&mut packet).unwrap().is_valid());
+    }
+}
@@ -0,0 +1,461 @@
+use std::collections::BTreeMap;
+
+use miners_protocol::{packet::RawPacket, utils::{location::BlockPos, nbt::NBTType}};
+
+use crate::{chat::component::FormattedChatMessage, entities::EntityKind};
+
+/// Item stack (e.g. item lying on the ground)
+#[derive(Debug, Clone)]
+pub struct Slot {
+    /// Id from `minecraft:item` registry
+    pub item_id: i32,
+    pub count: i8,
+    pub nbt: Option<NBTType>,
+}
+
+impl Slot {
+    /// Reads slot from the packet (`Some(None)` if the slot is empty)
+    ///
+    /// Returns None if the packet is truncated or item NBT is malformed
+    pub fn read(packet: &mut RawPacket) -> Option<Option<Slot>> {
+        if !packet.try_read_bool()? {
+            return Some(None);
+        }
+        let item_id = packet.try_read_varint()?;
+        let count = packet.try_read_byte()? as i8;
+        let nbt = NBTType::from_packet_optional(packet).ok()?;
+
+        Some(Some(Slot {
+            item_id,
+            count,
+            nbt,
+        }))
+    }
+
+    /// Returns item of the slot using data of the version
+    pub fn item(&self, version: miners_data::Version) -> Option<miners_data::Item> {
+        miners_data::Item::from_id(version, self.item_id)
+    }
+}
+
+/// Pose of the entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Pose {
+    Standing,
+    FallFlying,
+    Sleeping,
+    Swimming,
+    SpinAttack,
+    Sneaking,
+    LongJumping,
+    Dying,
+    Croaking,
+    UsingTongue,
+    Roaring,
+    Sniffing,
+    Emerging,
+    Digging,
+    Other(i32),
+}
+
+impl From<i32> for Pose {
+    fn from(id: i32) -> Self {
+        match id {
+            0 => Pose::Standing,
+            1 => Pose::FallFlying,
+            2 => Pose::Sleeping,
+            3 => Pose::Swimming,
+            4 => Pose::SpinAttack,
+            5 => Pose::Sneaking,
+            6 => Pose::LongJumping,
+            7 => Pose::Dying,
+            8 => Pose::Croaking,
+            9 => Pose::UsingTongue,
+            10 => Pose::Roaring,
+            11 => Pose::Sniffing,
+            12 => Pose::Emerging,
+            13 => Pose::Digging,
+            id => Pose::Other(id),
+        }
+    }
+}
+
+/// Single value of entity metadata
+#[derive(Debug, Clone)]
+pub enum MetadataValue {
+    Byte(i8),
+    VarInt(i32),
+    Float(f32),
+    String(String),
+    Component(FormattedChatMessage),
+    OptionalComponent(Option<FormattedChatMessage>),
+    Slot(Option<Slot>),
+    Boolean(bool),
+    /// Rotation around X, Y and Z axes (in degrees)
+    Rotation(f32, f32, f32),
+    Position(BlockPos),
+    OptionalPosition(Option<BlockPos>),
+    /// Direction (0 - down, 1 - up, 2 - north, 3 - south, 4 - west, 5 - east)
+    Direction(i32),
+    OptionalUuid(Option<u128>),
+    /// Block state id (`None` for air)
+    OptionalBlockState(Option<i32>),
+    Nbt(Option<NBTType>),
+    /// Particle id (its additional data is not decoded)
+    Particle(i32),
+    VillagerData {
+        villager_type: i32,
+        profession: i32,
+        level: i32,
+    },
+    OptionalVarInt(Option<i32>),
+    Pose(Pose),
+    CatVariant(i32),
+    FrogVariant(i32),
+    /// Dimension name and position
+    OptionalGlobalPosition(Option<(String, BlockPos)>),
+    PaintingVariant(i32),
+}
+
+impl MetadataValue {
+    /// Reads value of specified type from the packet
+    ///
+    /// Returns None if the type is unknown or the packet is truncated
+    fn read(packet: &mut RawPacket, value_type: i32) -> Option<MetadataValue> {
+        let read_component = |packet: &mut RawPacket| {
+            let json = packet.try_read_string()?;
+            Some(FormattedChatMessage::from_json(&json).unwrap_or_else(|_| FormattedChatMessage::from_plain(json)))
+        };
+
+        Some(match value_type {
+            0 => MetadataValue::Byte(packet.try_read_byte()? as i8),
+            1 => MetadataValue::VarInt(packet.try_read_varint()?),
+            2 => MetadataValue::Float(packet.try_read_float()?),
+            3 => MetadataValue::String(packet.try_read_string()?),
+            4 => MetadataValue::Component(read_component(packet)?),
+            5 => MetadataValue::OptionalComponent(if packet.try_read_bool()? { Some(read_component(packet)?) } else { None }),
+            6 => MetadataValue::Slot(Slot::read(packet)?),
+            7 => MetadataValue::Boolean(packet.try_read_bool()?),
+            8 => MetadataValue::Rotation(packet.try_read_float()?, packet.try_read_float()?, packet.try_read_float()?),
+            9 => MetadataValue::Position(packet.try_read_position()?),
+            10 => MetadataValue::OptionalPosition(if packet.try_read_bool()? { Some(packet.try_read_position()?) } else { None }),
+            11 => MetadataValue::Direction(packet.try_read_varint()?),
+            12 => MetadataValue::OptionalUuid(if packet.try_read_bool()? { Some(packet.try_read_uuid()?) } else { None }),
+            13 => MetadataValue::OptionalBlockState(match packet.try_read_varint()? {
+                0 => None,
+                id => Some(id),
+            }),
+            14 => MetadataValue::Nbt(NBTType::from_packet_optional(packet).ok()?),
+            15 => MetadataValue::Particle(packet.try_read_varint()?),
+            16 => MetadataValue::VillagerData {
+                villager_type: packet.try_read_varint()?,
+                profession: packet.try_read_varint()?,
+                level: packet.try_read_varint()?,
+            },
+            17 => MetadataValue::OptionalVarInt(match packet.try_read_varint()? {
+                0 => None,
+                value => Some(value - 1),
+            }),
+            18 => MetadataValue::Pose(Pose::from(packet.try_read_varint()?)),
+            19 => MetadataValue::CatVariant(packet.try_read_varint()?),
+            20 => MetadataValue::FrogVariant(packet.try_read_varint()?),
+            21 => MetadataValue::OptionalGlobalPosition(if packet.try_read_bool()? {
+                Some((packet.try_read_string()?, packet.try_read_position()?))
+            } else {
+                None
+            }),
+            22 => MetadataValue::PaintingVariant(packet.try_read_varint()?),
+            _ => return None,
+        })
+    }
+}
+
+/// Metadata of the entity (values by their index)
+///
+/// Indexes of values depend on the entity class, so accessors of subclass fields
+/// take kind of the entity (e.g. index 8 holds a slot only for item entities).
+#[derive(Debug, Clone, Default)]
+pub struct EntityMetadata {
+    pub values: BTreeMap<u8, MetadataValue>,
+}
+
+impl EntityMetadata {
+    /// Reads metadata entries from the packet (until the `0xFF` terminator)
+    ///
+    /// Reading stops at particle (its data depends on the particle type), unknown types and truncated values,
+    /// values read before are kept. Particle is the last field of the only entity which uses it,
+    /// so nothing is lost in practice.
+    pub fn read(packet: &mut RawPacket) -> EntityMetadata {
+        let mut metadata = EntityMetadata::default();
+        loop {
+            let Some(index) = packet.try_read_byte() else {
+                log::warn!(target: "miners-client", "Entity metadata is missing the terminator");
+                break;
+            };
+            if index == 0xFF {
+                break;
+            }
+
+            let Some(value_type) = packet.try_read_varint() else {
+                log::warn!(target: "miners-client", "Entity metadata is truncated at index {}", index);
+                break;
+            };
+            match MetadataValue::read(packet, value_type) {
+                Some(value) => {
+                    let stop = matches!(value, MetadataValue::Particle(_));
+                    metadata.values.insert(index, value);
+                    if stop {
+                        break;
+                    }
+                },
+                None => {
+                    log::warn!(target: "miners-client", "Unknown or truncated entity metadata of type {} at index {}", value_type, index);
+                    break;
+                }
+            }
+        }
+        metadata
+    }
+
+    /// Applies values from metadata update
+    pub fn update(&mut self, update: EntityMetadata) {
+        self.values.extend(update.values);
+    }
+
+    pub fn get(&self, index: u8) -> Option<&MetadataValue> {
+        self.values.get(&index)
+    }
+
+    /// Returns entity flags (bit mask, see `is_*` methods)
+    pub fn flags(&self) -> u8 {
+        match self.get(0) {
+            Some(MetadataValue::Byte(flags)) => *flags as u8,
+            _ => 0,
+        }
+    }
+
+    pub fn is_on_fire(&self) -> bool {
+        self.flags() & 0x01 != 0
+    }
+
+    pub fn is_sneaking(&self) -> bool {
+        self.flags() & 0x02 != 0
+    }
+
+    pub fn is_sprinting(&self) -> bool {
+        self.flags() & 0x08 != 0
+    }
+
+    pub fn is_swimming(&self) -> bool {
+        self.flags() & 0x10 != 0
+    }
+
+    pub fn is_invisible(&self) -> bool {
+        self.flags() & 0x20 != 0
+    }
+
+    pub fn is_glowing(&self) -> bool {
+        self.flags() & 0x40 != 0
+    }
+
+    pub fn is_flying_with_elytra(&self) -> bool {
+        self.flags() & 0x80 != 0
+    }
+
+    /// Remaining air ticks
+    pub fn air(&self) -> Option<i32> {
+        match self.get(1) {
+            Some(MetadataValue::VarInt(air)) => Some(*air),
+            _ => None,
+        }
+    }
+
+    pub fn custom_name(&self) -> Option<&FormattedChatMessage> {
+        match self.get(2) {
+            Some(MetadataValue::OptionalComponent(name)) => name.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn is_custom_name_visible(&self) -> bool {
+        matches!(self.get(3), Some(MetadataValue::Boolean(true)))
+    }
+
+    pub fn is_silent(&self) -> bool {
+        matches!(self.get(4), Some(MetadataValue::Boolean(true)))
+    }
+
+    pub fn has_no_gravity(&self) -> bool {
+        matches!(self.get(5), Some(MetadataValue::Boolean(true)))
+    }
+
+    pub fn pose(&self) -> Pose {
+        match self.get(6) {
+            Some(MetadataValue::Pose(pose)) => *pose,
+            _ => Pose::Standing,
+        }
+    }
+
+    /// Item of item entity (`None` for other entities)
+    pub fn item(&self, kind: EntityKind) -> Option<&Slot> {
+        match self.get(8) {
+            Some(MetadataValue::Slot(slot)) if kind == EntityKind::ITEM => slot.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Health of living entity (`None` for other entities)
+    pub fn health(&self, kind: EntityKind) -> Option<f32> {
+        match self.get(9) {
+            Some(MetadataValue::Float(health)) if kind.is_living() => Some(*health),
+            _ => None,
+        }
+    }
+
+    /// Number of arrows stuck in living entity
+    pub fn arrows(&self, kind: EntityKind) -> Option<i32> {
+        match self.get(12) {
+            Some(MetadataValue::VarInt(arrows)) if kind.is_living() => Some(*arrows),
+            _ => None,
+        }
+    }
+
+    /// Position of the bed the living entity sleeps in
+    pub fn sleeping_position(&self, kind: EntityKind) -> Option<BlockPos> {
+        match self.get(14) {
+            Some(MetadataValue::OptionalPosition(position)) if kind.is_living() => *position,
+            _ => None,
+        }
+    }
+
+    /// Additional hearts (absorption) of the player
+    pub fn absorption(&self, kind: EntityKind) -> Option<f32> {
+        match self.get(15) {
+            Some(MetadataValue::Float(absorption)) if kind.is_player() => Some(*absorption),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use miners_protocol::utils::nbt::NBTCompound;
+
+    use super::*;
+
+    fn metadata_packet() -> RawPacket {
+        let mut packet = RawPacket::empty(0x50);
+        // Flags: on fire and sneaking
+        packet.write_byte(0);
+        packet.write_varint(0);
+        packet.write_byte(0x03);
+        packet.write_byte(1);
+        packet.write_varint(1);
+        packet.write_varint(300);
+        packet.write_byte(2);
+        packet.write_varint(5);
+        packet.write_bool(true);
+        packet.write_string(r#"{"text":"Bob"}"#);
+        packet.write_byte(6);
+        packet.write_varint(18);
+        packet.write_varint(5);
+        packet.write_byte(8);
+        packet.write_varint(6);
+        packet.write_bool(true);
+        packet.write_varint(1);
+        packet.write_byte(64);
+        packet.write_nbt(&NBTType::Compound(NBTCompound::from_iter([("Damage", NBTType::Int(3))])));
+        packet.write_byte(9);
+        packet.write_varint(2);
+        packet.write_float(20.0);
+        packet.write_byte(10);
+        packet.write_varint(6);
+        packet.write_bool(false);
+        packet.write_byte(14);
+        packet.write_varint(10);
+        packet.write_bool(true);
+        packet.write_long(BlockPos::new(1, 64, -2).to_long());
+        packet.write_byte(0xFF);
+        packet
+    }
+
+    #[test]
+    fn read_values() {
+        let metadata = EntityMetadata::read(&mut metadata_packet());
+        assert_eq!(metadata.values.len(), 8);
+        assert_eq!(metadata.flags(), 0x03);
+        assert!(metadata.is_on_fire());
+        assert!(matches!(metadata.get(1), Some(MetadataValue::VarInt(300))));
+        let Some(MetadataValue::OptionalComponent(Some(name))) = metadata.get(2) else {
+            panic!("expected custom name");
+        };
+        assert_eq!(name.to_plain(), "Bob");
+        assert!(matches!(metadata.get(6), Some(MetadataValue::Pose(Pose::Sneaking))));
+        assert!(matches!(metadata.get(10), Some(MetadataValue::Slot(None))));
+        assert_eq!(metadata.health(EntityKind::Player), Some(20.0));
+        assert_eq!(metadata.sleeping_position(EntityKind::Player), Some(BlockPos::new(1, 64, -2)));
+
+        let slot = metadata.item(EntityKind::ITEM).unwrap();
+        assert_eq!((slot.item_id, slot.count), (1, 64));
+        let Some(NBTType::Compound(nbt)) = &slot.nbt else {
+            panic!("expected item NBT");
+        };
+        assert_eq!(nbt.data.get("Damage"), Some(&NBTType::Int(3)));
+        assert!(metadata.item(EntityKind::Player).is_none());
+    }
+
+    #[test]
+    fn read_stops_at_particle_and_unknown_type() {
+        let mut packet = RawPacket::empty(0x50);
+        packet.write_byte(0);
+        packet.write_varint(0);
+        packet.write_byte(0x20);
+        packet.write_byte(11);
+        packet.write_varint(15);
+        packet.write_varint(2);
+        // Particle data which would be read as another entry
+        packet.write_byte(12);
+        packet.write_byte(0xFF);
+        let metadata = EntityMetadata::read(&mut packet);
+        assert_eq!(metadata.values.len(), 2);
+        assert!(matches!(metadata.get(11), Some(MetadataValue::Particle(2))));
+
+        let mut packet = RawPacket::empty(0x50);
+        packet.write_byte(0);
+        packet.write_varint(0);
+        packet.write_byte(0x20);
+        packet.write_byte(1);
+        packet.write_varint(23);
+        packet.write_varint(0);
+        packet.write_byte(2);
+        packet.write_varint(7);
+        packet.write_bool(true);
+        packet.write_byte(0xFF);
+        let metadata = EntityMetadata::read(&mut packet);
+        assert_eq!(metadata.values.len(), 1);
+        assert!(metadata.is_invisible());
+    }
+
+    #[test]
+    fn read_truncated() {
+        let data = metadata_packet().data;
+        // Last byte is the terminator, without it every value is still read
+        for length in 0..data.len() - 1 {
+            let mut packet = RawPacket::new(0x50, data[..length].to_vec());
+            let metadata = EntityMetadata::read(&mut packet);
+            assert!(metadata.values.len() < 8, "prefix of {} bytes read every value", length);
+        }
+
+        // Slot with malformed NBT
+        let mut packet = RawPacket::empty(0x50);
+        packet.write_byte(8);
+        packet.write_varint(6);
+        packet.write_bool(true);
+        packet.write_varint(1);
+        packet.write_byte(1);
+        packet.write_byte(13);
+        packet.write_byte(0xFF);
+        assert!(EntityMetadata::read(&mut packet).values.is_empty());
+        assert!(MetadataValue::read(&mut RawPacket::new(0x50, vec![1]), 6).is_none());
+    }
+}
@@ -0,0 +1,123 @@
+//! Tracking of entities (other players, mobs, items, etc.) visible to the client
+
+use std::collections::HashMap;
+
+use miners_protocol::utils::location::Vec3;
+
+use self::metadata::EntityMetadata;
+
+pub mod metadata;
+
+/// Kind of the entity
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EntityKind {
+    /// Player (spawned using spawn player packet)
+    Player,
+    /// Any other entity with its id from `minecraft:entity_type` registry
+    Other(i32),
+}
+
+impl EntityKind {
+    /// Id of item entity in `minecraft:entity_type` registry
+    pub const ITEM: EntityKind = EntityKind::Other(44);
+    /// Id of player in `minecraft:entity_type` registry
+    const PLAYER_ID: i32 = 116;
+    /// Entity types which don't extend living entity (projectiles, vehicles, decorations etc.)
+    const NON_LIVING_IDS: [i32; 39] = [
+        1, 3, 8, 9, 18, 21, 26, 27, 28, 29, 30, 35, 44, 45, 46, 47, 48, 50, 52, 53, 54, 55, 56, 57, 58, 59,
+        63, 72, 79, 84, 86, 87, 93, 94, 95, 96, 97, 109, 117,
+    ];
+
+    pub fn is_player(&self) -> bool {
+        matches!(self, EntityKind::Player | EntityKind::Other(EntityKind::PLAYER_ID))
+    }
+
+    /// Whether the entity is a living entity (has health, e.g. mobs, players and armor stands)
+    pub fn is_living(&self) -> bool {
+        match self {
+            EntityKind::Player => true,
+            EntityKind::Other(id) => !EntityKind::NON_LIVING_IDS.contains(id),
+        }
+    }
+}
+
+/// Entity tracked by the client
+#[derive(Debug, Clone)]
+pub struct Entity {
+    /// Network id of the entity (unique only during the session)
+    pub id: i32,
+    pub uuid: u128,
+    pub kind: EntityKind,
+    pub position: Vec3,
+    /// Velocity in blocks per tick
+    pub velocity: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub head_yaw: f32,
+    pub on_ground: bool,
+    /// Additional spawn data, meaning depends on the entity kind (e.g. block state of falling block)
+    pub data: i32,
+    pub metadata: EntityMetadata,
+}
+
+/// Store of entities tracked by the client, kept up to date by `EntitiesHandler`
+///
+/// # Example
+/// ```rs
+/// let client = client.rl();
+/// let position = Vec3::new(0.0, 64.0, 0.0);
+/// if let Some(player) = client.entities().nearest(position, EntityKind::Player) {
+///     println!("Nearest player is {:.1} blocks away", player.position.distance(position));
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Entities {
+    pub entities: HashMap<i32, Entity>,
+}
+
+impl Entities {
+    /// Returns entity with specified network id
+    pub fn get(&self, id: i32) -> Option<&Entity> {
+        self.entities.get(&id)
+    }
+
+    /// Returns entity with specified UUID
+    pub fn get_by_uuid(&self, uuid: u128) -> Option<&Entity> {
+        self.entities.values().find(|entity| entity.uuid == uuid)
+    }
+
+    /// Returns iterator over all entities (in no particular order)
+    pub fn iter(&self) -> impl Iterator<Item = &Entity> {
+        self.entities.values()
+    }
+
+    /// Returns iterator over entities of specified kind
+    pub fn of_kind(&self, kind: EntityKind) -> impl Iterator<Item = &Entity> {
+        self.entities.values().filter(move |entity| entity.kind == kind)
+    }
+
+    /// Returns iterator over entities within `radius` blocks from `position`
+    pub fn within_radius(&self, position: Vec3, radius: f64) -> impl Iterator<Item = &Entity> {
+        self.entities.values().filter(move |entity| entity.position.distance_squared(position) <= radius * radius)
+    }
+
+    /// Returns the nearest entity of specified kind
+    pub fn nearest(&self, position: Vec3, kind: EntityKind) -> Option<&Entity> {
+        self.nearest_by(position, |entity| entity.kind == kind)
+    }
+
+    /// Returns the nearest entity matching the predicate
+    pub fn nearest_by<F: Fn(&Entity) -> bool>(&self, position: Vec3, predicate: F) -> Option<&Entity> {
+        self.entities.values()
+            .filter(|entity| predicate(entity))
+            .min_by(|a, b| a.position.distance_squared(position).total_cmp(&b.position.distance_squared(position)))
+    }
+
+    pub fn len(&self) -> usize {
+        self.entities.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
\ No newline at end of file
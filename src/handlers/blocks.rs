@@ -0,0 +1,131 @@
+use miners_protocol::{packet::RawPacket, utils::location::BlockPos};
+
+use crate::{client::{ClientPacketHandler, ClientMutLock, ClientLockExt}, define_events};
+
+/// Handles block update (0x09), update section blocks (0x40) and acknowledge block change (0x05) packets
+///
+/// Applies changes to the `World` of the client and emits `BlockChangeEvent` for each changed block
+#[derive(Clone)]
+pub struct BlockUpdateHandler;
+
+impl ClientPacketHandler for BlockUpdateHandler {
+    fn handle(&self, client: ClientMutLock, packet: &RawPacket) {
+        // Ensure that we are in play state
+        if client.get_state() != miners_protocol::ConnectionState::Play {
+            return;
+        }
+
+        // Clone packet to read it
+        let packet = packet.clone();
+        let mut client = client.wl();
+        let changes = match packet.id {
+            0x09 => {
+                let packet = BlockUpdatePacket::from(packet);
+                log::debug!(target: "miners-client", "Block update packet received: {:?}", packet);
+                vec![(packet.position, packet.state)]
+            },
+            0x40 => {
+                let packet = UpdateSectionBlocksPacket::from(packet);
+                log::debug!(target: "miners-client", "Update section blocks packet received: {} blocks", packet.blocks.len());
+                packet.blocks
+            },
+            _ => {
+                let packet = AcknowledgeBlockChangePacket::from(packet);
+                log::debug!(target: "miners-client", "Acknowledge block change packet received: {:?}", packet);
+                for (pos, old, new) in client.world.acknowledge(packet.sequence) {
+                    client.emit(BlockChangeEvent { pos, old, new });
+                }
+                return;
+            },
+        };
+
+        for (pos, new) in changes {
+            if let Some(old) = client.world.apply_block_update(pos, new) {
+                client.emit(BlockChangeEvent { pos, old, new });
+            }
+        }
+    }
+
+    fn ids(&self) -> &'static [i32] {
+        &[0x09, 0x40, 0x05]
+    }
+}
+
+define_events!(
+    BlockChangeEvent (pos: BlockPos, old: i32, new: i32) => "Event emitted when block state in a loaded chunk changes (states are block state ids)"
+);
+
+/// Represents block update packet (0x09)
+#[derive(Debug, Clone)]
+pub struct BlockUpdatePacket {
+    pub position: BlockPos,
+    /// New block state id
+    pub state: i32,
+}
+
+impl From<RawPacket> for BlockUpdatePacket {
+    fn from(mut packet: RawPacket) -> Self {
+        BlockUpdatePacket {
+            position: packet.read_position(),
+            state: packet.read_varint(),
+        }
+    }
+}
+
+/// Represents update section blocks packet (0x40) which changes multiple blocks of a single section
+#[derive(Debug, Clone)]
+pub struct UpdateSectionBlocksPacket {
+    /// Section position (in sections, not blocks)
+    pub section: BlockPos,
+    pub suppress_light_updates: bool,
+    /// Changed blocks with their new states (positions are absolute)
+    pub blocks: Vec<(BlockPos, i32)>,
+}
+
+impl From<RawPacket> for UpdateSectionBlocksPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        // Section position is packed into a long (x: 22 bits, z: 22 bits, y: 20 bits)
+        let value = packet.read_long();
+        let section = BlockPos::new((value >> 42) as i32, (value << 44 >> 44) as i32, (value << 22 >> 42) as i32);
+        let suppress_light_updates = packet.read_bool();
+
+        let count = packet.read_varint();
+        let mut blocks = Vec::new();
+        for _ in 0..count {
+            // Each entry is `state << 12 | x << 8 | z << 4 | y` (coordinates relative to the section)
+            let entry = packet.read_varlong();
+            let position = section_origin(section).offset(
+                (entry >> 8 & 0xF) as i32,
+                (entry & 0xF) as i32,
+                (entry >> 4 & 0xF) as i32,
+            );
+            blocks.push((position, (entry >> 12) as i32));
+        }
+
+        UpdateSectionBlocksPacket {
+            section,
+            suppress_light_updates,
+            blocks,
+        }
+    }
+}
+
+fn section_origin(section: BlockPos) -> BlockPos {
+    BlockPos::new(section.x * 16, section.y * 16, section.z * 16)
+}
+
+/// Represents acknowledge block change packet (0x05)
+///
+/// Sent after the server processes block action of the client (all actions up to `sequence` are acknowledged)
+#[derive(Debug, Clone)]
+pub struct AcknowledgeBlockChangePacket {
+    pub sequence: i32,
+}
+
+impl From<RawPacket> for AcknowledgeBlockChangePacket {
+    fn from(mut packet: RawPacket) -> Self {
+        AcknowledgeBlockChangePacket {
+            sequence: packet.read_varint(),
+        }
+    }
+}
\ No newline at end of file
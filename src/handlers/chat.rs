@@ -1,4 +1,8 @@
-use crate::{client::{ClientPacketHandler, ClientMutLock, ClientLockExt}, define_events};
+use miners_protocol::packet::RawPacket;
+
+use crate::{client::{ClientPacketHandler, ClientMutLock, ClientLockExt}, define_events, plugins::basic::MessageAcknowledgmentPacket, players::PlayerList, chat::signing::{message_body_hash, LastSeenMessages, LastSeenEntry, VerificationStatus}};
+
+pub use crate::chat::component::FormattedChatMessage;
 
 /// Handler for basic chat messages
 /// 
@@ -17,136 +21,264 @@ impl ClientPacketHandler for ChatHandler {
         let mut packet = packet.clone();
         // If packet id is 0x33 (Player Chat Message), read additional data
         if packet.id == 0x33 {
-            // ==< Header >==
-            if packet.read_bool() { // Signature
-                let sl = packet.read_varint();
-                let _signature = packet.read_bytes(sl as usize);
-            }
+            let Some(packet) = PlayerChatPacket::read(&mut packet) else {
+                log::warn!(target: "miners-client", "Received truncated player chat packet");
+                return;
+            };
+
+            let parse_component = |json: &str| FormattedChatMessage::from_json(json).unwrap_or_else(|e| {
+                log::warn!(target: "miners-client", "Failed to parse chat component {}: {:?}", json, e);
+                FormattedChatMessage::from_plain(json.to_string())
+            });
+            let decorated_message = packet.decorated_message.as_deref().map(parse_component);
 
-            let sender = packet.read_uuid();
-            let hsl = packet.read_varint();
-            let _header_signature = packet.read_bytes(hsl as usize);
+            // Verify message signature and track last seen messages (acknowledge them if there are too many pending)
+            let verified = {
+                let mut client = client.wl();
+                let body_hash = message_body_hash(&packet.plain_message, decorated_message.as_ref(), packet.timestamp as u64, packet.salt, &packet.last_seen);
+                let verified = client.chat_session.verify_message(packet.sender, packet.previous_signature.as_deref(), &packet.header_signature, &body_hash);
 
-            let message = packet.read_string();
+                if !packet.header_signature.is_empty() && client.chat_session.on_message_received(packet.sender, packet.header_signature.clone()) {
+                    let acknowledgment = client.chat_session.acknowledge();
+                    client.socket.send_packet(MessageAcknowledgmentPacket(acknowledgment)).ok();
+                }
+                verified
+            };
+
+            // Message content shown to the player (unsigned content is set by the server, e.g. by chat plugins)
+            let message = match (&packet.unsigned_content, decorated_message) {
+                (Some(unsigned_content), _) => parse_component(unsigned_content),
+                (None, Some(decorated_message)) => decorated_message,
+                (None, None) => FormattedChatMessage::from_plain(packet.plain_message.clone()),
+            };
+            let sender_name = parse_component(&packet.network_name);
+            let target_name = packet.network_target_name.as_deref().map(parse_component);
 
-            //? For now we ignore remaining data as we don't use it yet
+            // Decorate message using its chat type (e.g. `<Steve> hi`)
+            let (decorated, plain_message, plain_decorated) = {
+                let client = client.rl();
+                let language = client.language();
+                let decorated = match client.chat_type(packet.chat_type) {
+                    Some(chat_type) => chat_type.chat.decorate(&message, &sender_name, target_name.as_ref()),
+                    None => {
+                        log::warn!(target: "miners-client", "Unknown chat type: {}", packet.chat_type);
+                        message.clone()
+                    }
+                };
+                (decorated.clone(), message.to_plain_with(&language), decorated.to_plain_with(&language))
+            };
 
-            log::debug!(target: "miners-client", "Chat message received: {}", message);
+            log::debug!(target: "miners-client", "Chat message received: {} ({:?})", plain_decorated, verified);
             // Emit event with appropriate data
             client.emit(ChatMessageEvent {
                 message: ChatMessage {
-                    source: ChatMessageSource::Player(sender),
-                    message: FormattedChatMessage::from_plain(message.clone()), // TODO: Use formatted message
-                    plain_message: message,
+                    source: ChatMessageSource::Player(packet.sender),
+                    message,
+                    plain_message,
+                    decorated,
+                    plain_decorated,
+                    sender_name: Some(sender_name),
+                    verified,
                 }
             });
+        } else if packet.id == 0x32 {
+            // Message Header (sent instead of hidden messages), only used to keep message chains intact
+            let Some(MessageHeaderPacket { previous_signature, sender, header_signature, body_hash }) = MessageHeaderPacket::read(&mut packet) else {
+                log::warn!(target: "miners-client", "Received truncated message header packet");
+                return;
+            };
+
+            let verified = client.wl().chat_session.verify_message(sender, previous_signature.as_deref(), &header_signature, &body_hash);
+            log::debug!(target: "miners-client", "Message header received from {:x} ({:?})", sender, verified);
         } else {
             // If it is not 0x33, then it is 0x62 (System Chat Message)
-            let message = packet.read_string();
-            
-            let message: FormattedChatMessage = message.into();
-            let plain_message = message.text.clone();
+            let Some(json) = packet.try_read_string() else {
+                log::warn!(target: "miners-client", "Received truncated system chat packet");
+                return;
+            };
             
+            let message = FormattedChatMessage::from_json(&json).unwrap_or_else(|e| {
+                log::warn!(target: "miners-client", "Failed to parse system chat message {}: {:?}", json, e);
+                FormattedChatMessage::from_plain(json.clone())
+            });
+            let plain_message = message.to_plain_with(&client.rl().language());
+
             //? There is no more data in this packet :)
 
-            log::debug!(target: "miners-client", "System chat message received: {}", message.text);
+            log::debug!(target: "miners-client", "System chat message received: {}", plain_message);
             // Emit event with appropriate data
             client.emit(ChatMessageEvent {
                 message: ChatMessage {
                     source: ChatMessageSource::System,
+                    decorated: message.clone(),
+                    plain_decorated: plain_message.clone(),
                     message,
                     plain_message,
+                    sender_name: None,
+                    verified: VerificationStatus::Unsigned,
                 }
             });
         }
     }
 
     fn ids(&self) -> &'static [i32] {
-       &[0x33, 0x62]
+       &[0x32, 0x33, 0x62]
     }
 }
 
-/// Represents a chat message (both player and system)
-/// 
-/// Contains message sender, formatted message and plain message
+/// Represents player chat message packet (0x33)
 /// 
-/// **Warning:** This may change drastically in the future
-#[derive(Clone, Debug)]
-pub struct ChatMessage {
-    pub source: ChatMessageSource,
-    pub message: FormattedChatMessage,
+/// Contains message header (used for signature chain), message body and data needed to display the message
+#[derive(Debug, Clone)]
+pub struct PlayerChatPacket {
+    // Header
+    pub previous_signature: Option<Vec<u8>>,
+    pub sender: u128,
+    pub header_signature: Vec<u8>,
+
+    // Body
     pub plain_message: String,
-}
+    pub decorated_message: Option<String>,
+    pub timestamp: i64,
+    pub salt: i64,
+    pub last_seen: LastSeenMessages,
 
-/// Default font used in chat messages (Required by serde for default value)
-fn default_font() -> String {
-    String::from("minecraft:uniform")
+    // Other
+    /// Content set by the server (JSON), shown instead of the signed message
+    pub unsigned_content: Option<String>,
+    pub filter: FilterMask,
+    /// Network id of the chat type (see `MinecraftClient::chat_type`)
+    pub chat_type: i32,
+    /// Display name of the sender (JSON)
+    pub network_name: String,
+    /// Display name of the target, e.g. for `/msg` (JSON)
+    pub network_target_name: Option<String>,
 }
 
-/// Default color used in chat messages (Required by serde for default value)
-fn default_color() -> String {
-    String::from("minecraft:white")
+/// Describes which parts of the message were filtered by the server
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterMask {
+    PassThrough,
+    FullyFiltered,
+    /// Bit set of filtered characters
+    PartiallyFiltered(Vec<i64>),
 }
 
-/// Parsed version of formatted chat message (Like the one you pass to `/tellraw`)
-/// 
-/// **Warning:** This currently does not contain all fields that can be used in chat messages
-#[derive(Debug, Clone, serde::Deserialize)]
-pub struct FormattedChatMessage {
-    #[serde(default)]
-    pub text: String,
-
-    #[serde(default)]
-    pub bold: bool,
-    #[serde(default)]
-    pub italic: bool,
-    #[serde(default)]
-    pub underlined: bool,
-    #[serde(default)]
-    pub strikethrough: bool,
-    #[serde(default)]
-    pub obfuscated: bool,
-    
-    #[serde(default = "default_font")]
-    pub font: String,
-    #[serde(default = "default_color")]
-    pub color: String,
-
-    #[serde(default)]
-    pub extra: Vec<FormattedChatMessage>,
-}
+impl PlayerChatPacket {
+    /// Reads the packet (`None` if it is truncated)
+    pub fn read(packet: &mut RawPacket) -> Option<PlayerChatPacket> {
+        // ==< Header >==
+        let previous_signature = if packet.try_read_bool()? {
+            Some(packet.try_read_byte_array()?)
+        } else {
+            None
+        };
+        let sender = packet.try_read_uuid()?;
+        let header_signature = packet.try_read_byte_array()?;
+
+        // ==< Body >==
+        let plain_message = packet.try_read_string()?;
+        let decorated_message = if packet.try_read_bool()? {
+            Some(packet.try_read_string()?)
+        } else {
+            None
+        };
+        let timestamp = packet.try_read_long()?;
+        let salt = packet.try_read_long()?;
 
-impl Default for FormattedChatMessage {
-    fn default() -> Self {
-        FormattedChatMessage {
-            text: String::new(),
-            bold: false,
-            italic: false,
-            underlined: false,
-            strikethrough: false,
-            obfuscated: false,
-            font: default_font(),
-            color: default_color(),
-            extra: Vec::new(),
+        let mut last_seen = LastSeenMessages::default();
+        let last_seen_len = packet.try_read_varint()?;
+        for _ in 0..last_seen_len {
+            let profile_id = packet.try_read_uuid()?;
+            let signature = packet.try_read_byte_array()?;
+            last_seen.entries.push(LastSeenEntry {
+                profile_id,
+                signature,
+            });
         }
+
+        // ==< Other >==
+        let unsigned_content = if packet.try_read_bool()? {
+            Some(packet.try_read_string()?)
+        } else {
+            None
+        };
+        let filter = match packet.try_read_varint()? {
+            1 => FilterMask::FullyFiltered,
+            2 => FilterMask::PartiallyFiltered(packet.try_read_bitset()?),
+            _ => FilterMask::PassThrough,
+        };
+        let chat_type = packet.try_read_varint()?;
+        let network_name = packet.try_read_string()?;
+        let network_target_name = if packet.try_read_bool()? {
+            Some(packet.try_read_string()?)
+        } else {
+            None
+        };
+
+        Some(PlayerChatPacket {
+            previous_signature,
+            sender,
+            header_signature,
+            plain_message,
+            decorated_message,
+            timestamp,
+            salt,
+            last_seen,
+            unsigned_content,
+            filter,
+            chat_type,
+            network_name,
+            network_target_name,
+        })
     }
 }
 
-impl From<String> for FormattedChatMessage {
-    fn from(text: String) -> Self {
-        serde_json::from_str(&text).unwrap()
-    }
+/// Represents message header packet (0x32), sent instead of messages which are not shown to the player
+#[derive(Debug, Clone)]
+pub struct MessageHeaderPacket {
+    pub previous_signature: Option<Vec<u8>>,
+    pub sender: u128,
+    pub header_signature: Vec<u8>,
+    pub body_hash: Vec<u8>,
 }
 
-impl FormattedChatMessage {
-    pub fn from_plain(text: String) -> Self {
-        FormattedChatMessage {
-            text,
-            ..Default::default()
-        }
+impl MessageHeaderPacket {
+    /// Reads the packet (`None` if it is truncated)
+    pub fn read(packet: &mut RawPacket) -> Option<MessageHeaderPacket> {
+        let previous_signature = if packet.try_read_bool()? {
+            Some(packet.try_read_byte_array()?)
+        } else {
+            None
+        };
+        Some(MessageHeaderPacket {
+            previous_signature,
+            sender: packet.try_read_uuid()?,
+            header_signature: packet.try_read_byte_array()?,
+            body_hash: packet.try_read_byte_array()?,
+        })
     }
 }
 
+/// Represents a chat message (both player and system)
+/// 
+/// Contains message sender, formatted message (with its decorated version, e.g. `<Steve> hi`) and result of signature verification
+/// 
+/// **Warning:** This may change drastically in the future
+#[derive(Clone, Debug)]
+pub struct ChatMessage {
+    pub source: ChatMessageSource,
+    pub message: FormattedChatMessage,
+    pub plain_message: String,
+    /// Message decorated according to its chat type (same as `message` for system messages)
+    pub decorated: FormattedChatMessage,
+    pub plain_decorated: String,
+    /// Display name of the sender (`None` for system messages)
+    pub sender_name: Option<FormattedChatMessage>,
+    /// Whether the message is genuine (system messages are always `Unsigned`)
+    pub verified: VerificationStatus,
+}
+
 define_events!(ChatMessageEvent (message: ChatMessage) => "Event emitted when chat message is received");
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -155,9 +287,96 @@ pub enum ChatMessageSource {
     System,
 }
 
+impl ChatMessageSource {
+    /// Returns username of the sender (`None` for system messages and players missing from the player list)
+    pub fn username<'a>(&self, players: &'a PlayerList) -> Option<&'a str> {
+        match self {
+            ChatMessageSource::Player(uuid) => players.name_of(*uuid),
+            ChatMessageSource::System => None,
+        }
+    }
+}
+
 impl From<&ClientMutLock> for ChatMessageSource {
     fn from(client: &ClientMutLock) -> Self {
         let rl = client.read().unwrap();
         ChatMessageSource::Player(rl.uuid.clone())
     }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn player_chat_packet() -> RawPacket {
+        let mut packet = RawPacket::empty(0x33);
+        // Header
+        packet.write_bool(true);
+        packet.write_byte_array(&[1, 2]);
+        packet.write_uuid(0x1234);
+        packet.write_byte_array(&[3; 256]);
+        // Body
+        packet.write_string("hi");
+        packet.write_bool(true);
+        packet.write_string(r#"{"text":"hi"}"#);
+        packet.write_long(1_660_000_000_000);
+        packet.write_long(-5);
+        packet.write_varint(1);
+        packet.write_uuid(0x5678);
+        packet.write_byte_array(&[4; 256]);
+        // Other
+        packet.write_bool(false);
+        packet.write_varint(2);
+        packet.write_varint(1);
+        packet.write_long(0b10);
+        packet.write_varint(0);
+        packet.write_string(r#"{"text":"Steve"}"#);
+        packet.write_bool(true);
+        packet.write_string(r#"{"text":"Alex"}"#);
+        packet
+    }
+
+    #[test]
+    fn read_player_chat() {
+        let packet = PlayerChatPacket::read(&mut player_chat_packet()).unwrap();
+        assert_eq!(packet.previous_signature, Some(vec![1, 2]));
+        assert_eq!(packet.sender, 0x1234);
+        assert_eq!(packet.header_signature, vec![3; 256]);
+        assert_eq!(packet.plain_message, "hi");
+        assert_eq!(packet.decorated_message.as_deref(), Some(r#"{"text":"hi"}"#));
+        assert_eq!(packet.timestamp, 1_660_000_000_000);
+        assert_eq!(packet.salt, -5);
+        assert_eq!(packet.last_seen.entries, vec![LastSeenEntry { profile_id: 0x5678, signature: vec![4; 256] }]);
+        assert_eq!(packet.unsigned_content, None);
+        assert_eq!(packet.filter, FilterMask::PartiallyFiltered(vec![0b10]));
+        assert_eq!(packet.chat_type, 0);
+        assert_eq!(packet.network_name, r#"{"text":"Steve"}"#);
+        assert_eq!(packet.network_target_name.as_deref(), Some(r#"{"text":"Alex"}"#));
+    }
+
+    #[test]
+    fn read_truncated() {
+        let packet = player_chat_packet();
+        for length in 0..packet.data.len() {
+            let mut truncated = RawPacket::new(0x33, packet.data[..length].to_vec());
+            assert!(PlayerChatPacket::read(&mut truncated).is_none(), "packet truncated to {} bytes should be rejected", length);
+        }
+
+        let mut packet = RawPacket::empty(0x32);
+        packet.write_bool(false);
+        packet.write_uuid(0x1234);
+        packet.write_byte_array(&[3; 256]);
+        packet.write_byte_array(&[5; 32]);
+        let header = MessageHeaderPacket::read(&mut packet.clone()).unwrap();
+        assert_eq!((header.previous_signature, header.sender, header.body_hash), (None, 0x1234, vec![5; 32]));
+        for length in 0..packet.data.len() {
+            assert!(MessageHeaderPacket::read(&mut RawPacket::new(0x32, packet.data[..length].to_vec())).is_none());
+        }
+
+        // Negative array length
+        let mut packet = RawPacket::empty(0x32);
+        packet.write_bool(true);
+        packet.write_varint(-1);
+        assert!(MessageHeaderPacket::read(&mut packet).is_none());
+    }
 }
\ No newline at end of file
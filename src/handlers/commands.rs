@@ -0,0 +1,36 @@
+use miners_protocol::packet::RawPacket;
+
+use crate::{client::{ClientPacketHandler, ClientMutLock, ClientLockExt}, commands::CommandTree};
+
+/// Handles commands packets (0x0F) which contain the command tree of the server
+/// 
+/// Tree is stored in the client and can be accessed using `client.commands()`
+#[derive(Clone)]
+pub struct CommandsHandler;
+
+impl ClientPacketHandler for CommandsHandler {
+    fn handle(&self, client: ClientMutLock, packet: &RawPacket) {
+        // Ensure that we are in play state
+        if client.get_state() != miners_protocol::ConnectionState::Play {
+            return;
+        }
+
+        // Clone packet to read it
+        let Some(tree) = CommandTree::read(&mut packet.clone()) else {
+            log::warn!(target: "miners-client", "Commands packet uses unknown argument parser, ignoring it");
+            return;
+        };
+        log::debug!(target: "miners-client", "Commands packet received with {} nodes", tree.nodes.len());
+
+        if !tree.is_valid() {
+            log::warn!(target: "miners-client", "Commands packet contains references to nonexistent nodes, ignoring it");
+            return;
+        }
+
+        client.wl().commands = Some(tree);
+    }
+
+    fn ids(&self) -> &'static [i32] {
+        &[0x0F]
+    }
+}
\ No newline at end of file
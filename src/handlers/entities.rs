@@ -0,0 +1,359 @@
+use miners_protocol::{packet::RawPacket, utils::location::Vec3};
+
+use crate::{client::{ClientPacketHandler, ClientMutLock, ClientLockExt}, entities::{Entity, EntityKind, metadata::EntityMetadata}, define_events};
+
+/// Handles packets which spawn, move, update metadata of and remove entities
+///
+/// Keeps `Entities` store of the client up to date and emits spawn, despawn, move and metadata events
+#[derive(Clone)]
+pub struct EntitiesHandler;
+
+impl ClientPacketHandler for EntitiesHandler {
+    fn handle(&self, client: ClientMutLock, packet: &RawPacket) {
+        // Ensure that we are in play state
+        if client.get_state() != miners_protocol::ConnectionState::Play {
+            return;
+        }
+
+        // Clone packet to read it
+        let packet = packet.clone();
+        let mut client = client.wl();
+        match packet.id {
+            0x00 => {
+                let packet = SpawnEntityPacket::from(packet);
+                let entity = Entity {
+                    id: packet.id,
+                    uuid: packet.uuid,
+                    kind: EntityKind::Other(packet.entity_type),
+                    position: packet.position,
+                    velocity: packet.velocity,
+                    yaw: packet.yaw,
+                    pitch: packet.pitch,
+                    head_yaw: packet.head_yaw,
+                    on_ground: false,
+                    data: packet.data,
+                    metadata: EntityMetadata::default(),
+                };
+                client.entities.entities.insert(entity.id, entity.clone());
+                client.emit(EntitySpawnEvent { entity });
+            },
+            0x02 => {
+                let packet = SpawnPlayerPacket::from(packet);
+                let entity = Entity {
+                    id: packet.id,
+                    uuid: packet.uuid,
+                    kind: EntityKind::Player,
+                    position: packet.position,
+                    velocity: Vec3::ZERO,
+                    yaw: packet.yaw,
+                    pitch: packet.pitch,
+                    head_yaw: packet.yaw,
+                    on_ground: false,
+                    data: 0,
+                    metadata: EntityMetadata::default(),
+                };
+                client.entities.entities.insert(entity.id, entity.clone());
+                client.emit(EntitySpawnEvent { entity });
+            },
+            0x28..=0x2A => {
+                let packet = UpdateEntityPositionPacket::from(packet);
+                let Some(entity) = client.entities.entities.get_mut(&packet.id) else { return };
+
+                let previous_position = entity.position;
+                if let Some(delta) = packet.delta {
+                    entity.position = entity.position + delta;
+                }
+                if let Some((yaw, pitch)) = packet.rotation {
+                    entity.yaw = yaw;
+                    entity.pitch = pitch;
+                }
+                entity.on_ground = packet.on_ground;
+
+                let entity = entity.clone();
+                client.emit(EntityMoveEvent { entity, previous_position });
+            },
+            0x3B => {
+                let packet = RemoveEntitiesPacket::from(packet);
+                for id in packet.ids {
+                    if let Some(entity) = client.entities.entities.remove(&id) {
+                        client.emit(EntityDespawnEvent { entity });
+                    }
+                }
+            },
+            0x3F => {
+                let packet = SetHeadRotationPacket::from(packet);
+                if let Some(entity) = client.entities.entities.get_mut(&packet.id) {
+                    entity.head_yaw = packet.head_yaw;
+                }
+            },
+            0x50 => {
+                let packet = EntityMetadataPacket::from(packet);
+                let Some(entity) = client.entities.entities.get_mut(&packet.id) else { return };
+
+                entity.metadata.update(packet.metadata);
+                let entity = entity.clone();
+                client.emit(EntityMetadataEvent { entity });
+            },
+            0x52 => {
+                let packet = SetEntityVelocityPacket::from(packet);
+                if let Some(entity) = client.entities.entities.get_mut(&packet.id) {
+                    entity.velocity = packet.velocity;
+                }
+            },
+            _ => { // 0x66
+                let packet = TeleportEntityPacket::from(packet);
+                let Some(entity) = client.entities.entities.get_mut(&packet.id) else { return };
+
+                let previous_position = entity.position;
+                entity.position = packet.position;
+                entity.yaw = packet.yaw;
+                entity.pitch = packet.pitch;
+                entity.on_ground = packet.on_ground;
+
+                let entity = entity.clone();
+                client.emit(EntityMoveEvent { entity, previous_position });
+            },
+        }
+    }
+
+    fn ids(&self) -> &'static [i32] {
+        &[0x00, 0x02, 0x28, 0x29, 0x2A, 0x3B, 0x3F, 0x50, 0x52, 0x66]
+    }
+}
+
+define_events!(
+    EntitySpawnEvent (entity: Entity) => "Event emitted when entity (or player) spawns in view distance",
+    EntityDespawnEvent (entity: Entity) => "Event emitted when entity is removed (e.g. it died or left view distance)",
+    EntityMoveEvent (entity: Entity, previous_position: Vec3) => "Event emitted when entity moves or rotates",
+    EntityMetadataEvent (entity: Entity) => "Event emitted when entity metadata changes (e.g. health, pose or custom name)"
+);
+
+/// Reads velocity (in 1/8000 of a block per tick) from the packet
+fn read_velocity(packet: &mut RawPacket) -> Vec3 {
+    let x = packet.read_short() as f64 / 8000.0;
+    let y = packet.read_short() as f64 / 8000.0;
+    let z = packet.read_short() as f64 / 8000.0;
+    Vec3::new(x, y, z)
+}
+
+/// Reads position (three doubles) from the packet
+fn read_vec3(packet: &mut RawPacket) -> Vec3 {
+    let x = packet.read_double();
+    let y = packet.read_double();
+    let z = packet.read_double();
+    Vec3::new(x, y, z)
+}
+
+/// Represents spawn entity packet (0x00)
+#[derive(Debug, Clone)]
+pub struct SpawnEntityPacket {
+    pub id: i32,
+    pub uuid: u128,
+    /// Id from `minecraft:entity_type` registry
+    pub entity_type: i32,
+    pub position: Vec3,
+    pub pitch: f32,
+    pub yaw: f32,
+    pub head_yaw: f32,
+    pub data: i32,
+    pub velocity: Vec3,
+}
+
+impl From<RawPacket> for SpawnEntityPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let id = packet.read_varint();
+        let uuid = packet.read_uuid();
+        let entity_type = packet.read_varint();
+        let position = read_vec3(&mut packet);
+        let pitch = packet.read_angle();
+        let yaw = packet.read_angle();
+        let head_yaw = packet.read_angle();
+        let data = packet.read_varint();
+        let velocity = read_velocity(&mut packet);
+
+        SpawnEntityPacket {
+            id,
+            uuid,
+            entity_type,
+            position,
+            pitch,
+            yaw,
+            head_yaw,
+            data,
+            velocity,
+        }
+    }
+}
+
+/// Represents spawn player packet (0x02), sent when player comes into view distance
+#[derive(Debug, Clone)]
+pub struct SpawnPlayerPacket {
+    pub id: i32,
+    pub uuid: u128,
+    pub position: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl From<RawPacket> for SpawnPlayerPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let id = packet.read_varint();
+        let uuid = packet.read_uuid();
+        let position = read_vec3(&mut packet);
+        let yaw = packet.read_angle();
+        let pitch = packet.read_angle();
+
+        SpawnPlayerPacket {
+            id,
+            uuid,
+            position,
+            yaw,
+            pitch,
+        }
+    }
+}
+
+/// Represents update entity position (0x28), position and rotation (0x29) and rotation (0x2A) packets
+#[derive(Debug, Clone)]
+pub struct UpdateEntityPositionPacket {
+    pub id: i32,
+    /// Change of position (`None` for rotation only packet)
+    pub delta: Option<Vec3>,
+    /// New yaw and pitch (`None` for position only packet)
+    pub rotation: Option<(f32, f32)>,
+    pub on_ground: bool,
+}
+
+impl From<RawPacket> for UpdateEntityPositionPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let id = packet.read_varint();
+        let delta = if packet.id != 0x2A {
+            // Delta is sent in 1/4096 of a block
+            let x = packet.read_short() as f64 / 4096.0;
+            let y = packet.read_short() as f64 / 4096.0;
+            let z = packet.read_short() as f64 / 4096.0;
+            Some(Vec3::new(x, y, z))
+        } else {
+            None
+        };
+        let rotation = if packet.id != 0x28 {
+            let yaw = packet.read_angle();
+            let pitch = packet.read_angle();
+            Some((yaw, pitch))
+        } else {
+            None
+        };
+        let on_ground = packet.read_bool();
+
+        UpdateEntityPositionPacket {
+            id,
+            delta,
+            rotation,
+            on_ground,
+        }
+    }
+}
+
+/// Represents remove entities packet (0x3B)
+#[derive(Debug, Clone)]
+pub struct RemoveEntitiesPacket {
+    pub ids: Vec<i32>,
+}
+
+impl From<RawPacket> for RemoveEntitiesPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let count = packet.read_varint();
+        let ids = (0..count).map(|_| packet.read_varint()).collect();
+
+        RemoveEntitiesPacket {
+            ids,
+        }
+    }
+}
+
+/// Represents set head rotation packet (0x3F)
+#[derive(Debug, Clone)]
+pub struct SetHeadRotationPacket {
+    pub id: i32,
+    pub head_yaw: f32,
+}
+
+impl From<RawPacket> for SetHeadRotationPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let id = packet.read_varint();
+        let head_yaw = packet.read_angle();
+
+        SetHeadRotationPacket {
+            id,
+            head_yaw,
+        }
+    }
+}
+
+/// Represents set entity metadata packet (0x50)
+#[derive(Debug, Clone)]
+pub struct EntityMetadataPacket {
+    pub id: i32,
+    /// Changed values only
+    pub metadata: EntityMetadata,
+}
+
+impl From<RawPacket> for EntityMetadataPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let id = packet.read_varint();
+        let metadata = EntityMetadata::read(&mut packet);
+
+        EntityMetadataPacket {
+            id,
+            metadata,
+        }
+    }
+}
+
+/// Represents set entity velocity packet (0x52)
+#[derive(Debug, Clone)]
+pub struct SetEntityVelocityPacket {
+    pub id: i32,
+    /// Velocity in blocks per tick
+    pub velocity: Vec3,
+}
+
+impl From<RawPacket> for SetEntityVelocityPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let id = packet.read_varint();
+        let velocity = read_velocity(&mut packet);
+
+        SetEntityVelocityPacket {
+            id,
+            velocity,
+        }
+    }
+}
+
+/// Represents teleport entity packet (0x66), sent when entity moves more than 8 blocks
+#[derive(Debug, Clone)]
+pub struct TeleportEntityPacket {
+    pub id: i32,
+    pub position: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub on_ground: bool,
+}
+
+impl From<RawPacket> for TeleportEntityPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let id = packet.read_varint();
+        let position = read_vec3(&mut packet);
+        let yaw = packet.read_angle();
+        let pitch = packet.read_angle();
+        let on_ground = packet.read_bool();
+
+        TeleportEntityPacket {
+            id,
+            position,
+            yaw,
+            pitch,
+            on_ground,
+        }
+    }
+}
\ No newline at end of file
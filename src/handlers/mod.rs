@@ -1,11 +1,27 @@
 use crate::client::MinecraftClient;
 
 pub mod basic;
+pub mod blocks;
 pub mod chat;
+pub mod commands;
+pub mod entities;
+pub mod player_info;
+pub mod position;
+pub mod suggestions;
+pub mod tab_list;
+pub mod world;
 
 pub fn register_all_handlers(client: &mut MinecraftClient) {
     client.register_packet_handler(basic::KeepAliveHandler);
     client.register_packet_handler(basic::DeathHandler);
+    client.register_packet_handler(position::SyncPlayerPositionHandler);
+    client.register_packet_handler(world::WorldHandler);
+    client.register_packet_handler(blocks::BlockUpdateHandler);
     
     client.register_packet_handler(chat::ChatHandler);
+    client.register_packet_handler(player_info::PlayerInfoHandler);
+    client.register_packet_handler(tab_list::TabListHandler);
+    client.register_packet_handler(entities::EntitiesHandler);
+    client.register_packet_handler(commands::CommandsHandler);
+    client.register_packet_handler(suggestions::CommandSuggestionsHandler);
 }
\ No newline at end of file
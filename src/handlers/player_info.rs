@@ -0,0 +1,293 @@
+use miners_protocol::{packet::RawPacket, utils::certificates::ProfilePublicKey};
+
+use crate::{client::{ClientPacketHandler, ClientMutLock, ClientLockExt, MinecraftClient}, chat::component::FormattedChatMessage, players::PlayerEntry, define_events};
+
+/// Handles player info packets (0x37) which are sent by the server when players join, leave or change
+///
+/// Keeps the player list up to date (emitting join, leave and update events) as well as public keys of players,
+/// which are needed for verifying their chat messages
+#[derive(Clone)]
+pub struct PlayerInfoHandler;
+
+impl ClientPacketHandler for PlayerInfoHandler {
+    fn handle(&self, client: ClientMutLock, packet: &RawPacket) {
+        // Ensure that we are in play state
+        if client.get_state() != miners_protocol::ConnectionState::Play {
+            return;
+        }
+
+        // Clone packet to read it
+        let Some(packet) = PlayerInfoPacket::read(&mut packet.clone()) else {
+            log::warn!(target: "miners-client", "Failed to read player info packet");
+            return;
+        };
+        log::debug!(target: "miners-client", "Player info packet received: {:?}", packet);
+
+        let mut client = client.wl();
+        for (uuid, action) in packet.players {
+            match action {
+                PlayerInfoAction::AddPlayer { name, properties, gamemode, ping, display_name, public_key } => {
+                    // Keys not signed by Mojang are treated as absent, so their messages are unsigned
+                    match public_key.filter(|public_key| public_key.validate(uuid)) {
+                        Some(public_key) => client.chat_session.player_keys.insert(uuid, public_key),
+                        None => client.chat_session.player_keys.remove(&uuid),
+                    };
+
+                    let player = PlayerEntry {
+                        uuid,
+                        name,
+                        properties,
+                        gamemode,
+                        latency: ping,
+                        display_name: display_name.as_deref().map(parse_display_name),
+                    };
+                    match client.player_list.players.insert(uuid, player.clone()) {
+                        Some(previous) => client.emit(PlayerUpdateEvent { player, previous }),
+                        None => client.emit(PlayerJoinEvent { player }),
+                    }
+                },
+                PlayerInfoAction::RemovePlayer => {
+                    client.chat_session.remove_player(uuid);
+                    if let Some(player) = client.player_list.players.remove(&uuid) {
+                        client.emit(PlayerLeaveEvent { player });
+                    }
+                },
+                PlayerInfoAction::UpdateGamemode(gamemode) => update_player(&mut client, uuid, |player| player.gamemode = gamemode),
+                PlayerInfoAction::UpdateLatency(latency) => update_player(&mut client, uuid, |player| player.latency = latency),
+                PlayerInfoAction::UpdateDisplayName(display_name) => {
+                    update_player(&mut client, uuid, |player| player.display_name = display_name.as_deref().map(parse_display_name));
+                },
+            }
+        }
+    }
+
+    fn ids(&self) -> &'static [i32] {
+        &[0x37]
+    }
+}
+
+define_events!(
+    PlayerJoinEvent (player: PlayerEntry) => "Event emitted when player is added to the player list",
+    PlayerLeaveEvent (player: PlayerEntry) => "Event emitted when player is removed from the player list",
+    PlayerUpdateEvent (player: PlayerEntry, previous: PlayerEntry) => "Event emitted when player's game mode, latency or display name changes"
+);
+
+/// Updates player in the player list and emits `PlayerUpdateEvent`
+fn update_player(client: &mut MinecraftClient, uuid: u128, update: impl FnOnce(&mut PlayerEntry)) {
+    let Some(player) = client.player_list.players.get_mut(&uuid) else {
+        log::debug!(target: "miners-client", "Received update of unknown player {:x}", uuid);
+        return;
+    };
+
+    let previous = player.clone();
+    update(player);
+    let player = player.clone();
+    client.emit(PlayerUpdateEvent { player, previous });
+}
+
+fn parse_display_name(json: &str) -> FormattedChatMessage {
+    FormattedChatMessage::from_json(json).unwrap_or_else(|e| {
+        log::warn!(target: "miners-client", "Failed to parse display name {}: {:?}", json, e);
+        FormattedChatMessage::from_plain(json.to_string())
+    })
+}
+
+/// Represents player info packet (0x37)
+///
+/// Each packet contains a single action applied to a list of players
+#[derive(Debug, Clone)]
+pub struct PlayerInfoPacket {
+    pub players: Vec<(u128, PlayerInfoAction)>,
+}
+
+/// Single profile property (e.g. `textures`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileProperty {
+    pub name: String,
+    pub value: String,
+    pub signature: Option<String>,
+}
+
+/// Action of player info packet (with its data)
+#[derive(Debug, Clone)]
+pub enum PlayerInfoAction {
+    AddPlayer {
+        name: String,
+        properties: Vec<ProfileProperty>,
+        gamemode: i32,
+        ping: i32,
+        display_name: Option<String>,
+        public_key: Option<ProfilePublicKey>,
+    },
+    UpdateGamemode(i32),
+    UpdateLatency(i32),
+    UpdateDisplayName(Option<String>),
+    RemovePlayer,
+}
+
+impl PlayerInfoPacket {
+    /// Reads the packet (`None` if its action is unknown or it is truncated)
+    pub fn read(packet: &mut RawPacket) -> Option<PlayerInfoPacket> {
+        let action = packet.try_read_varint()?;
+        let count = packet.try_read_varint()?;
+
+        let read_action: fn(&mut RawPacket, u128) -> Option<PlayerInfoAction> = match action {
+            0 => read_add_player,
+            1 => |packet, _| Some(PlayerInfoAction::UpdateGamemode(packet.try_read_varint()?)),
+            2 => |packet, _| Some(PlayerInfoAction::UpdateLatency(packet.try_read_varint()?)),
+            3 => |packet, _| Some(PlayerInfoAction::UpdateDisplayName(if packet.try_read_bool()? {
+                Some(packet.try_read_string()?)
+            } else {
+                None
+            })),
+            4 => |_, _| Some(PlayerInfoAction::RemovePlayer),
+            _ => {
+                // Rest of the packet can't be read without knowing the action
+                log::warn!(target: "miners-client", "Unknown player info action: {}", action);
+                return None;
+            },
+        };
+
+        let mut players = Vec::new();
+        for _ in 0..count {
+            let uuid = packet.try_read_uuid()?;
+            players.push((uuid, read_action(packet, uuid)?));
+        }
+
+        Some(PlayerInfoPacket {
+            players,
+        })
+    }
+}
+
+fn read_add_player(packet: &mut RawPacket, uuid: u128) -> Option<PlayerInfoAction> {
+    let name = packet.try_read_string()?;
+    let properties_len = packet.try_read_varint()?;
+    let mut properties = Vec::new();
+    for _ in 0..properties_len {
+        let name = packet.try_read_string()?;
+        let value = packet.try_read_string()?;
+        let signature = if packet.try_read_bool()? {
+            Some(packet.try_read_string()?)
+        } else {
+            None
+        };
+        properties.push(ProfileProperty {
+            name,
+            value,
+            signature,
+        });
+    }
+
+    let gamemode = packet.try_read_varint()?;
+    let ping = packet.try_read_varint()?;
+    let display_name = if packet.try_read_bool()? {
+        Some(packet.try_read_string()?)
+    } else {
+        None
+    };
+
+    let public_key = if packet.try_read_bool()? { // Has sig data
+        let expires_at = packet.try_read_long()?;
+        let key = packet.try_read_byte_array()?;
+        let signature = packet.try_read_byte_array()?;
+        ProfilePublicKey::from_der(expires_at, &key, signature)
+            .map_err(|e| log::warn!(target: "miners-client", "Invalid public key of player {:x}: {:?}", uuid, e))
+            .ok()
+    } else {
+        None
+    };
+
+    Some(PlayerInfoAction::AddPlayer {
+        name,
+        properties,
+        gamemode,
+        ping,
+        display_name,
+        public_key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn add_player_packet() -> RawPacket {
+        let mut packet = RawPacket::empty(0x37);
+        packet.write_varint(0);
+        packet.write_varint(1);
+        packet.write_uuid(0x1234);
+        packet.write_string("Steve");
+        packet.write_varint(1);
+        packet.write_string("textures");
+        packet.write_string("e30=");
+        packet.write_bool(true);
+        packet.write_string("c2ln");
+        packet.write_varint(1);
+        packet.write_varint(42);
+        packet.write_bool(true);
+        packet.write_string(r#"{"text":"Steve"}"#);
+        // Public key which is not valid DER
+        packet.write_bool(true);
+        packet.write_long(0);
+        packet.write_byte_array(&[1, 2, 3]);
+        packet.write_byte_array(&[4]);
+        packet
+    }
+
+    #[test]
+    fn read_actions() {
+        let packet = PlayerInfoPacket::read(&mut add_player_packet()).unwrap();
+        assert_eq!(packet.players.len(), 1);
+        let (uuid, PlayerInfoAction::AddPlayer { name, properties, gamemode, ping, display_name, public_key }) = &packet.players[0] else {
+            panic!("expected add player action");
+        };
+        assert_eq!((*uuid, name.as_str(), *gamemode, *ping), (0x1234, "Steve", 1, 42));
+        assert_eq!(properties, &vec![ProfileProperty { name: "textures".to_string(), value: "e30=".to_string(), signature: Some("c2ln".to_string()) }]);
+        assert_eq!(display_name.as_deref(), Some(r#"{"text":"Steve"}"#));
+        assert!(public_key.is_none());
+
+        let mut packet = RawPacket::empty(0x37);
+        packet.write_varint(2);
+        packet.write_varint(2);
+        packet.write_uuid(1);
+        packet.write_varint(100);
+        packet.write_uuid(2);
+        packet.write_varint(250);
+        let players = PlayerInfoPacket::read(&mut packet).unwrap().players;
+        assert!(matches!(players[..], [(1, PlayerInfoAction::UpdateLatency(100)), (2, PlayerInfoAction::UpdateLatency(250))]));
+
+        let mut packet = RawPacket::empty(0x37);
+        packet.write_varint(3);
+        packet.write_varint(1);
+        packet.write_uuid(1);
+        packet.write_bool(false);
+        let players = PlayerInfoPacket::read(&mut packet).unwrap().players;
+        assert!(matches!(players[..], [(1, PlayerInfoAction::UpdateDisplayName(None))]));
+
+        let mut packet = RawPacket::empty(0x37);
+        packet.write_varint(4);
+        packet.write_varint(1);
+        packet.write_uuid(1);
+        let players = PlayerInfoPacket::read(&mut packet).unwrap().players;
+        assert!(matches!(players[..], [(1, PlayerInfoAction::RemovePlayer)]));
+    }
+
+    #[test]
+    fn read_malformed() {
+        // Unknown actions
+        for action in [-1, 5, 1000] {
+            let mut packet = RawPacket::empty(0x37);
+            packet.write_varint(action);
+            packet.write_varint(1);
+            packet.write_uuid(1);
+            assert!(PlayerInfoPacket::read(&mut packet).is_none());
+        }
+
+        let packet = add_player_packet();
+        for length in 0..packet.data.len() {
+            let mut truncated = RawPacket::new(0x37, packet.data[..length].to_vec());
+            assert!(PlayerInfoPacket::read(&mut truncated).is_none(), "packet truncated to {} bytes should be rejected", length);
+        }
+    }
+}
\ No newline at end of file
@@ -0,0 +1,102 @@
+use miners_protocol::{packet::RawPacket, utils::location::Vec3};
+
+use crate::{client::{ClientPacketHandler, ClientMutLock, ClientLockExt}, player::{ConfirmTeleportPacket, MovementPacket}, define_events};
+
+/// Handles synchronize player position packets (0x39) which are sent by the server on spawn and teleport
+///
+/// Applies new position, confirms the teleport and sends the position back (same as vanilla)
+#[derive(Clone)]
+pub struct SyncPlayerPositionHandler;
+
+impl ClientPacketHandler for SyncPlayerPositionHandler {
+    fn handle(&self, client: ClientMutLock, packet: &RawPacket) {
+        // Ensure that we are in play state
+        if client.get_state() != miners_protocol::ConnectionState::Play {
+            return;
+        }
+
+        // Clone packet to read it
+        let packet = SyncPlayerPositionPacket::from(packet.clone());
+        log::debug!(target: "miners-client", "Synchronize player position packet received: {:?}", packet);
+
+        let mut client = client.wl();
+        let (position, yaw, pitch) = packet.apply(client.player.position, client.player.yaw, client.player.pitch);
+        client.player.teleport(position, yaw, pitch);
+        // Velocity is kept only along relative axes
+        let velocity = &mut client.player.velocity;
+        for (flag, velocity) in [(0x01, &mut velocity.x), (0x02, &mut velocity.y), (0x04, &mut velocity.z)] {
+            if packet.flags & flag == 0 {
+                *velocity = 0.0;
+            }
+        }
+
+        client.socket.send_packet(ConfirmTeleportPacket { teleport_id: packet.teleport_id }).ok();
+        // Vanilla always reports not being on ground here
+        client.socket.send_packet(MovementPacket {
+            position: Some(position),
+            rotation: Some((yaw, pitch)),
+            on_ground: false,
+        }).ok();
+
+        client.emit(TeleportEvent { position, yaw, pitch });
+    }
+
+    fn ids(&self) -> &'static [i32] {
+        &[0x39]
+    }
+}
+
+define_events!(TeleportEvent (position: Vec3, yaw: f32, pitch: f32) => "Event emitted when the server sets player's position (on spawn, teleport or when movement is rejected)");
+
+/// Represents synchronize player position packet (0x39)
+#[derive(Debug, Clone)]
+pub struct SyncPlayerPositionPacket {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub yaw: f32,
+    pub pitch: f32,
+    /// Bit mask of relative fields (0x01 - x, 0x02 - y, 0x04 - z, 0x08 - yaw, 0x10 - pitch)
+    pub flags: u8,
+    pub teleport_id: i32,
+    pub dismount_vehicle: bool,
+}
+
+impl SyncPlayerPositionPacket {
+    /// Returns new position, yaw and pitch (relative fields are added to the current values)
+    pub fn apply(&self, position: Vec3, yaw: f32, pitch: f32) -> (Vec3, f32, f32) {
+        let relative = |flag: u8, value: f64, current: f64| if self.flags & flag != 0 { current + value } else { value };
+        let position = Vec3::new(
+            relative(0x01, self.x, position.x),
+            relative(0x02, self.y, position.y),
+            relative(0x04, self.z, position.z),
+        );
+        let yaw = relative(0x08, self.yaw as f64, yaw as f64) as f32;
+        let pitch = relative(0x10, self.pitch as f64, pitch as f64) as f32;
+        (position, yaw, pitch)
+    }
+}
+
+impl From<RawPacket> for SyncPlayerPositionPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let x = packet.read_double();
+        let y = packet.read_double();
+        let z = packet.read_double();
+        let yaw = packet.read_float();
+        let pitch = packet.read_float();
+        let flags = packet.read_byte();
+        let teleport_id = packet.read_varint();
+        let dismount_vehicle = packet.read_bool();
+
+        SyncPlayerPositionPacket {
+            x,
+            y,
+            z,
+            yaw,
+            pitch,
+            flags,
+            teleport_id,
+            dismount_vehicle,
+        }
+    }
+}
\ No newline at end of file
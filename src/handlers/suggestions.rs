@@ -0,0 +1,93 @@
+use miners_protocol::packet::{RawPacket, IntoPacket};
+
+use crate::{client::{ClientPacketHandler, ClientMutLock, ClientLockExt}, chat::component::FormattedChatMessage};
+
+/// Handles command suggestions response packets (0x0E) and passes them to the waiting requests
+/// 
+/// Use `MinecraftClient::complete` to request suggestions
+#[derive(Clone)]
+pub struct CommandSuggestionsHandler;
+
+impl ClientPacketHandler for CommandSuggestionsHandler {
+    fn handle(&self, client: ClientMutLock, packet: &RawPacket) {
+        // Ensure that we are in play state
+        if client.get_state() != miners_protocol::ConnectionState::Play {
+            return;
+        }
+
+        // Read transaction id from a copy, so whole packet can be passed further
+        let transaction_id = packet.clone().read_varint();
+        if !client.wl().pending_requests.resolve(packet.id, transaction_id, packet.clone()) {
+            log::debug!(target: "miners-client", "Received command suggestions for unknown transaction: {}", transaction_id);
+        }
+    }
+
+    fn ids(&self) -> &'static [i32] {
+        &[0x0E]
+    }
+}
+
+/// Single command suggestion
+#[derive(Debug, Clone, PartialEq)]
+pub struct Suggestion {
+    /// Text which should replace part of the input (`start..start + length`)
+    pub text: String,
+    pub tooltip: Option<FormattedChatMessage>,
+    pub start: usize,
+    pub length: usize,
+}
+
+/// Represents command suggestions response packet (0x0E)
+#[derive(Debug, Clone)]
+pub struct CommandSuggestionsResponse {
+    pub transaction_id: i32,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl From<RawPacket> for CommandSuggestionsResponse {
+    fn from(mut packet: RawPacket) -> Self {
+        let transaction_id = packet.read_varint();
+        let start = packet.read_varint() as usize;
+        let length = packet.read_varint() as usize;
+        let count = packet.read_varint();
+
+        let mut suggestions = Vec::new();
+        for _ in 0..count {
+            let text = packet.read_string();
+            let tooltip = if packet.read_bool() {
+                let json = packet.read_string();
+                Some(FormattedChatMessage::from_json(&json).unwrap_or(FormattedChatMessage::from_plain(json)))
+            } else {
+                None
+            };
+            suggestions.push(Suggestion {
+                text,
+                tooltip,
+                start,
+                length,
+            });
+        }
+
+        CommandSuggestionsResponse {
+            transaction_id,
+            suggestions,
+        }
+    }
+}
+
+/// Command suggestions request packet (0x09)
+/// 
+/// Text should include leading `/` when completing commands
+pub struct CommandSuggestionsRequestPacket {
+    pub transaction_id: i32,
+    pub text: String,
+}
+
+impl IntoPacket for CommandSuggestionsRequestPacket {
+    fn into_packet(self, _protocol_version: i32) -> RawPacket {
+        let mut packet = RawPacket::empty(0x09);
+        packet.write_varint(self.transaction_id);
+        packet.write_string(&self.text);
+        packet
+    }
+}
\ No newline at end of file
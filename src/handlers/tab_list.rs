@@ -0,0 +1,64 @@
+use miners_protocol::packet::RawPacket;
+
+use crate::{client::{ClientPacketHandler, ClientMutLock, ClientLockExt}, chat::component::FormattedChatMessage, players::TabList, define_events};
+
+/// Handles set tab list header and footer packets (0x63)
+///
+/// Stores header and footer on the client and emits `TabListEvent`
+#[derive(Clone)]
+pub struct TabListHandler;
+
+impl ClientPacketHandler for TabListHandler {
+    fn handle(&self, client: ClientMutLock, packet: &RawPacket) {
+        // Ensure that we are in play state
+        if client.get_state() != miners_protocol::ConnectionState::Play {
+            return;
+        }
+
+        // Clone packet to read it
+        let packet = TabListPacket::from(packet.clone());
+        let parse_component = |json: &str| FormattedChatMessage::from_json(json).unwrap_or_else(|e| {
+            log::warn!(target: "miners-client", "Failed to parse tab list component {}: {:?}", json, e);
+            FormattedChatMessage::from_plain(json.to_string())
+        });
+        let tab_list = TabList {
+            header: parse_component(&packet.header),
+            footer: parse_component(&packet.footer),
+        };
+        log::debug!(target: "miners-client", "Tab list updated: {:?}", tab_list);
+
+        let mut client = client.wl();
+        client.tab_list = tab_list.clone();
+        client.emit(TabListEvent {
+            header: tab_list.header,
+            footer: tab_list.footer,
+        });
+    }
+
+    fn ids(&self) -> &'static [i32] {
+        &[0x63]
+    }
+}
+
+define_events!(TabListEvent (header: FormattedChatMessage, footer: FormattedChatMessage) => "Event emitted when tab list header or footer changes");
+
+/// Represents set tab list header and footer packet (0x63)
+#[derive(Debug, Clone)]
+pub struct TabListPacket {
+    /// Header (JSON), empty component (`{"text":""}`) hides it
+    pub header: String,
+    /// Footer (JSON), empty component (`{"text":""}`) hides it
+    pub footer: String,
+}
+
+impl From<RawPacket> for TabListPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let header = packet.read_string();
+        let footer = packet.read_string();
+
+        TabListPacket {
+            header,
+            footer,
+        }
+    }
+}
\ No newline at end of file
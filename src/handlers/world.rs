@@ -0,0 +1,86 @@
+use miners_protocol::packet::RawPacket;
+
+use crate::{client::{ClientPacketHandler, ClientMutLock, ClientLockExt}, world::chunk::{ChunkDataPacket, UpdateLightPacket, UnloadChunkPacket}, define_events};
+
+/// Handles packets which load, light and unload chunks (0x21, 0x24, 0x1C) as well as respawn packets (0x3E)
+///
+/// Keeps `World` of the client up to date and emits chunk load and unload events
+#[derive(Clone)]
+pub struct WorldHandler;
+
+impl ClientPacketHandler for WorldHandler {
+    fn handle(&self, client: ClientMutLock, packet: &RawPacket) {
+        // Ensure that we are in play state
+        if client.get_state() != miners_protocol::ConnectionState::Play {
+            return;
+        }
+
+        // Clone packet to read it
+        let packet = packet.clone();
+        let mut client = client.wl();
+        match packet.id {
+            0x21 => {
+                let packet = ChunkDataPacket::from(packet);
+                let (x, z) = (packet.x, packet.z);
+                let (min_y, height) = (client.world.min_y, client.world.height);
+                match packet.into_chunk(min_y, height) {
+                    Some(chunk) => {
+                        client.world.insert_chunk(chunk);
+                        client.emit(ChunkLoadEvent { x, z });
+                    },
+                    None => log::warn!(target: "miners-client", "Received malformed chunk data of chunk {} {}", x, z),
+                }
+            },
+            0x24 => {
+                let packet = UpdateLightPacket::from(packet);
+                match client.world.get_chunk_mut(packet.x, packet.z) {
+                    Some(chunk) => chunk.apply_light(packet.light),
+                    None => log::debug!(target: "miners-client", "Received light of unloaded chunk {} {}", packet.x, packet.z),
+                }
+            },
+            0x1C => {
+                let packet = UnloadChunkPacket::from(packet);
+                if client.world.unload_chunk(packet.x, packet.z).is_some() {
+                    client.emit(ChunkUnloadEvent { x: packet.x, z: packet.z });
+                }
+            },
+            _ => {
+                let packet = RespawnPacket::from(packet);
+                log::debug!(target: "miners-client", "Respawn packet received: {:?}", packet);
+
+                client.dimension_type = packet.dimension_type;
+                let dimension_type = client.registries.dimension_types.get_by_name(&client.dimension_type).cloned();
+                client.world.set_dimension(&packet.dimension_name, dimension_type.as_ref());
+                // Entities of the previous world are not removed by the server
+                client.entities.entities.clear();
+            },
+        }
+    }
+
+    fn ids(&self) -> &'static [i32] {
+        &[0x21, 0x24, 0x1C, 0x3E]
+    }
+}
+
+define_events!(
+    ChunkLoadEvent (x: i32, z: i32) => "Event emitted when chunk is loaded (coordinates are chunk coordinates)",
+    ChunkUnloadEvent (x: i32, z: i32) => "Event emitted when chunk is unloaded (coordinates are chunk coordinates)"
+);
+
+/// Represents respawn packet (0x3E) which is sent when player respawns or changes dimension
+///
+/// Only the dimension is read, as the rest is not used by the client
+#[derive(Debug, Clone)]
+pub struct RespawnPacket {
+    pub dimension_type: String,
+    pub dimension_name: String,
+}
+
+impl From<RawPacket> for RespawnPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        RespawnPacket {
+            dimension_type: packet.read_string(),
+            dimension_name: packet.read_string(),
+        }
+    }
+}
\ No newline at end of file
@@ -1,4 +1,14 @@
+pub mod chat;
 pub mod client;
+pub mod commands;
+pub mod entities;
 pub mod events;
 pub mod handlers;
-pub mod plugins;
\ No newline at end of file
+pub mod pathfinder;
+pub mod physics;
+pub mod player;
+pub mod players;
+pub mod plugins;
+pub mod registry;
+pub mod requests;
+pub mod world;
\ No newline at end of file
@@ -0,0 +1,344 @@
+use std::{collections::{BinaryHeap, HashMap}, cmp::Ordering};
+
+use miners_protocol::utils::location::BlockPos;
+
+use crate::physics::{PhysicsWorld, Fluid};
+
+use super::{goals::Goal, PathfinderSettings};
+
+/// Maximum height the player may drop from
+pub const MAX_DROP: i32 = 3;
+
+/// How the player gets to the node from the previous one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Movement {
+    /// Start of the path (current position)
+    Start,
+    Walk,
+    /// Jump one block up
+    Jump,
+    /// Drop down (by specified number of blocks)
+    Drop(i32),
+    /// Climb ladder or vines (up or down)
+    Climb,
+    /// Break blocks in the way, then walk
+    Break(Vec<BlockPos>),
+    /// Place block to walk on (bridge over a gap)
+    Place(BlockPos),
+}
+
+/// Single node of the path (feet position of the player)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PathNode {
+    pub position: BlockPos,
+    pub movement: Movement,
+}
+
+/// Node in the open set ordered by its estimated total cost
+struct OpenNode {
+    position: BlockPos,
+    estimated_cost: f64,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimated_cost == other.estimated_cost
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Reversed, as BinaryHeap is a max-heap
+        other.estimated_cost.total_cmp(&self.estimated_cost)
+    }
+}
+
+/// Finds the cheapest path from `start` to the goal using A* (returns `None` if there is no path within the node limit)
+pub fn find_path(world: &impl PhysicsWorld, start: BlockPos, goal: &dyn Goal, settings: &PathfinderSettings) -> Option<Vec<PathNode>> {
+    let mut open = BinaryHeap::new();
+    let mut costs = HashMap::from([(start, 0.0)]);
+    let mut came_from: HashMap<BlockPos, (BlockPos, Movement)> = HashMap::new();
+    open.push(OpenNode { position: start, estimated_cost: goal.heuristic(start) });
+
+    let mut visited = 0;
+    while let Some(OpenNode { position, .. }) = open.pop() {
+        if goal.is_reached(position) {
+            return Some(reconstruct_path(&came_from, start, position));
+        }
+
+        visited += 1;
+        if visited > settings.max_nodes {
+            log::debug!(target: "miners-client", "Pathfinder gave up after visiting {} nodes", visited);
+            return None;
+        }
+
+        let cost = costs[&position];
+        for (neighbor, movement, move_cost) in neighbors(world, position, settings) {
+            let new_cost = cost + move_cost;
+            if costs.get(&neighbor).is_some_and(|&old_cost| old_cost <= new_cost) {
+                continue;
+            }
+            costs.insert(neighbor, new_cost);
+            came_from.insert(neighbor, (position, movement));
+            open.push(OpenNode { position: neighbor, estimated_cost: new_cost + goal.heuristic(neighbor) });
+        }
+    }
+    None
+}
+
+fn reconstruct_path(came_from: &HashMap<BlockPos, (BlockPos, Movement)>, start: BlockPos, end: BlockPos) -> Vec<PathNode> {
+    let mut path = Vec::new();
+    let mut position = end;
+    while position != start {
+        let (previous, movement) = came_from[&position].clone();
+        path.push(PathNode { position, movement });
+        position = previous;
+    }
+    path.push(PathNode { position: start, movement: Movement::Start });
+    path.reverse();
+    path
+}
+
+/// Returns nodes reachable from `pos` with movement used and its cost
+fn neighbors(world: &impl PhysicsWorld, pos: BlockPos, settings: &PathfinderSettings) -> Vec<(BlockPos, Movement, f64)> {
+    let mut result = Vec::new();
+    let up = pos.offset(0, 1, 0);
+
+    for (dx, dz) in [(1, 0), (-1, 0), (0, 1), (0, -1)] {
+        let next = pos.offset(dx, 0, dz);
+
+        if can_stand_in(world, next) {
+            result.push((next, Movement::Walk, 1.0));
+            continue;
+        }
+
+        // Jump one block up
+        let above = next.offset(0, 1, 0);
+        if is_passable(world, up.offset(0, 1, 0)) && can_stand_in(world, above) {
+            result.push((above, Movement::Jump, 2.0));
+            continue;
+        }
+
+        // Drop down
+        if is_passable(world, next) && is_passable(world, next.offset(0, 1, 0)) {
+            if let Some(depth) = (1..=MAX_DROP + 1).find(|depth| !is_passable(world, next.offset(0, -depth, 0))) {
+                let landing = next.offset(0, 1 - depth, 0);
+                if depth > 1 && can_stand_in(world, landing) {
+                    result.push((landing, Movement::Drop(depth - 1), 1.0 + (depth - 1) as f64 * 0.5));
+                    continue;
+                }
+            }
+
+            // Bridge over the gap
+            let below = next.offset(0, -1, 0);
+            if settings.allow_place && is_replaceable(world, below) && is_solid(world, pos.offset(0, -1, 0)) {
+                result.push((next, Movement::Place(below), 1.0 + settings.place_cost));
+                continue;
+            }
+        }
+
+        // Break blocks in the way
+        if settings.allow_break && is_solid(world, next.offset(0, -1, 0)) {
+            let blocks = [next, next.offset(0, 1, 0)].into_iter()
+                .filter(|block| !is_passable(world, *block))
+                .collect::<Vec<BlockPos>>();
+            if !blocks.is_empty() && blocks.iter().all(|block| is_breakable(world, *block, settings)) {
+                let cost = 1.0 + settings.break_cost * blocks.len() as f64;
+                result.push((next, Movement::Break(blocks), cost));
+            }
+        }
+    }
+
+    // Diagonal movement (only if both sides are free, so the player doesn't clip corners)
+    for (dx, dz) in [(1, 1), (1, -1), (-1, 1), (-1, -1)] {
+        let next = pos.offset(dx, 0, dz);
+        let side_a = pos.offset(dx, 0, 0);
+        let side_b = pos.offset(0, 0, dz);
+        if can_stand_in(world, next) && is_column_free(world, side_a) && is_column_free(world, side_b) {
+            result.push((next, Movement::Walk, std::f64::consts::SQRT_2));
+        }
+    }
+
+    // Ladders and vines
+    if world.is_climbable(pos) && is_column_free(world, up) {
+        result.push((up, Movement::Climb, 1.5));
+    }
+    let down = pos.offset(0, -1, 0);
+    if world.is_climbable(down) {
+        result.push((down, Movement::Climb, 1.5));
+    }
+
+    result
+}
+
+/// Whether the player can move through the block (no collision boxes or climbable) and it isn't dangerous (lava)
+fn is_passable(world: &impl PhysicsWorld, pos: BlockPos) -> bool {
+    world.is_loaded(pos) && (world.collision_boxes(pos).is_empty() || world.is_climbable(pos)) && world.fluid(pos) != Some(Fluid::Lava)
+}
+
+fn is_solid(world: &impl PhysicsWorld, pos: BlockPos) -> bool {
+    world.is_loaded(pos) && !world.collision_boxes(pos).is_empty()
+}
+
+fn is_replaceable(world: &impl PhysicsWorld, pos: BlockPos) -> bool {
+    world.is_loaded(pos) && world.collision_boxes(pos).is_empty() && world.fluid(pos).is_none()
+}
+
+/// Whether the block can be broken with the tool (bedrock and blocks with unknown hardness can't)
+/// without flooding the path (no fluid next to it)
+fn is_breakable(world: &impl PhysicsWorld, pos: BlockPos, settings: &PathfinderSettings) -> bool {
+    world.destroy_progress(pos, settings.tool.as_ref(), false, true) > 0.0
+        && [pos.offset(0, 1, 0), pos.offset(1, 0, 0), pos.offset(-1, 0, 0), pos.offset(0, 0, 1), pos.offset(0, 0, -1)]
+            .into_iter()
+            .all(|neighbor| world.fluid(neighbor).is_none())
+}
+
+/// Whether both feet and head blocks are passable
+fn is_column_free(world: &impl PhysicsWorld, pos: BlockPos) -> bool {
+    is_passable(world, pos) && is_passable(world, pos.offset(0, 1, 0))
+}
+
+/// Whether the player can stand with its feet in the block
+fn can_stand_in(world: &impl PhysicsWorld, pos: BlockPos) -> bool {
+    is_column_free(world, pos) && (is_solid(world, pos.offset(0, -1, 0)) || world.is_climbable(pos) || world.fluid(pos) == Some(Fluid::Water))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{pathfinder::goals::GoalBlock, physics::{Tool, aabb::Aabb}};
+
+    use super::*;
+
+    #[derive(Clone, Copy, PartialEq)]
+    enum TestBlock {
+        Stone,
+        Bedrock,
+        Ladder,
+    }
+
+    /// Blocks inside the loaded area (X and Z between -20 and 20, Y between 0 and 20), other blocks are air
+    #[derive(Default)]
+    struct TestWorld(HashMap<BlockPos, TestBlock>);
+
+    impl TestWorld {
+        /// World with stone floor at Y 0 between `from` and `to` X (Z 0)
+        fn with_floor(from: i32, to: i32) -> TestWorld {
+            let mut world = TestWorld::default();
+            for x in from..=to {
+                world.set(x, 0, 0, TestBlock::Stone);
+            }
+            world
+        }
+
+        fn set(&mut self, x: i32, y: i32, z: i32, block: TestBlock) {
+            self.0.insert(BlockPos::new(x, y, z), block);
+        }
+    }
+
+    impl PhysicsWorld for TestWorld {
+        fn is_loaded(&self, pos: BlockPos) -> bool {
+            pos.x.abs() <= 20 && pos.z.abs() <= 20 && (0..=20).contains(&pos.y)
+        }
+
+        fn collision_boxes(&self, pos: BlockPos) -> Vec<Aabb> {
+            match self.0.get(&pos) {
+                Some(TestBlock::Stone | TestBlock::Bedrock) => vec![Aabb::FULL_BLOCK],
+                _ => Vec::new(),
+            }
+        }
+
+        fn is_climbable(&self, pos: BlockPos) -> bool {
+            self.0.get(&pos) == Some(&TestBlock::Ladder)
+        }
+
+        fn destroy_progress(&self, pos: BlockPos, _tool: Option<&Tool>, _eyes_in_water: bool, _on_ground: bool) -> f32 {
+            if self.0.get(&pos) == Some(&TestBlock::Stone) { 1.0 / 150.0 } else { 0.0 }
+        }
+    }
+
+    fn search(world: &TestWorld, start: BlockPos, goal: BlockPos, settings: &PathfinderSettings) -> Option<Vec<PathNode>> {
+        let path = find_path(world, start, &GoalBlock(goal), settings)?;
+        assert_eq!(path.first(), Some(&PathNode { position: start, movement: Movement::Start }));
+        assert_eq!(path.last().map(|node| node.position), Some(goal));
+        Some(path)
+    }
+
+    fn movements(path: &[PathNode]) -> Vec<Movement> {
+        path.iter().skip(1).map(|node| node.movement.clone()).collect()
+    }
+
+    #[test]
+    fn walk() {
+        let world = TestWorld::with_floor(0, 5);
+        let path = search(&world, BlockPos::new(0, 1, 0), BlockPos::new(4, 1, 0), &PathfinderSettings::default()).unwrap();
+        assert_eq!(movements(&path), vec![Movement::Walk; 4]);
+    }
+
+    #[test]
+    fn jump_and_drop() {
+        let mut world = TestWorld::with_floor(0, 6);
+        world.set(3, 1, 0, TestBlock::Stone);
+        let path = search(&world, BlockPos::new(0, 1, 0), BlockPos::new(3, 2, 0), &PathfinderSettings::default()).unwrap();
+        assert_eq!(movements(&path), [Movement::Walk, Movement::Walk, Movement::Jump]);
+
+        // Pillar two blocks high
+        world.set(3, 2, 0, TestBlock::Stone);
+        world.set(3, 3, 0, TestBlock::Stone);
+        let path = search(&world, BlockPos::new(3, 4, 0), BlockPos::new(5, 1, 0), &PathfinderSettings::default()).unwrap();
+        assert_eq!(movements(&path), [Movement::Drop(3), Movement::Walk]);
+    }
+
+    #[test]
+    fn climb() {
+        let mut world = TestWorld::with_floor(0, 1);
+        for y in 1..=3 {
+            world.set(1, y, 0, TestBlock::Ladder);
+        }
+        world.set(2, 3, 0, TestBlock::Stone);
+        let path = search(&world, BlockPos::new(0, 1, 0), BlockPos::new(2, 4, 0), &PathfinderSettings::default()).unwrap();
+        assert_eq!(path[1], PathNode { position: BlockPos::new(1, 1, 0), movement: Movement::Walk });
+        assert!(movements(&path).iter().filter(|movement| **movement == Movement::Climb).count() >= 2);
+    }
+
+    #[test]
+    fn no_path() {
+        // Gap which is too deep to drop into
+        let world = TestWorld::with_floor(0, 3);
+        assert_eq!(find_path(&world, BlockPos::new(0, 1, 0), &GoalBlock(BlockPos::new(10, 1, 0)), &PathfinderSettings::default()), None);
+
+        // Wall in the whole loaded area, which can only be broken if it isn't bedrock
+        let mut world = TestWorld::default();
+        for x in -20..=20 {
+            for z in -20..=20 {
+                world.set(x, 0, z, TestBlock::Stone);
+            }
+        }
+        for z in -20..=20 {
+            world.set(2, 1, z, TestBlock::Stone);
+            world.set(2, 2, z, TestBlock::Stone);
+        }
+        let (start, goal) = (BlockPos::new(0, 1, 0), BlockPos::new(4, 1, 0));
+        let mut settings = PathfinderSettings::default();
+        assert_eq!(find_path(&world, start, &GoalBlock(goal), &settings), None);
+
+        settings.allow_break = true;
+        let path = search(&world, start, goal, &settings).unwrap();
+        assert!(movements(&path).contains(&Movement::Break(vec![BlockPos::new(2, 1, 0), BlockPos::new(2, 2, 0)])));
+
+        world.set(2, 1, 0, TestBlock::Bedrock);
+        let path = search(&world, start, goal, &settings).unwrap();
+        assert!(!movements(&path).contains(&Movement::Break(vec![BlockPos::new(2, 1, 0), BlockPos::new(2, 2, 0)])));
+        for z in -20..=20 {
+            world.set(2, 1, z, TestBlock::Bedrock);
+        }
+        assert_eq!(find_path(&world, start, &GoalBlock(goal), &settings), None);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,126 @@
+use std::fmt::Debug;
+
+use miners_protocol::utils::location::BlockPos;
+
+use crate::entities::Entities;
+
+/// Goal of the pathfinder
+///
+/// Custom goals can be created by implementing this trait
+pub trait Goal: Debug + Send + Sync {
+    /// Whether the player standing at `pos` (feet position) reached the goal
+    fn is_reached(&self, pos: BlockPos) -> bool;
+    /// Estimated cost of reaching the goal from `pos` (should not overestimate it)
+    fn heuristic(&self, pos: BlockPos) -> f64;
+    /// Updates goal which depends on other state (e.g. followed entity), returns whether it changed
+    fn update(&mut self, _entities: &Entities) -> bool {
+        false
+    }
+    /// Whether the goal is known (the pathfinder waits until it is, e.g. for followed entity to be spawned)
+    fn is_ready(&self) -> bool {
+        true
+    }
+    /// Whether the pathfinder keeps the goal after reaching it (e.g. following entity)
+    fn is_persistent(&self) -> bool {
+        false
+    }
+}
+
+/// Goal of standing in the specified block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GoalBlock(pub BlockPos);
+
+impl Goal for GoalBlock {
+    fn is_reached(&self, pos: BlockPos) -> bool {
+        pos == self.0
+    }
+
+    fn heuristic(&self, pos: BlockPos) -> f64 {
+        distance(pos, self.0)
+    }
+}
+
+/// Goal of getting within `radius` blocks of the specified block
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GoalNear {
+    pub position: BlockPos,
+    pub radius: f64,
+}
+
+impl Goal for GoalNear {
+    fn is_reached(&self, pos: BlockPos) -> bool {
+        distance(pos, self.position) <= self.radius
+    }
+
+    fn heuristic(&self, pos: BlockPos) -> f64 {
+        (distance(pos, self.position) - self.radius).max(0.0)
+    }
+}
+
+/// Goal of reaching the specified column (at any height)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GoalXZ {
+    pub x: i32,
+    pub z: i32,
+}
+
+impl Goal for GoalXZ {
+    fn is_reached(&self, pos: BlockPos) -> bool {
+        pos.x == self.x && pos.z == self.z
+    }
+
+    fn heuristic(&self, pos: BlockPos) -> f64 {
+        ((pos.x - self.x) as f64).hypot((pos.z - self.z) as f64)
+    }
+}
+
+/// Goal of staying within `radius` blocks of the entity (the path is recomputed when it moves)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GoalFollowEntity {
+    /// Network id of the followed entity
+    pub entity_id: i32,
+    pub radius: f64,
+    /// Last known position of the entity (updated automatically, `None` until the entity is known)
+    pub target: Option<BlockPos>,
+}
+
+impl GoalFollowEntity {
+    pub fn new(entity_id: i32, radius: f64) -> GoalFollowEntity {
+        GoalFollowEntity {
+            entity_id,
+            radius,
+            target: None,
+        }
+    }
+}
+
+impl Goal for GoalFollowEntity {
+    fn is_reached(&self, pos: BlockPos) -> bool {
+        self.target.is_some_and(|target| distance(pos, target) <= self.radius)
+    }
+
+    fn heuristic(&self, pos: BlockPos) -> f64 {
+        self.target.map_or(0.0, |target| (distance(pos, target) - self.radius).max(0.0))
+    }
+
+    fn update(&mut self, entities: &Entities) -> bool {
+        let Some(entity) = entities.get(self.entity_id) else { return false };
+        let target = Some(BlockPos::containing(entity.position));
+        let changed = target != self.target;
+        self.target = target;
+        changed
+    }
+
+    fn is_ready(&self) -> bool {
+        self.target.is_some()
+    }
+
+    fn is_persistent(&self) -> bool {
+        true
+    }
+}
+
+fn distance(a: BlockPos, b: BlockPos) -> f64 {
+    let (dx, dy, dz) = ((a.x - b.x) as f64, (a.y - b.y) as f64, (a.z - b.z) as f64);
+    (dx * dx + dy * dy + dz * dz).sqrt()
+}
\ No newline at end of file
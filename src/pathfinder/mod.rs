@@ -0,0 +1,307 @@
+//! A* pathfinder which drives movement controls to reach goals (see [`goals`])
+
+use std::collections::VecDeque;
+
+use miners_protocol::{packet::{IntoPacket, RawPacket}, utils::location::{BlockPos, Vec3}};
+
+use crate::{define_events, define_non_arg_events, entities::Entities, physics::{Controls, Fluid, PhysicsWorld, Tool}, player::{PlayerState, rotation_towards}};
+
+use self::{astar::{PathNode, Movement}, goals::Goal};
+
+pub mod astar;
+pub mod goals;
+
+/// Options of the pathfinder
+#[derive(Debug, Clone)]
+pub struct PathfinderSettings {
+    /// Maximum number of nodes visited before giving up
+    pub max_nodes: usize,
+    pub allow_sprint: bool,
+    /// Whether blocks in the way can be broken
+    pub allow_break: bool,
+    /// Whether blocks can be placed to bridge gaps (the player must hold a block in its main hand)
+    pub allow_place: bool,
+    /// Additional cost of breaking a block (in walked blocks)
+    pub break_cost: f64,
+    /// Additional cost of placing a block (in walked blocks)
+    pub place_cost: f64,
+    /// Tool held in the main hand while breaking blocks (`None` for bare hand). The client doesn't track
+    /// the inventory, so it must match the held item, otherwise the server rejects blocks broken too fast
+    pub tool: Option<Tool>,
+}
+
+impl Default for PathfinderSettings {
+    fn default() -> Self {
+        PathfinderSettings {
+            max_nodes: 10000,
+            allow_sprint: true,
+            allow_break: false,
+            allow_place: false,
+            break_cost: 4.0,
+            place_cost: 2.0,
+            tool: None,
+        }
+    }
+}
+
+define_events!(
+    PathFoundEvent (path: Vec<PathNode>) => "Event emitted when the pathfinder finds path to the goal",
+    PathFailedEvent (reason: String) => "Event emitted when the pathfinder can't find path to the goal (the goal is dropped)"
+);
+define_non_arg_events!(GoalReachedEvent => "Event emitted when the player reaches the goal");
+
+/// Result of a single pathfinder tick which must be handled by the client
+#[derive(Debug, Clone)]
+pub enum PathfinderAction {
+    PathFound(Vec<PathNode>),
+    PathFailed(String),
+    GoalReached,
+    StartDigging(BlockPos),
+    FinishDigging(BlockPos),
+    /// Start digging the block which is broken in a single tick (finishing isn't sent)
+    BreakInstantly(BlockPos),
+    /// Place block against `against` on its `face`
+    Place { against: BlockPos, face: BlockFace },
+}
+
+/// Face of the block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockFace {
+    Bottom = 0,
+    Top = 1,
+    North = 2,
+    South = 3,
+    West = 4,
+    East = 5,
+}
+
+/// Pathfinder state (current goal and path)
+#[derive(Debug, Default)]
+pub struct Pathfinder {
+    pub settings: PathfinderSettings,
+    goal: Option<Box<dyn Goal>>,
+    path: VecDeque<PathNode>,
+    needs_path: bool,
+    reached: bool,
+    /// Block being broken with its progress (it's broken at 1.0)
+    digging: Option<(BlockPos, f32)>,
+    /// Block being placed with remaining ticks before placing it again (if the server didn't place it)
+    placing: Option<(BlockPos, u32)>,
+    /// Position used for detecting that the player got stuck
+    last_progress: (Vec3, u32),
+}
+
+impl Pathfinder {
+    /// Sets new goal (path is computed in the next tick)
+    pub fn set_goal(&mut self, goal: Box<dyn Goal>) {
+        self.goal = Some(goal);
+        self.path.clear();
+        self.needs_path = true;
+        self.reached = false;
+        self.digging = None;
+        self.placing = None;
+    }
+
+    /// Stops moving towards the goal
+    pub fn stop(&mut self) {
+        self.goal = None;
+        self.path.clear();
+        self.digging = None;
+        self.placing = None;
+    }
+
+    pub fn goal(&self) -> Option<&dyn Goal> {
+        self.goal.as_deref()
+    }
+
+    /// Remaining nodes of the current path
+    pub fn path(&self) -> &VecDeque<PathNode> {
+        &self.path
+    }
+
+    /// Updates controls to follow the path (called every tick before physics)
+    pub fn tick(&mut self, player: &mut PlayerState, controls: &mut Controls, world: &impl PhysicsWorld, entities: &Entities) -> Vec<PathfinderAction> {
+        let mut actions = Vec::new();
+        let Some(goal) = self.goal.as_mut() else { return actions };
+        if !player.position_known {
+            return actions;
+        }
+
+        if goal.update(entities) {
+            self.needs_path = true;
+        }
+        if !goal.is_ready() {
+            controls.clear();
+            self.path.clear();
+            self.needs_path = true;
+            return actions;
+        }
+
+        // Slightly above feet, so standing on slabs and soul sand counts as standing in the block above them
+        let current = BlockPos::containing(player.position + Vec3::new(0.0, 0.1, 0.0));
+        if goal.is_reached(current) && (player.on_ground || player.velocity.y == 0.0) {
+            controls.clear();
+            self.path.clear();
+            if !self.reached {
+                self.reached = true;
+                actions.push(PathfinderAction::GoalReached);
+            }
+            if !goal.is_persistent() {
+                self.goal = None;
+            }
+            return actions;
+        }
+        self.reached = false;
+
+        if self.needs_path || self.path.is_empty() {
+            self.needs_path = false;
+            self.last_progress = (player.position, 0);
+            match astar::find_path(world, current, goal.as_ref(), &self.settings) {
+                Some(path) => {
+                    actions.push(PathfinderAction::PathFound(path.clone()));
+                    self.path = path.into_iter().skip(1).collect();
+                },
+                None => {
+                    actions.push(PathfinderAction::PathFailed(format!("No path to {:?} found", goal)));
+                    controls.clear();
+                    self.stop();
+                    return actions;
+                }
+            }
+        }
+
+        // Eyes in a water block count as in water even above its surface, so blocks are never broken too fast
+        let eyes_in_water = world.fluid(BlockPos::containing(player.eye_position())) == Some(Fluid::Water);
+
+        // Finish breaking the block (progress is added every tick after starting, like in vanilla)
+        if let Some((block, progress)) = self.digging {
+            controls.clear();
+            (player.yaw, player.pitch) = rotation_towards(player.eye_position(), block.bottom_center() + Vec3::new(0.0, 0.5, 0.0));
+            let progress = progress + world.destroy_progress(block, self.settings.tool.as_ref(), eyes_in_water, player.on_ground);
+            if progress >= 1.0 {
+                actions.push(PathfinderAction::FinishDigging(block));
+                self.digging = None;
+            } else {
+                self.digging = Some((block, progress));
+            }
+            return actions;
+        }
+
+        let Some(node) = self.path.front().cloned() else { return actions };
+        match &node.movement {
+            Movement::Break(blocks) => {
+                if let Some(block) = blocks.iter().find(|block| !world.collision_boxes(**block).is_empty()) {
+                    controls.clear();
+                    let progress = world.destroy_progress(*block, self.settings.tool.as_ref(), eyes_in_water, player.on_ground);
+                    if progress >= 1.0 {
+                        actions.push(PathfinderAction::BreakInstantly(*block));
+                    } else if progress > 0.0 {
+                        actions.push(PathfinderAction::StartDigging(*block));
+                        self.digging = Some((*block, 0.0));
+                    } else {
+                        // The block can't be broken anymore (e.g. it was replaced)
+                        self.needs_path = true;
+                    }
+                    return actions;
+                }
+            },
+            Movement::Place(block) if world.collision_boxes(*block).is_empty() => {
+                // Wait for the server to place the block (it's placed again if the server rejected it)
+                controls.clear();
+                controls.sneak = true;
+                match &mut self.placing {
+                    Some((placing, ticks)) if placing == block && *ticks > 0 => *ticks -= 1,
+                    _ => {
+                        let below = current.offset(0, -1, 0);
+                        let face = match (block.x - below.x, block.z - below.z) {
+                            (1, _) => BlockFace::East,
+                            (-1, _) => BlockFace::West,
+                            (_, 1) => BlockFace::South,
+                            _ => BlockFace::North,
+                        };
+                        actions.push(PathfinderAction::Place { against: below, face });
+                        self.placing = Some((*block, 10));
+                    }
+                }
+                return actions;
+            },
+            _ => self.placing = None,
+        }
+
+        // Move to the next node
+        let target = node.position.bottom_center();
+        let horizontal_distance = player.position.horizontal_distance(target);
+        if horizontal_distance < 0.35 && (player.position.y - target.y).abs() < 0.6 {
+            self.path.pop_front();
+            self.last_progress = (player.position, 0);
+            return actions;
+        }
+
+        let (yaw, _) = rotation_towards(player.position, target);
+        player.yaw = yaw;
+        controls.forward = horizontal_distance >= 0.2;
+        controls.jump = node.position.y > current.y && matches!(node.movement, Movement::Jump | Movement::Climb);
+        controls.sprint = self.settings.allow_sprint && self.path.len() > 2 && matches!(node.movement, Movement::Walk);
+        controls.sneak = matches!(node.movement, Movement::Place(_));
+
+        // Recompute path if the player didn't make progress for 2 seconds
+        let (position, ticks) = &mut self.last_progress;
+        if player.position.distance_squared(*position) > 0.25 {
+            self.last_progress = (player.position, 0);
+        } else {
+            *ticks += 1;
+            if *ticks > 40 {
+                log::debug!(target: "miners-client", "Player got stuck, recomputing path");
+                self.needs_path = true;
+            }
+        }
+        actions
+    }
+}
+
+/// Player action packet (0x1D) used for breaking blocks
+pub struct PlayerActionPacket {
+    /// 0 - started digging, 1 - cancelled digging, 2 - finished digging
+    pub status: i32,
+    pub position: BlockPos,
+    pub face: BlockFace,
+    pub sequence: i32,
+}
+
+impl IntoPacket for PlayerActionPacket {
+    fn into_packet(self, _protocol_version: i32) -> RawPacket {
+        let mut packet = RawPacket::empty(0x1D);
+        packet.write_varint(self.status);
+        packet.write_long(self.position.to_long());
+        packet.write_byte(self.face as u8);
+        packet.write_varint(self.sequence);
+        packet
+    }
+}
+
+/// Use item on packet (0x31) used for placing blocks
+pub struct UseItemOnPacket {
+    /// 0 - main hand, 1 - off hand
+    pub hand: i32,
+    pub position: BlockPos,
+    pub face: BlockFace,
+    /// Position of the crosshair on the block face (0 - 1)
+    pub cursor: Vec3,
+    pub inside_block: bool,
+    pub sequence: i32,
+}
+
+impl IntoPacket for UseItemOnPacket {
+    fn into_packet(self, _protocol_version: i32) -> RawPacket {
+        let mut packet = RawPacket::empty(0x31);
+        packet.write_varint(self.hand);
+        packet.write_long(self.position.to_long());
+        packet.write_varint(self.face as i32);
+        packet.write_float(self.cursor.x as f32);
+        packet.write_float(self.cursor.y as f32);
+        packet.write_float(self.cursor.z as f32);
+        packet.write_bool(self.inside_block);
+        packet.write_varint(self.sequence);
+        packet
+    }
+}
\ No newline at end of file
@@ -0,0 +1,96 @@
+use miners_protocol::utils::location::Vec3;
+
+/// Tolerance used when comparing box edges (same as vanilla)
+const EPSILON: f64 = 1.0e-7;
+
+/// Axis-aligned bounding box
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Aabb {
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+impl Aabb {
+    pub fn new(min: Vec3, max: Vec3) -> Aabb {
+        Aabb { min, max }
+    }
+
+    /// Box of a full block (relative to block origin)
+    pub const FULL_BLOCK: Aabb = Aabb {
+        min: Vec3 { x: 0.0, y: 0.0, z: 0.0 },
+        max: Vec3 { x: 1.0, y: 1.0, z: 1.0 },
+    };
+
+    /// Box of entity with specified width and height standing at `position` (center of the bottom face)
+    pub fn from_feet(position: Vec3, width: f64, height: f64) -> Aabb {
+        let half = width / 2.0;
+        Aabb {
+            min: Vec3::new(position.x - half, position.y, position.z - half),
+            max: Vec3::new(position.x + half, position.y + height, position.z + half),
+        }
+    }
+
+    /// Returns box moved by `offset`
+    pub fn offset(&self, offset: Vec3) -> Aabb {
+        Aabb::new(self.min + offset, self.max + offset)
+    }
+
+    /// Returns box extended in the direction of `delta` (covers the whole movement)
+    pub fn expand_towards(&self, delta: Vec3) -> Aabb {
+        let mut result = *self;
+        if delta.x < 0.0 { result.min.x += delta.x } else { result.max.x += delta.x }
+        if delta.y < 0.0 { result.min.y += delta.y } else { result.max.y += delta.y }
+        if delta.z < 0.0 { result.min.z += delta.z } else { result.max.z += delta.z }
+        result
+    }
+
+    /// Returns box grown by `amount` in every direction (negative amount shrinks it)
+    pub fn inflate(&self, amount: f64) -> Aabb {
+        let amount = Vec3::new(amount, amount, amount);
+        Aabb::new(self.min - amount, self.max + amount)
+    }
+
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        self.min.x < other.max.x && self.max.x > other.min.x
+            && self.min.y < other.max.y && self.max.y > other.min.y
+            && self.min.z < other.max.z && self.max.z > other.min.z
+    }
+
+    /// Limits movement along X axis so this box doesn't enter `other`
+    pub fn clip_x(&self, other: &Aabb, dx: f64) -> f64 {
+        if !overlaps(self.min.y, self.max.y, other.min.y, other.max.y) || !overlaps(self.min.z, self.max.z, other.min.z, other.max.z) {
+            return dx;
+        }
+        clip(self.min.x, self.max.x, other.min.x, other.max.x, dx)
+    }
+
+    /// Limits movement along Y axis so this box doesn't enter `other`
+    pub fn clip_y(&self, other: &Aabb, dy: f64) -> f64 {
+        if !overlaps(self.min.x, self.max.x, other.min.x, other.max.x) || !overlaps(self.min.z, self.max.z, other.min.z, other.max.z) {
+            return dy;
+        }
+        clip(self.min.y, self.max.y, other.min.y, other.max.y, dy)
+    }
+
+    /// Limits movement along Z axis so this box doesn't enter `other`
+    pub fn clip_z(&self, other: &Aabb, dz: f64) -> f64 {
+        if !overlaps(self.min.x, self.max.x, other.min.x, other.max.x) || !overlaps(self.min.y, self.max.y, other.min.y, other.max.y) {
+            return dz;
+        }
+        clip(self.min.z, self.max.z, other.min.z, other.max.z, dz)
+    }
+}
+
+fn overlaps(min: f64, max: f64, other_min: f64, other_max: f64) -> bool {
+    max - EPSILON > other_min && min + EPSILON < other_max
+}
+
+fn clip(min: f64, max: f64, other_min: f64, other_max: f64, delta: f64) -> f64 {
+    if delta > 0.0 && other_min >= max - EPSILON {
+        delta.min(other_min - max)
+    } else if delta < 0.0 && other_max <= min + EPSILON {
+        delta.max(other_max - min)
+    } else {
+        delta
+    }
+}
\ No newline at end of file
@@ -0,0 +1,445 @@
+//! Simulation of vanilla player movement (gravity, drag, collisions, jumping, swimming)
+//!
+//! Physics runs against any [`PhysicsWorld`], so it doesn't depend on how blocks are stored
+
+use miners_data::{ToolKind, ToolTier};
+use miners_protocol::utils::location::{BlockPos, Vec3};
+
+use crate::player::PlayerState;
+
+use self::aabb::Aabb;
+
+pub mod aabb;
+
+pub const PLAYER_WIDTH: f64 = 0.6;
+pub const PLAYER_HEIGHT: f64 = 1.8;
+pub const PLAYER_SNEAKING_HEIGHT: f64 = 1.5;
+/// Maximum height of the block player can walk onto without jumping
+pub const STEP_HEIGHT: f64 = 0.6;
+pub const GRAVITY: f64 = 0.08;
+/// Velocity set when jumping
+pub const JUMP_VELOCITY: f64 = 0.42;
+/// Base movement speed of the player (`generic.movement_speed` attribute)
+pub const WALK_SPEED: f64 = 0.1;
+/// Friction of most blocks (ice and slime are slipperier)
+pub const DEFAULT_FRICTION: f64 = 0.6;
+
+/// Fluid occupying the block
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Fluid {
+    Water,
+    Lava,
+}
+
+/// Tool held by the player while breaking blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tool {
+    pub kind: ToolKind,
+    pub tier: ToolTier,
+    /// Level of the efficiency enchantment (0 if the tool isn't enchanted)
+    pub efficiency: u8,
+}
+
+/// World which physics is simulated in
+///
+/// Collision boxes are relative to the block origin (e.g. [`Aabb::FULL_BLOCK`] for stone)
+pub trait PhysicsWorld {
+    /// Whether the chunk containing the block is loaded (player doesn't move in unloaded chunks)
+    fn is_loaded(&self, pos: BlockPos) -> bool;
+    /// Collision boxes of the block (empty for air and other passable blocks)
+    fn collision_boxes(&self, pos: BlockPos) -> Vec<Aabb>;
+    /// Fluid in the block
+    fn fluid(&self, _pos: BlockPos) -> Option<Fluid> {
+        None
+    }
+    /// Friction of the block (player walking on it slides more if it's higher)
+    fn friction(&self, _pos: BlockPos) -> f64 {
+        DEFAULT_FRICTION
+    }
+    /// Whether the block can be climbed (e.g. ladder or vines)
+    fn is_climbable(&self, _pos: BlockPos) -> bool {
+        false
+    }
+    /// Multiplier of the horizontal velocity of the player in or on the block (e.g. soul sand),
+    /// `None` if the block doesn't change it (the block below the player is used instead)
+    fn speed_factor(&self, _pos: BlockPos) -> Option<f64> {
+        None
+    }
+    /// Multiplier of the jump velocity of the player in or on the block (e.g. honey)
+    fn jump_factor(&self, _pos: BlockPos) -> f64 {
+        1.0
+    }
+    /// Progress of breaking the block in a single tick (vanilla `getDestroyProgress`, the block is broken when it reaches 1.0),
+    /// 0.0 if the block can't be broken or the time needed to break it is unknown
+    fn destroy_progress(&self, _pos: BlockPos, _tool: Option<&Tool>, _eyes_in_water: bool, _on_ground: bool) -> f32 {
+        0.0
+    }
+}
+
+/// World without any loaded chunks (player stays in place)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EmptyWorld;
+
+impl PhysicsWorld for EmptyWorld {
+    fn is_loaded(&self, _pos: BlockPos) -> bool {
+        false
+    }
+
+    fn collision_boxes(&self, _pos: BlockPos) -> Vec<Aabb> {
+        Vec::new()
+    }
+}
+
+/// Movement controls (keys pressed by the player)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Controls {
+    pub forward: bool,
+    pub back: bool,
+    pub left: bool,
+    pub right: bool,
+    pub jump: bool,
+    pub sprint: bool,
+    pub sneak: bool,
+}
+
+impl Controls {
+    /// Releases all keys
+    pub fn clear(&mut self) {
+        *self = Controls::default();
+    }
+}
+
+/// Simulates single tick of player movement (same as vanilla client)
+pub fn simulate(player: &mut PlayerState, controls: &Controls, world: &impl PhysicsWorld) {
+    if !player.position_known || !world.is_loaded(BlockPos::containing(player.position)) {
+        return;
+    }
+
+    // Very small velocities are zeroed
+    for v in [&mut player.velocity.x, &mut player.velocity.y, &mut player.velocity.z] {
+        if v.abs() < 0.003 {
+            *v = 0.0;
+        }
+    }
+
+    // Movement input
+    let mut forward = controls.forward as i8 as f64 - controls.back as i8 as f64;
+    let mut strafe = controls.left as i8 as f64 - controls.right as i8 as f64;
+    if controls.sneak {
+        forward *= 0.3;
+        strafe *= 0.3;
+    }
+    player.sneaking = controls.sneak;
+    player.sprinting = controls.sprint && forward > 0.0 && !controls.sneak && !(player.sprinting && player.horizontal_collision);
+    let input = Vec3::new(strafe * 0.98, 0.0, forward * 0.98);
+
+    let in_water = is_in_fluid(player, world, Fluid::Water);
+    let in_lava = is_in_fluid(player, world, Fluid::Lava);
+
+    // Jumping
+    if controls.jump {
+        if in_water || in_lava {
+            player.velocity.y += 0.04;
+        } else if player.on_ground && player.jump_ticks == 0 {
+            player.velocity.y = JUMP_VELOCITY * jump_factor(player, world);
+            if player.sprinting {
+                let yaw = (player.yaw as f64).to_radians();
+                player.velocity.x -= yaw.sin() * 0.2;
+                player.velocity.z += yaw.cos() * 0.2;
+            }
+            player.jump_ticks = 10;
+        }
+    } else {
+        player.jump_ticks = 0;
+    }
+    player.jump_ticks = player.jump_ticks.saturating_sub(1);
+
+    let climbing = world.is_climbable(BlockPos::containing(player.position));
+    if in_water {
+        let drag = if player.sprinting { 0.9 } else { 0.8 };
+        let start_y = player.position.y;
+        move_relative(player, input, 0.02);
+        move_player(player, controls, world);
+        if player.horizontal_collision && climbing {
+            player.velocity.y = 0.2;
+        }
+        player.velocity = Vec3::new(player.velocity.x * drag, player.velocity.y * 0.8, player.velocity.z * drag);
+        if !player.sprinting {
+            player.velocity.y -= GRAVITY / 16.0;
+        }
+        escape_fluid(player, world, start_y);
+    } else if in_lava {
+        let start_y = player.position.y;
+        move_relative(player, input, 0.02);
+        move_player(player, controls, world);
+        player.velocity = player.velocity * 0.5;
+        player.velocity.y -= GRAVITY / 4.0;
+        escape_fluid(player, world, start_y);
+    } else {
+        // Friction of the block below the player
+        let friction = if player.on_ground { world.friction(block_below(player)) } else { 1.0 };
+        let speed = if player.on_ground {
+            let walk_speed = if player.sprinting { WALK_SPEED * 1.3 } else { WALK_SPEED };
+            walk_speed * (0.21600002 / (friction * friction * friction))
+        } else if player.sprinting {
+            0.026
+        } else {
+            0.02
+        };
+
+        move_relative(player, input, speed);
+        if climbing {
+            player.velocity.x = player.velocity.x.clamp(-0.15, 0.15);
+            player.velocity.z = player.velocity.z.clamp(-0.15, 0.15);
+            player.velocity.y = player.velocity.y.max(-0.15);
+            if controls.sneak && player.velocity.y < 0.0 {
+                player.velocity.y = 0.0; // Holding onto the ladder
+            }
+        }
+        move_player(player, controls, world);
+        if climbing && (player.horizontal_collision || controls.jump) {
+            player.velocity.y = 0.2;
+        }
+
+        let drag = friction * 0.91;
+        player.velocity.y = (player.velocity.y - GRAVITY) * 0.98;
+        player.velocity.x *= drag;
+        player.velocity.z *= drag;
+    }
+}
+
+/// Returns bounding box of the player at its current position
+pub fn player_box(player: &PlayerState) -> Aabb {
+    let height = if player.sneaking { PLAYER_SNEAKING_HEIGHT } else { PLAYER_HEIGHT };
+    Aabb::from_feet(player.position, PLAYER_WIDTH, height)
+}
+
+/// Whether the box doesn't collide with any block
+pub fn is_space_empty(world: &impl PhysicsWorld, area: &Aabb) -> bool {
+    collision_boxes(world, area).iter().all(|block| !block.intersects(area))
+}
+
+fn is_in_fluid(player: &PlayerState, world: &impl PhysicsWorld, fluid: Fluid) -> bool {
+    blocks_in(&player_box(player).inflate(-0.001)).any(|pos| world.fluid(pos) == Some(fluid))
+}
+
+/// Jumps out of fluid when player swims against a wall which can be climbed onto
+fn escape_fluid(player: &mut PlayerState, world: &impl PhysicsWorld, start_y: f64) {
+    let offset = Vec3::new(player.velocity.x, player.velocity.y + 0.6 - player.position.y + start_y, player.velocity.z);
+    let area = player_box(player).offset(offset);
+    if player.horizontal_collision && is_space_empty(world, &area) && !blocks_in(&area).any(|pos| world.fluid(pos).is_some()) {
+        player.velocity.y = 0.3;
+    }
+}
+
+/// Adds movement input (rotated by player's yaw) to the velocity
+fn move_relative(player: &mut PlayerState, input: Vec3, speed: f64) {
+    let length_squared = input.length_squared();
+    if length_squared < 1.0e-7 {
+        return;
+    }
+    let input = if length_squared > 1.0 { input * (1.0 / length_squared.sqrt()) } else { input } * speed;
+    let yaw = (player.yaw as f64).to_radians();
+    let (sin, cos) = (yaw.sin(), yaw.cos());
+    player.velocity.x += input.x * cos - input.z * sin;
+    player.velocity.z += input.z * cos + input.x * sin;
+}
+
+/// Moves player by its velocity, resolving collisions (with step-up and sneaking edge protection)
+fn move_player(player: &mut PlayerState, controls: &Controls, world: &impl PhysicsWorld) {
+    let bounding_box = player_box(player);
+    let mut delta = player.velocity;
+
+    // Sneaking players don't fall off edges
+    if controls.sneak && player.on_ground && delta.y <= 0.0 {
+        delta = back_off_from_edge(world, &bounding_box, delta);
+    }
+
+    let mut collided = collide(world, &bounding_box, delta);
+
+    // Step up onto blocks (e.g. slabs and stairs)
+    let horizontal_collision = collided.x != delta.x || collided.z != delta.z;
+    if horizontal_collision && (player.on_ground || (collided.y != delta.y && delta.y < 0.0)) {
+        let mut stepped = collide(world, &bounding_box, Vec3::new(delta.x, STEP_HEIGHT, delta.z));
+        let up = collide(world, &bounding_box.expand_towards(Vec3::new(delta.x, 0.0, delta.z)), Vec3::new(0.0, STEP_HEIGHT, 0.0));
+        if up.y < STEP_HEIGHT {
+            let horizontal = collide(world, &bounding_box.offset(up), Vec3::new(delta.x, 0.0, delta.z)) + up;
+            if horizontal_length_squared(horizontal) > horizontal_length_squared(stepped) {
+                stepped = horizontal;
+            }
+        }
+        if horizontal_length_squared(stepped) > horizontal_length_squared(collided) {
+            let down = collide(world, &bounding_box.offset(stepped), Vec3::new(0.0, -stepped.y + delta.y, 0.0));
+            collided = stepped + down;
+        }
+    }
+
+    player.position = player.position + collided;
+
+    let collided_x = collided.x != delta.x;
+    let collided_z = collided.z != delta.z;
+    let collided_y = collided.y != delta.y;
+    player.horizontal_collision = collided_x || collided_z;
+    player.on_ground = collided_y && delta.y < 0.0;
+
+    if collided_x {
+        player.velocity.x = 0.0;
+    }
+    if collided_z {
+        player.velocity.z = 0.0;
+    }
+    if collided_y {
+        player.velocity.y = 0.0;
+    }
+
+    // Blocks like soul sand slow the player down
+    let speed_factor = world
+        .speed_factor(BlockPos::containing(player.position))
+        .or_else(|| world.speed_factor(block_below(player)))
+        .unwrap_or(1.0);
+    player.velocity.x *= speed_factor;
+    player.velocity.z *= speed_factor;
+}
+
+/// Returns jump factor of the block at player's feet or the block below if the block at feet doesn't change it
+fn jump_factor(player: &PlayerState, world: &impl PhysicsWorld) -> f64 {
+    let factor = world.jump_factor(BlockPos::containing(player.position));
+    if factor == 1.0 { world.jump_factor(block_below(player)) } else { factor }
+}
+
+/// Returns position of the block which affects player's movement (friction, speed and jump factors)
+fn block_below(player: &PlayerState) -> BlockPos {
+    BlockPos::containing(player.position - Vec3::new(0.0, 0.5000001, 0.0))
+}
+
+/// Reduces horizontal movement so the player doesn't leave the edge of the block
+fn back_off_from_edge(world: &impl PhysicsWorld, bounding_box: &Aabb, delta: Vec3) -> Vec3 {
+    let has_ground = |x: f64, z: f64| !is_space_empty(world, &bounding_box.offset(Vec3::new(x, -STEP_HEIGHT, z)));
+    let reduce = |v: f64| if v.abs() < 0.05 { 0.0 } else { v - 0.05 * v.signum() };
+
+    let (mut x, mut z) = (delta.x, delta.z);
+    while x != 0.0 && !has_ground(x, 0.0) {
+        x = reduce(x);
+    }
+    while z != 0.0 && !has_ground(0.0, z) {
+        z = reduce(z);
+    }
+    while x != 0.0 && z != 0.0 && !has_ground(x, z) {
+        x = reduce(x);
+        z = reduce(z);
+    }
+    Vec3::new(x, delta.y, z)
+}
+
+/// Returns movement limited by collisions with blocks (Y axis first, then the larger of X and Z)
+fn collide(world: &impl PhysicsWorld, bounding_box: &Aabb, delta: Vec3) -> Vec3 {
+    let blocks = collision_boxes(world, &bounding_box.expand_towards(delta));
+    let mut current = *bounding_box;
+    let mut result = delta;
+
+    result.y = blocks.iter().fold(result.y, |dy, block| current.clip_y(block, dy));
+    current = current.offset(Vec3::new(0.0, result.y, 0.0));
+
+    if result.x.abs() < result.z.abs() {
+        result.z = blocks.iter().fold(result.z, |dz, block| current.clip_z(block, dz));
+        current = current.offset(Vec3::new(0.0, 0.0, result.z));
+        result.x = blocks.iter().fold(result.x, |dx, block| current.clip_x(block, dx));
+    } else {
+        result.x = blocks.iter().fold(result.x, |dx, block| current.clip_x(block, dx));
+        current = current.offset(Vec3::new(result.x, 0.0, 0.0));
+        result.z = blocks.iter().fold(result.z, |dz, block| current.clip_z(block, dz));
+    }
+    result
+}
+
+/// Returns collision boxes (in world coordinates) of all blocks which may intersect the area
+fn collision_boxes(world: &impl PhysicsWorld, area: &Aabb) -> Vec<Aabb> {
+    // One extra block below, as some blocks are higher than one block (e.g. fences)
+    let area = Aabb::new(area.min - Vec3::new(0.0, 1.0, 0.0), area.max);
+    blocks_in(&area)
+        .flat_map(|pos| {
+            let origin = Vec3::new(pos.x as f64, pos.y as f64, pos.z as f64);
+            world.collision_boxes(pos).into_iter().map(move |block| block.offset(origin))
+        })
+        .collect()
+}
+
+/// Returns positions of all blocks intersecting the area
+fn blocks_in(area: &Aabb) -> impl Iterator<Item = BlockPos> {
+    let min = BlockPos::containing(area.min);
+    let max = BlockPos::containing(area.max);
+    (min.x..=max.x).flat_map(move |x| (min.y..=max.y).flat_map(move |y| (min.z..=max.z).map(move |z| BlockPos::new(x, y, z))))
+}
+
+fn horizontal_length_squared(v: Vec3) -> f64 {
+    v.x * v.x + v.z * v.z
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Floor of the same blocks below Y 64
+    struct Floor {
+        speed_factor: Option<f64>,
+        jump_factor: f64,
+    }
+
+    impl PhysicsWorld for Floor {
+        fn is_loaded(&self, _pos: BlockPos) -> bool {
+            true
+        }
+
+        fn collision_boxes(&self, pos: BlockPos) -> Vec<Aabb> {
+            if pos.y < 64 { vec![Aabb::FULL_BLOCK] } else { Vec::new() }
+        }
+
+        fn speed_factor(&self, pos: BlockPos) -> Option<f64> {
+            if pos.y < 64 { self.speed_factor } else { None }
+        }
+
+        fn jump_factor(&self, pos: BlockPos) -> f64 {
+            if pos.y < 64 { self.jump_factor } else { 1.0 }
+        }
+    }
+
+    fn standing_player() -> PlayerState {
+        let mut player = PlayerState::default();
+        player.position = Vec3::new(0.5, 64.0, 0.5);
+        player.position_known = true;
+        player.on_ground = true;
+        player
+    }
+
+    fn walked_distance(world: &Floor) -> f64 {
+        let mut player = standing_player();
+        let controls = Controls { forward: true, ..Default::default() };
+        for _ in 0..20 {
+            simulate(&mut player, &controls, world);
+        }
+        assert_eq!(player.position.y, 64.0);
+        player.position.z - 0.5
+    }
+
+    fn jump_height(world: &Floor) -> f64 {
+        let mut player = standing_player();
+        simulate(&mut player, &Controls { jump: true, ..Default::default() }, world);
+        player.position.y - 64.0
+    }
+
+    #[test]
+    fn speed_factor() {
+        let stone = walked_distance(&Floor { speed_factor: None, jump_factor: 1.0 });
+        let soul_sand = walked_distance(&Floor { speed_factor: Some(0.4), jump_factor: 1.0 });
+        assert!(stone > 2.0, "{stone}");
+        assert!(soul_sand < stone * 0.65, "{soul_sand} {stone}");
+    }
+
+    #[test]
+    fn jump_factor() {
+        let stone = jump_height(&Floor { speed_factor: None, jump_factor: 1.0 });
+        let honey = jump_height(&Floor { speed_factor: Some(0.4), jump_factor: 0.5 });
+        assert!((stone - JUMP_VELOCITY).abs() < 1e-9, "{stone}");
+        assert!((honey - JUMP_VELOCITY * 0.5).abs() < 1e-9, "{honey}");
+    }
+}
\ No newline at end of file
@@ -0,0 +1,191 @@
+//! State of the client's own player (position, rotation) and packets used to move it
+
+use miners_protocol::{packet::{IntoPacket, RawPacket}, utils::location::Vec3};
+
+/// Height of player's eyes above its feet (when standing)
+pub const EYE_HEIGHT: f64 = 1.62;
+
+/// Position and rotation of the player together with what was last sent to the server
+#[derive(Debug, Clone, Default)]
+pub struct PlayerState {
+    /// Position of player's feet
+    pub position: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub on_ground: bool,
+    /// Whether the server has already sent player's position (the player must not move before that)
+    pub position_known: bool,
+    /// Velocity in blocks per tick
+    pub velocity: Vec3,
+    pub sprinting: bool,
+    pub sneaking: bool,
+    /// Whether the player hit a wall in the last tick
+    pub horizontal_collision: bool,
+    /// Ticks until the player can jump again
+    pub(crate) jump_ticks: u32,
+
+    last_sent_position: Vec3,
+    last_sent_rotation: (f32, f32),
+    last_sent_on_ground: bool,
+    last_sent_sprinting: bool,
+    last_sent_sneaking: bool,
+    /// Ticks since position was last sent (vanilla sends it at least once per second)
+    ticks_since_position: u32,
+}
+
+impl PlayerState {
+    /// Applies position sent by the server (it has already been sent back, so it doesn't need to be sent again)
+    pub(crate) fn teleport(&mut self, position: Vec3, yaw: f32, pitch: f32) {
+        self.position = position;
+        self.yaw = yaw;
+        self.pitch = pitch;
+        self.position_known = true;
+
+        self.last_sent_position = position;
+        self.last_sent_rotation = (yaw, pitch);
+        self.ticks_since_position = 0;
+    }
+
+    /// Returns movement packet which should be sent in this tick (same rules as vanilla)
+    pub(crate) fn movement_packet(&mut self) -> Option<MovementPacket> {
+        if !self.position_known {
+            return None;
+        }
+
+        self.ticks_since_position += 1;
+        let moved = self.position.distance_squared(self.last_sent_position) > 2.0e-4 * 2.0e-4 || self.ticks_since_position >= 20;
+        let rotated = (self.yaw, self.pitch) != self.last_sent_rotation;
+        let on_ground_changed = self.on_ground != self.last_sent_on_ground;
+
+        if moved {
+            self.last_sent_position = self.position;
+            self.ticks_since_position = 0;
+        }
+        if rotated {
+            self.last_sent_rotation = (self.yaw, self.pitch);
+        }
+        self.last_sent_on_ground = self.on_ground;
+
+        let (position, rotation) = (moved.then_some(self.position), rotated.then_some((self.yaw, self.pitch)));
+        if position.is_none() && rotation.is_none() && !on_ground_changed {
+            return None;
+        }
+        Some(MovementPacket {
+            position,
+            rotation,
+            on_ground: self.on_ground,
+        })
+    }
+
+    /// Returns commands which must be sent because sprinting or sneaking state changed
+    pub(crate) fn command_packets(&mut self, entity_id: i32) -> Vec<PlayerCommandPacket> {
+        let mut packets = Vec::new();
+        if self.sneaking != self.last_sent_sneaking {
+            let action = if self.sneaking { PlayerCommand::StartSneaking } else { PlayerCommand::StopSneaking };
+            packets.push(PlayerCommandPacket { entity_id, action, jump_boost: 0 });
+            self.last_sent_sneaking = self.sneaking;
+        }
+        if self.sprinting != self.last_sent_sprinting {
+            let action = if self.sprinting { PlayerCommand::StartSprinting } else { PlayerCommand::StopSprinting };
+            packets.push(PlayerCommandPacket { entity_id, action, jump_boost: 0 });
+            self.last_sent_sprinting = self.sprinting;
+        }
+        packets
+    }
+
+    /// Returns position of player's eyes
+    pub fn eye_position(&self) -> Vec3 {
+        self.position + Vec3::new(0.0, EYE_HEIGHT, 0.0)
+    }
+}
+
+/// Returns yaw and pitch (in degrees) needed to look from `from` at `target`
+pub fn rotation_towards(from: Vec3, target: Vec3) -> (f32, f32) {
+    let delta = target - from;
+    let horizontal = (delta.x * delta.x + delta.z * delta.z).sqrt();
+    let yaw = (-delta.x).atan2(delta.z).to_degrees();
+    let pitch = (-delta.y).atan2(horizontal).to_degrees();
+    (yaw as f32, pitch as f32)
+}
+
+/// Set player position (0x14), position and rotation (0x15), rotation (0x16) and on ground (0x17) packets
+///
+/// Packet id is chosen based on which fields are set
+#[derive(Debug, Clone)]
+pub struct MovementPacket {
+    /// Position of player's feet
+    pub position: Option<Vec3>,
+    /// Yaw and pitch (in degrees)
+    pub rotation: Option<(f32, f32)>,
+    pub on_ground: bool,
+}
+
+impl IntoPacket for MovementPacket {
+    fn into_packet(self, _protocol_version: i32) -> RawPacket {
+        let id = match (self.position, self.rotation) {
+            (Some(_), None) => 0x14,
+            (Some(_), Some(_)) => 0x15,
+            (None, Some(_)) => 0x16,
+            (None, None) => 0x17,
+        };
+        let mut packet = RawPacket::empty(id);
+
+        if let Some(position) = self.position {
+            packet.write_double(position.x);
+            packet.write_double(position.y);
+            packet.write_double(position.z);
+        }
+        if let Some((yaw, pitch)) = self.rotation {
+            packet.write_float(yaw);
+            packet.write_float(pitch);
+        }
+        packet.write_bool(self.on_ground);
+
+        packet
+    }
+}
+
+/// Confirm teleportation packet (0x00), sent in response to synchronize player position packet
+pub struct ConfirmTeleportPacket {
+    pub teleport_id: i32,
+}
+
+impl IntoPacket for ConfirmTeleportPacket {
+    fn into_packet(self, _protocol_version: i32) -> RawPacket {
+        let mut packet = RawPacket::empty(0x00);
+        packet.write_varint(self.teleport_id);
+        packet
+    }
+}
+
+/// Action of player command packet
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlayerCommand {
+    StartSneaking = 0,
+    StopSneaking = 1,
+    LeaveBed = 2,
+    StartSprinting = 3,
+    StopSprinting = 4,
+    StartHorseJump = 5,
+    StopHorseJump = 6,
+    OpenHorseInventory = 7,
+    StartFlyingWithElytra = 8,
+}
+
+/// Player command packet (0x1E)
+pub struct PlayerCommandPacket {
+    pub entity_id: i32,
+    pub action: PlayerCommand,
+    /// Used only when jumping with horse (0 - 100)
+    pub jump_boost: i32,
+}
+
+impl IntoPacket for PlayerCommandPacket {
+    fn into_packet(self, _protocol_version: i32) -> RawPacket {
+        let mut packet = RawPacket::empty(0x1E);
+        packet.write_varint(self.entity_id);
+        packet.write_varint(self.action as i32);
+        packet.write_varint(self.jump_boost);
+        packet
+    }
+}
\ No newline at end of file
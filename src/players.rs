@@ -0,0 +1,79 @@
+use std::collections::HashMap;
+
+use crate::{chat::component::FormattedChatMessage, handlers::player_info::ProfileProperty};
+
+/// Player listed in the tab list
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayerEntry {
+    pub uuid: u128,
+    pub name: String,
+    /// Profile properties (e.g. `textures`)
+    pub properties: Vec<ProfileProperty>,
+    /// Game mode (0 - survival, 1 - creative, 2 - adventure, 3 - spectator)
+    pub gamemode: i32,
+    /// Latency in milliseconds
+    pub latency: i32,
+    /// Name shown in the tab list instead of the username (if set)
+    pub display_name: Option<FormattedChatMessage>,
+}
+
+impl PlayerEntry {
+    /// Returns display name if set, otherwise username
+    pub fn shown_name(&self) -> String {
+        match &self.display_name {
+            Some(display_name) => display_name.to_plain(),
+            None => self.name.clone(),
+        }
+    }
+}
+
+/// List of players on the server (the tab list), kept up to date by `PlayerInfoHandler`
+///
+/// # Example
+/// ```rs
+/// let client = client.rl();
+/// if let Some(player) = client.player_list().get(uuid) {
+///     println!("{} has {}ms ping", player.name, player.latency);
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct PlayerList {
+    pub players: HashMap<u128, PlayerEntry>,
+}
+
+impl PlayerList {
+    /// Returns player with specified UUID
+    pub fn get(&self, uuid: u128) -> Option<&PlayerEntry> {
+        self.players.get(&uuid)
+    }
+
+    /// Returns player with specified username (case insensitive, same as the server)
+    pub fn get_by_name(&self, name: &str) -> Option<&PlayerEntry> {
+        self.players.values().find(|player| player.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns username of player with specified UUID
+    pub fn name_of(&self, uuid: u128) -> Option<&str> {
+        self.get(uuid).map(|player| player.name.as_str())
+    }
+
+    /// Returns iterator over all players (in no particular order)
+    pub fn iter(&self) -> impl Iterator<Item = &PlayerEntry> {
+        self.players.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.players.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.players.is_empty()
+    }
+}
+
+/// Header and footer of the tab list
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TabList {
+    pub header: FormattedChatMessage,
+    pub footer: FormattedChatMessage,
+}
\ No newline at end of file
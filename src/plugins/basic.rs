@@ -2,7 +2,7 @@ use std::time;
 
 use miners_protocol::packet::{IntoPacket, RawPacket};
 
-use crate::client::MinecraftClient;
+use crate::{client::MinecraftClient, chat::signing::MessageAcknowledgment};
 
 /// Basic plugin trait that provides some useful methods for the client
 pub trait BasicPlugin {
@@ -10,8 +10,11 @@ pub trait BasicPlugin {
     fn respawn(&mut self);
     /// Sends chat message to the server with the specified message
     /// 
-    /// **Warning:** This is only temporary and experimental implementation
+    /// Message is signed if the client has player certificates.
+    /// Messages starting with `/` are sent as commands (see [`send_command`](BasicPlugin::send_command))
     fn send_chat_message(&mut self, message: String);
+    /// Sends command to the server (without leading `/`, e.g. `tp foo`)
+    fn send_command(&mut self, command: String);
 }
 
 impl BasicPlugin for MinecraftClient {
@@ -21,11 +24,58 @@ impl BasicPlugin for MinecraftClient {
     }
 
     fn send_chat_message(&mut self, message: String) {
+        // Commands must be sent using separate packet
+        if let Some(command) = message.strip_prefix('/') {
+            log::debug!(target: "miners-client", "Chat message starts with a slash, sending it as command: {}", command);
+            self.send_command(command.to_string());
+            return;
+        }
+
+        let timestamp = time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64;
+        let salt = rand::random::<i64>();
+
+        // Sign message (it uses current last seen messages, so it must be done before acknowledging)
+        let signature = self.chat_session.sign_message(self.uuid, &message, timestamp, salt);
+        let acknowledgment = self.chat_session.acknowledge();
+
         // Send chat message packet
         self.socket.send_packet(ChatMessagePacket {
-            message: message.clone(),
-            timestamp: time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64,
-            // TODO: Implement this properly
+            message,
+            timestamp,
+            salt,
+            signature,
+            acknowledgment,
+        }).ok();
+    }
+
+    fn send_command(&mut self, command: String) {
+        let timestamp = time::UNIX_EPOCH.elapsed().unwrap().as_millis() as u64;
+        let salt = rand::random::<i64>();
+
+        // Sign arguments which contain player's message (e.g. message in `/msg`)
+        let signed_arguments = match self.commands.as_ref().map(|tree| tree.parse(&command)) {
+            Some(Ok(parsed)) => parsed.signed_arguments(),
+            Some(Err(e)) => {
+                log::warn!(target: "miners-client", "Command doesn't match server's command tree: {:?}", e);
+                Vec::new()
+            },
+            None => Vec::new(),
+        };
+        let argument_signatures = signed_arguments.into_iter()
+            .filter_map(|(name, value)| {
+                let signature = self.chat_session.sign_message(self.uuid, &value, timestamp, salt)?;
+                Some((name, signature))
+            })
+            .collect();
+        let acknowledgment = self.chat_session.acknowledge();
+
+        // Send chat command packet
+        self.socket.send_packet(ChatCommandPacket {
+            command,
+            timestamp,
+            salt,
+            argument_signatures,
+            acknowledgment,
         }).ok();
     }
 }
@@ -50,10 +100,13 @@ impl IntoPacket for ClientCommandAction {
 
 /// Chat message packet (0x05)
 /// 
-/// **Warning:** This is only temporary and experimental implementation
+/// Signature is optional, unsigned messages are rejected by servers that enforce secure chat
 pub struct ChatMessagePacket {
     pub message: String,
     pub timestamp: u64,
+    pub salt: i64,
+    pub signature: Option<Vec<u8>>,
+    pub acknowledgment: MessageAcknowledgment,
 }
 
 impl IntoPacket for ChatMessagePacket {
@@ -63,14 +116,58 @@ impl IntoPacket for ChatMessagePacket {
         packet.write_string(&self.message);
         packet.write_ulong(self.timestamp);
 
-        packet.write_long(0); // Salt
-        packet.write_varint(0); // No signature
+        packet.write_long(self.salt);
+        packet.write_byte_array(&self.signature.unwrap_or_default()); // Empty if unsigned
 
         packet.write_bool(false); // No signed preview
-        packet.write_varint(0); // No previous messages
+        self.acknowledgment.write_to(&mut packet); // Last seen and last received messages
 
-        packet.write_bool(false); // Has last message
+        packet
+    }
+}
+
+/// Chat command packet (0x04)
+/// 
+/// Command is sent without leading `/`, arguments which contain player's message are signed separately
+pub struct ChatCommandPacket {
+    pub command: String,
+    pub timestamp: u64,
+    pub salt: i64,
+    /// Signatures of arguments (argument name, signature)
+    pub argument_signatures: Vec<(String, Vec<u8>)>,
+    pub acknowledgment: MessageAcknowledgment,
+}
 
+impl IntoPacket for ChatCommandPacket {
+    fn into_packet(self, _protocol_version: i32) -> RawPacket {
+        let mut packet = RawPacket::empty(0x04);
+
+        packet.write_string(&self.command);
+        packet.write_ulong(self.timestamp);
+        packet.write_long(self.salt);
+
+        packet.write_varint(self.argument_signatures.len() as i32);
+        for (name, signature) in &self.argument_signatures {
+            packet.write_string(name);
+            packet.write_byte_array(signature);
+        }
+
+        packet.write_bool(false); // No signed preview
+        self.acknowledgment.write_to(&mut packet); // Last seen and last received messages
+
+        packet
+    }
+}
+
+/// Message acknowledgment packet (0x03)
+/// 
+/// Sent when client received many messages without sending any of its own
+pub struct MessageAcknowledgmentPacket(pub MessageAcknowledgment);
+
+impl IntoPacket for MessageAcknowledgmentPacket {
+    fn into_packet(self, _protocol_version: i32) -> RawPacket {
+        let mut packet = RawPacket::empty(0x03);
+        self.0.write_to(&mut packet);
         packet
     }
 }
@@ -0,0 +1,210 @@
+use std::{sync::{Arc, Mutex}, time::Duration};
+
+use regex::Regex;
+
+use crate::{client::{MinecraftClient, ClientMutLock, ClientLockExt}, handlers::{chat::{ChatMessageEvent, ChatMessageSource}, tab_list::TabListEvent}, define_events};
+
+/// Position in the server queue
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueuePosition {
+    pub position: u32,
+    /// Estimated time left (if the server shows it)
+    pub eta: Option<Duration>,
+}
+
+define_events!(QueuePositionEvent (position: QueuePosition) => "Event emitted when position in the server queue changes (requires `QueueWatcherPlugin`)");
+
+/// Patterns used to find queue position and ETA in tab list and system messages
+///
+/// Position is taken from the `position` named group (or the first group), ETA from the `eta` named group (or the first group).
+/// ETA can be written as `1h 2m 3s`, `1:02:03` or `62:03`.
+#[derive(Debug, Clone)]
+pub struct QueueWatcherConfig {
+    pub position_patterns: Vec<Regex>,
+    pub eta_patterns: Vec<Regex>,
+    /// Whether to look for queue position in the tab list header and footer
+    pub watch_tab_list: bool,
+    /// Whether to look for queue position in system chat messages
+    pub watch_chat: bool,
+}
+
+impl Default for QueueWatcherConfig {
+    /// Patterns matching the most common queue plugins (e.g. `Position in queue: 12`)
+    fn default() -> Self {
+        QueueWatcherConfig {
+            position_patterns: vec![
+                Regex::new(r"(?i)position in queue:?\s*(?P<position>\d+)").unwrap(),
+                Regex::new(r"(?i)you are (?:currently )?(?:in )?(?:position|#)\s*(?P<position>\d+)").unwrap(),
+            ],
+            eta_patterns: vec![
+                Regex::new(r"(?i)(?:estimated time|eta):?\s*(?P<eta>(?:\d+\s*[dhms]\s*)+|\d+(?::\d+)+)").unwrap(),
+            ],
+            watch_tab_list: true,
+            watch_chat: true,
+        }
+    }
+}
+
+impl QueueWatcherConfig {
+    /// Finds queue position (and ETA) in the text
+    pub fn parse(&self, text: &str) -> Option<QueuePosition> {
+        let position = self.position_patterns.iter()
+            .find_map(|pattern| capture(pattern, text, "position"))
+            .and_then(|position| position.parse().ok())?;
+        let eta = self.eta_patterns.iter()
+            .find_map(|pattern| capture(pattern, text, "eta"))
+            .and_then(|eta| parse_duration(&eta));
+
+        Some(QueuePosition {
+            position,
+            eta,
+        })
+    }
+}
+
+/// Returns named group of the first match (or the first group if pattern doesn't have the named one)
+fn capture(pattern: &Regex, text: &str, name: &str) -> Option<String> {
+    let captures = pattern.captures(text)?;
+    captures.name(name).or_else(|| captures.get(1)).map(|m| m.as_str().trim().to_string())
+}
+
+/// Parses duration written as `1h 2m 3s` (units: `d`, `h`, `m`, `s`) or `1:02:03`
+pub fn parse_duration(text: &str) -> Option<Duration> {
+    let text = text.trim();
+    if text.contains(':') {
+        let mut seconds = 0;
+        for part in text.split(':') {
+            seconds = seconds * 60 + part.trim().parse::<u64>().ok()?;
+        }
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let mut seconds = 0;
+    let mut number = String::new();
+    let mut has_unit = false;
+    for c in text.chars().filter(|c| !c.is_whitespace()) {
+        if c.is_ascii_digit() {
+            number.push(c);
+            continue;
+        }
+
+        let multiplier = match c.to_ascii_lowercase() {
+            'd' => 86400,
+            'h' => 3600,
+            'm' => 60,
+            's' => 1,
+            _ => return None,
+        };
+        seconds += number.parse::<u64>().ok()? * multiplier;
+        number.clear();
+        has_unit = true;
+    }
+
+    if !number.is_empty() || !has_unit {
+        return None;
+    }
+    Some(Duration::from_secs(seconds))
+}
+
+/// Plugin which watches tab list and system messages for the position in the server queue
+///
+/// # Example
+/// ```rs
+/// client.watch_queue(QueueWatcherConfig::default());
+/// client.on(|_, e: &QueuePositionEvent| println!("Position in queue: {}", e.position.position));
+/// ```
+pub trait QueueWatcherPlugin {
+    /// Starts emitting `QueuePositionEvent` whenever queue position (or ETA) changes
+    fn watch_queue(&mut self, config: QueueWatcherConfig);
+}
+
+impl QueueWatcherPlugin for MinecraftClient {
+    fn watch_queue(&mut self, config: QueueWatcherConfig) {
+        let config = Arc::new(config);
+        let last_position = Arc::new(Mutex::new(None));
+
+        // Emits event if the text contains queue position different from the last one
+        let update = {
+            let config = config.clone();
+            move |client: &ClientMutLock, text: &str| {
+                let Some(position) = config.parse(text) else { return };
+                let mut last_position = last_position.lock().unwrap();
+                if last_position.as_ref() != Some(&position) {
+                    log::debug!(target: "miners-client", "Queue position changed: {:?}", position);
+                    *last_position = Some(position.clone());
+                    client.emit(QueuePositionEvent { position });
+                }
+            }
+        };
+        let update = Arc::new(update);
+
+        if config.watch_tab_list {
+            let update = update.clone();
+            self.on(move |client, e: &TabListEvent| {
+                let text = format!("{}\n{}", e.header.to_plain(), e.footer.to_plain());
+                update(&client, &text);
+            });
+        }
+
+        if config.watch_chat {
+            self.on(move |client, e: &ChatMessageEvent| {
+                if e.message.source == ChatMessageSource::System {
+                    update(&client, &e.message.plain_message);
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_default_patterns() {
+        let config = QueueWatcherConfig::default();
+        let position = |text: &str| config.parse(text).map(|position| position.position);
+        assert_eq!(position("Position in queue: 12"), Some(12));
+        assert_eq!(position("POSITION IN QUEUE 7"), Some(7));
+        assert_eq!(position("You are currently in position 5 of 100"), Some(5));
+        assert_eq!(position("You are #3 in the queue"), Some(3));
+        assert_eq!(position("Queue is full"), None);
+        assert_eq!(position("Position in queue: soon"), None);
+
+        let queue = config.parse("2b2t is full\nPosition in queue: 120\nEstimated time: 1h 2m 3s").unwrap();
+        assert_eq!(queue, QueuePosition { position: 120, eta: Some(Duration::from_secs(3723)) });
+        let queue = config.parse("Position in queue: 4, ETA: 62:03").unwrap();
+        assert_eq!(queue.eta, Some(Duration::from_secs(3723)));
+        assert_eq!(config.parse("Position in queue: 4").unwrap().eta, None);
+        assert_eq!(config.parse("Position in queue: 4, ETA: 5x").unwrap().eta, None);
+    }
+
+    #[test]
+    fn parse_custom_patterns() {
+        let config = QueueWatcherConfig {
+            position_patterns: vec![Regex::new(r"(\d+) players ahead").unwrap()],
+            eta_patterns: vec![Regex::new(r"wait (\S+)").unwrap()],
+            ..QueueWatcherConfig::default()
+        };
+        let queue = config.parse("3 players ahead, wait 2m").unwrap();
+        assert_eq!(queue, QueuePosition { position: 3, eta: Some(Duration::from_secs(120)) });
+        assert_eq!(config.parse("Position in queue: 12"), None);
+    }
+
+    #[test]
+    fn parse_durations() {
+        assert_eq!(parse_duration("1h 2m 3s"), Some(Duration::from_secs(3723)));
+        assert_eq!(parse_duration("1H2M3S"), Some(Duration::from_secs(3723)));
+        assert_eq!(parse_duration("1d 5s"), Some(Duration::from_secs(86405)));
+        assert_eq!(parse_duration("1:02:03"), Some(Duration::from_secs(3723)));
+        assert_eq!(parse_duration("62:03"), Some(Duration::from_secs(3723)));
+        assert_eq!(parse_duration(" 45s "), Some(Duration::from_secs(45)));
+    }
+
+    #[test]
+    fn parse_malformed_durations() {
+        for text in ["5x", "1h 5", "5", "", "h", "1:", ":30", "1::2", "1:ab", "-1:30", "1.5h"] {
+            assert_eq!(parse_duration(text), None, "{:?} was parsed", text);
+        }
+    }
+}
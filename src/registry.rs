@@ -0,0 +1,223 @@
+//! Typed registries decoded from the registry codec sent in login (play) packet
+
+use miners_protocol::utils::nbt::{self, NBTType, NBTCompound, NBTError};
+use serde::Deserialize;
+
+use crate::chat::chat_type::ChatType;
+
+/// Registries sent by the server (dimension types, biomes and chat types)
+#[derive(Debug, Clone, Default)]
+pub struct Registries {
+    pub dimension_types: Registry<DimensionType>,
+    pub biomes: Registry<Biome>,
+    pub chat_types: Registry<ChatType>,
+}
+
+impl Registries {
+    /// Decodes registries from registry codec (`LoginPlayPacket::nbt_registry_codec`)
+    ///
+    /// Invalid entries are skipped (and logged), missing registries are left empty
+    pub fn from_codec(codec: &NBTType) -> Registries {
+        Registries {
+            dimension_types: Registry::from_codec(codec, "minecraft:dimension_type", DimensionType::from_nbt),
+            biomes: Registry::from_codec(codec, "minecraft:worldgen/biome", Biome::from_nbt),
+            chat_types: Registry::from_codec(codec, "minecraft:chat_type", ChatType::from_nbt),
+        }
+    }
+}
+
+/// Single registry entry with its name and network id
+#[derive(Debug, Clone, PartialEq)]
+pub struct RegistryEntry<T> {
+    pub name: String,
+    pub id: i32,
+    pub element: T,
+}
+
+/// Registry of entries which can be looked up by network id or name (e.g. `minecraft:overworld`)
+#[derive(Debug, Clone)]
+pub struct Registry<T> {
+    pub entries: Vec<RegistryEntry<T>>,
+}
+
+impl<T> Default for Registry<T> {
+    fn default() -> Self {
+        Registry {
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl<T> Registry<T> {
+    /// Decodes registry with specified key from registry codec using `parse` for elements
+    pub fn from_codec(codec: &NBTType, key: &str, parse: impl Fn(String, &NBTCompound) -> Result<T, NBTError>) -> Registry<T> {
+        let mut registry = Registry::default();
+        let Some(values) = codec.as_compound().and_then(|codec| codec.get_compound(key)).and_then(|registry| registry.get_value::<&[NBTType]>("value")) else {
+            log::warn!(target: "miners-client", "Registry {} is missing in registry codec", key);
+            return registry;
+        };
+
+        for value in values {
+            let Some(value) = value.as_compound() else { continue };
+            let (Some(name), Some(id), Some(element)) = (value.get_str("name"), get_number(value, "id"), value.get_compound("element")) else {
+                continue;
+            };
+            let name = name.to_string();
+
+            match parse(name.clone(), element) {
+                Ok(element) => registry.entries.push(RegistryEntry {
+                    name,
+                    id: id as i32,
+                    element,
+                }),
+                Err(err) => log::warn!(target: "miners-client", "Invalid entry {} of registry {}: {}", name, key, err),
+            }
+        }
+        registry
+    }
+
+    /// Returns element with specified network id
+    pub fn get(&self, id: i32) -> Option<&T> {
+        self.entries.iter().find(|entry| entry.id == id).map(|entry| &entry.element)
+    }
+
+    /// Returns element with specified name (`minecraft:` namespace can be omitted)
+    pub fn get_by_name(&self, name: &str) -> Option<&T> {
+        self.entry_by_name(name).map(|entry| &entry.element)
+    }
+
+    /// Returns network id of element with specified name
+    pub fn id_of(&self, name: &str) -> Option<i32> {
+        self.entry_by_name(name).map(|entry| entry.id)
+    }
+
+    /// Returns name of element with specified network id
+    pub fn name_of(&self, id: i32) -> Option<&str> {
+        self.entries.iter().find(|entry| entry.id == id).map(|entry| entry.name.as_str())
+    }
+
+    fn entry_by_name(&self, name: &str) -> Option<&RegistryEntry<T>> {
+        self.entries.iter().find(|entry| entry.name == name || entry.name.strip_prefix("minecraft:") == Some(name))
+    }
+}
+
+/// Dimension type (entry of `minecraft:dimension_type` registry)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct DimensionType {
+    #[serde(skip)]
+    pub name: String,
+    /// Lowest block Y coordinate
+    pub min_y: i32,
+    /// Total height of the world (in blocks)
+    pub height: i32,
+    /// Maximum height to which chorus fruits and nether portals can bring players
+    pub logical_height: i32,
+    #[serde(default)]
+    pub ambient_light: f32,
+    #[serde(default = "default_true")]
+    pub has_skylight: bool,
+    #[serde(default)]
+    pub has_ceiling: bool,
+    #[serde(default)]
+    pub ultrawarm: bool,
+    #[serde(default = "default_true")]
+    pub natural: bool,
+    #[serde(default = "default_coordinate_scale")]
+    pub coordinate_scale: f64,
+    #[serde(default = "default_true")]
+    pub bed_works: bool,
+    #[serde(default)]
+    pub respawn_anchor_works: bool,
+    #[serde(default)]
+    pub piglin_safe: bool,
+    #[serde(default = "default_true")]
+    pub has_raids: bool,
+    /// Fixed time of day (`None` if time passes normally)
+    #[serde(default)]
+    pub fixed_time: Option<i64>,
+    #[serde(default)]
+    pub infiniburn: String,
+    #[serde(default = "default_effects")]
+    pub effects: String,
+}
+
+impl DimensionType {
+    /// Parses dimension type from registry entry element
+    pub fn from_nbt(name: String, element: &NBTCompound) -> Result<DimensionType, NBTError> {
+        let dimension_type: DimensionType = nbt::from_nbt(NBTType::Compound(element.clone()))?;
+        Ok(DimensionType { name, ..dimension_type })
+    }
+
+    /// Highest block Y coordinate (exclusive)
+    pub fn max_y(&self) -> i32 {
+        self.min_y + self.height
+    }
+
+    /// Number of chunk sections (16 blocks high) in a chunk column
+    pub fn section_count(&self) -> usize {
+        (self.height / 16) as usize
+    }
+}
+
+/// Biome (entry of `minecraft:worldgen/biome` registry)
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct Biome {
+    #[serde(skip)]
+    pub name: String,
+    /// `none`, `rain` or `snow`
+    #[serde(default = "default_precipitation")]
+    pub precipitation: String,
+    pub temperature: f32,
+    pub downfall: f32,
+    #[serde(default)]
+    pub effects: BiomeEffects,
+}
+
+/// Colors used to render biome
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default)]
+pub struct BiomeEffects {
+    pub sky_color: i32,
+    pub water_color: i32,
+    pub fog_color: i32,
+    pub water_fog_color: i32,
+    pub grass_color: Option<i32>,
+    pub foliage_color: Option<i32>,
+}
+
+impl Biome {
+    /// Parses biome from registry entry element
+    pub fn from_nbt(name: String, element: &NBTCompound) -> Result<Biome, NBTError> {
+        let biome: Biome = nbt::from_nbt(NBTType::Compound(element.clone()))?;
+        Ok(Biome { name, ..biome })
+    }
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_coordinate_scale() -> f64 {
+    1.0
+}
+
+fn default_effects() -> String {
+    String::from("minecraft:overworld")
+}
+
+fn default_precipitation() -> String {
+    String::from("none")
+}
+
+/// Returns numeric value of any type (servers are not consistent about them, e.g. booleans are bytes)
+fn get_number(nbt: &NBTCompound, key: &str) -> Option<f64> {
+    match nbt.get(key)? {
+        NBTType::Byte(value) => Some(*value as f64),
+        NBTType::Short(value) => Some(*value as f64),
+        NBTType::Int(value) => Some(*value as f64),
+        NBTType::Long(value) => Some(*value as f64),
+        NBTType::Float(value) => Some(*value as f64),
+        NBTType::Double(value) => Some(*value),
+        _ => None,
+    }
+}
\ No newline at end of file
@@ -0,0 +1,48 @@
+//! Request/response correlation for packets that carry a transaction id
+//! (e.g. Command Suggestions Request and Response)
+
+use std::{collections::HashMap, sync::mpsc::{Sender, Receiver, channel}};
+
+use miners_protocol::packet::RawPacket;
+
+/// Requests waiting for the response from the server
+/// 
+/// Each request is identified by id of the response packet and transaction id
+#[derive(Debug, Default)]
+pub struct PendingRequests {
+    next_transaction_id: i32,
+    waiting: HashMap<(i32, i32), Sender<RawPacket>>,
+}
+
+impl PendingRequests {
+    pub fn new() -> PendingRequests {
+        PendingRequests::default()
+    }
+
+    /// Registers new request waiting for packet with `response_id`
+    /// 
+    /// Returns transaction id which must be sent with the request and receiver of the response
+    pub fn register(&mut self, response_id: i32) -> (i32, Receiver<RawPacket>) {
+        let transaction_id = self.next_transaction_id;
+        self.next_transaction_id = self.next_transaction_id.wrapping_add(1) & i32::MAX; // Transaction ids are never negative
+
+        let (sender, receiver) = channel();
+        self.waiting.insert((response_id, transaction_id), sender);
+        (transaction_id, receiver)
+    }
+
+    /// Passes response to the waiting request
+    /// 
+    /// Returns `false` if nobody is waiting for it (e.g. request has already timed out)
+    pub fn resolve(&mut self, response_id: i32, transaction_id: i32, packet: RawPacket) -> bool {
+        match self.waiting.remove(&(response_id, transaction_id)) {
+            Some(sender) => sender.send(packet).is_ok(),
+            None => false,
+        }
+    }
+
+    /// Removes request (used when request times out)
+    pub fn cancel(&mut self, response_id: i32, transaction_id: i32) {
+        self.waiting.remove(&(response_id, transaction_id));
+    }
+}
\ No newline at end of file
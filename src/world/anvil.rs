@@ -0,0 +1,417 @@
+//! Export of the world to Anvil region files (format of vanilla saves since 1.18)
+//!
+//! # Example
+//! ```rs
+//! client.world().save_anvil("saves/event_map")?;
+//! ```
+
+use std::{collections::BTreeMap, fs, io, path::{Path, PathBuf}, time::{SystemTime, UNIX_EPOCH}};
+
+use miners_data::{BlockState, Version};
+use miners_protocol::utils::nbt::{NBTType, NBTCompound, NBTCompression};
+
+use super::{World, chunk::Chunk, palette::PalettedContainer};
+
+/// Size of a region file sector
+const SECTOR_SIZE: usize = 4096;
+/// Compression type of chunks (zlib)
+const ZLIB_COMPRESSION: u8 = 2;
+
+/// Index of the chunk in the region (`x + z * 32`) with compressed chunk data (prefixed with compression type)
+type RegionChunk = (usize, Vec<u8>);
+
+impl World {
+    /// Saves all loaded chunks as a vanilla save in `dir`
+    ///
+    /// Chunks are merged into existing region files, `level.dat` is only written if it doesn't exist yet
+    /// (the save uses void generator, so chunks which weren't downloaded stay empty).
+    /// Block names are needed for saving, so it fails if there is no data for the server version.
+    pub fn save_anvil(&self, dir: impl AsRef<Path>) -> io::Result<()> {
+        let dir = dir.as_ref();
+        let version = self.version.filter(Version::has_data).ok_or_else(|| {
+            io::Error::new(io::ErrorKind::Unsupported, "No block data for the server version")
+        })?;
+
+        for (dimension, chunks) in &self.dimensions {
+            let region_dir = dimension_dir(dir, dimension).join("region");
+            fs::create_dir_all(&region_dir)?;
+
+            let mut regions: BTreeMap<(i32, i32), Vec<RegionChunk>> = BTreeMap::new();
+            for chunk in chunks.values() {
+                let mut data = vec![ZLIB_COMPRESSION];
+                self.chunk_nbt(chunk, version).write_zlib(&mut data, "")?;
+
+                let index = (chunk.x.rem_euclid(32) + chunk.z.rem_euclid(32) * 32) as usize;
+                regions.entry((chunk.x.div_euclid(32), chunk.z.div_euclid(32))).or_default().push((index, data));
+            }
+
+            for ((x, z), chunks) in regions {
+                write_region(&region_dir.join(format!("r.{}.{}.mca", x, z)), chunks)?;
+            }
+        }
+
+        let level_path = dir.join("level.dat");
+        if !level_path.exists() {
+            self.level_nbt(version).write_file(level_path, "", NBTCompression::Gzip)?;
+        }
+        Ok(())
+    }
+
+    /// Converts chunk to its NBT stored in region files
+    fn chunk_nbt(&self, chunk: &Chunk, version: Version) -> NBTType {
+        let min_section = chunk.min_y.div_euclid(16);
+        let mut light_on = true;
+
+        // Light is also stored for one section below and above the world
+        let mut sections = Vec::new();
+        for index in 0..chunk.sections.len() + 2 {
+            let mut section = NBTCompound::new();
+            section.data.insert(String::from("Y"), NBTType::Byte((min_section - 1 + index as i32) as i8));
+            if let Some(chunk_section) = index.checked_sub(1).and_then(|index| chunk.sections.get(index)) {
+                section.data.insert(String::from("block_states"), block_states_nbt(&chunk_section.blocks, version));
+                section.data.insert(String::from("biomes"), self.biomes_nbt(&chunk_section.biomes));
+            }
+            for (key, light) in [("SkyLight", &chunk.light.sky_light), ("BlockLight", &chunk.light.block_light)] {
+                match light.get(index).and_then(Option::as_ref) {
+                    Some(light) => {
+                        section.data.insert(String::from(key), NBTType::ByteArray(light.iter().map(|v| *v as i8).collect()));
+                    },
+                    None => light_on = false,
+                }
+            }
+            sections.push(NBTType::Compound(section));
+        }
+
+        let block_entities = chunk.block_entities.iter().filter_map(|block_entity| {
+            let Some(id) = version.block_entity_type(block_entity.type_id) else {
+                log::warn!(target: "miners-client", "Skipping block entity of unknown type {}", block_entity.type_id);
+                return None;
+            };
+            let mut nbt = match &block_entity.nbt {
+                Some(NBTType::Compound(compound)) => compound.clone(),
+                _ => NBTCompound::new(),
+            };
+            nbt.data.insert(String::from("id"), NBTType::String(id.to_string()));
+            nbt.data.insert(String::from("x"), NBTType::Int(block_entity.position.x));
+            nbt.data.insert(String::from("y"), NBTType::Int(block_entity.position.y));
+            nbt.data.insert(String::from("z"), NBTType::Int(block_entity.position.z));
+            nbt.data.insert(String::from("keepPacked"), NBTType::Byte(0));
+            Some(NBTType::Compound(nbt))
+        }).collect();
+
+        compound([
+            ("DataVersion", NBTType::Int(version.data_version())),
+            ("xPos", NBTType::Int(chunk.x)),
+            ("zPos", NBTType::Int(chunk.z)),
+            ("yPos", NBTType::Int(min_section)),
+            ("Status", NBTType::String(String::from("full"))),
+            ("LastUpdate", NBTType::Long(0)),
+            ("InhabitedTime", NBTType::Long(0)),
+            // Vanilla recalculates light of chunks with missing light data
+            ("isLightOn", NBTType::Byte(light_on as i8)),
+            ("sections", NBTType::List(sections)),
+            ("block_entities", NBTType::List(block_entities)),
+            ("Heightmaps", chunk.heightmaps.clone().unwrap_or_else(|| NBTType::Compound(NBTCompound::new()))),
+            ("block_ticks", NBTType::List(Vec::new())),
+            ("fluid_ticks", NBTType::List(Vec::new())),
+            ("PostProcessing", NBTType::List(Vec::new())),
+            ("structures", compound([
+                ("References", NBTType::Compound(NBTCompound::new())),
+                ("starts", NBTType::Compound(NBTCompound::new())),
+            ])),
+        ])
+    }
+
+    /// Converts biome container to NBT (biomes are stored by name)
+    fn biomes_nbt(&self, biomes: &PalettedContainer) -> NBTType {
+        let (palette, indexes) = disk_palette(biomes);
+        let palette = palette.into_iter().map(|id| {
+            NBTType::String(self.biome_names.get(&id).cloned().unwrap_or_else(|| String::from("minecraft:plains")))
+        }).collect::<Vec<NBTType>>();
+        let bits = bits_for(palette.len());
+        palette_nbt(palette, &indexes, bits)
+    }
+
+    /// Creates `level.dat` of the save
+    fn level_nbt(&self, version: Version) -> NBTType {
+        let (spawn_x, spawn_y, spawn_z) = self.spawn_point();
+        let void_generator = |dimension_type: &str| compound([
+            ("type", NBTType::String(dimension_type.to_string())),
+            ("generator", compound([
+                ("type", NBTType::String(String::from("minecraft:flat"))),
+                ("settings", compound([
+                    ("layers", NBTType::List(Vec::new())),
+                    ("biome", NBTType::String(String::from("minecraft:the_void"))),
+                    ("lakes", NBTType::Byte(0)),
+                    ("features", NBTType::Byte(0)),
+                ])),
+            ])),
+        ]);
+        let last_played = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as i64).unwrap_or_default();
+
+        compound([("Data", compound([
+            ("DataVersion", NBTType::Int(version.data_version())),
+            ("Version", compound([
+                ("Id", NBTType::Int(version.data_version())),
+                ("Name", NBTType::String(version.name().to_string())),
+                ("Series", NBTType::String(String::from("main"))),
+                ("Snapshot", NBTType::Byte(0)),
+            ])),
+            ("LevelName", NBTType::String(String::from("Downloaded world"))),
+            ("GameType", NBTType::Int(3)), // Spectator
+            ("allowCommands", NBTType::Byte(1)),
+            ("initialized", NBTType::Byte(1)),
+            ("LastPlayed", NBTType::Long(last_played)),
+            ("SpawnX", NBTType::Int(spawn_x)),
+            ("SpawnY", NBTType::Int(spawn_y)),
+            ("SpawnZ", NBTType::Int(spawn_z)),
+            ("WorldGenSettings", compound([
+                ("seed", NBTType::Long(0)),
+                ("generate_features", NBTType::Byte(0)),
+                ("bonus_chest", NBTType::Byte(0)),
+                ("dimensions", compound([
+                    ("minecraft:overworld", void_generator("minecraft:overworld")),
+                    ("minecraft:the_nether", void_generator("minecraft:the_nether")),
+                    ("minecraft:the_end", void_generator("minecraft:the_end")),
+                ])),
+            ])),
+        ]))])
+    }
+
+    /// Returns spawn point of the save (on top of the loaded overworld chunk closest to the origin)
+    fn spawn_point(&self) -> (i32, i32, i32) {
+        let chunks = self.dimensions.get("minecraft:overworld").or_else(|| self.dimensions.get(&self.dimension));
+        let Some(chunk) = chunks.and_then(|chunks| chunks.values().min_by_key(|chunk| chunk.x.abs() + chunk.z.abs())) else {
+            return (0, 64, 0);
+        };
+
+        let (x, z) = (chunk.x * 16 + 8, chunk.z * 16 + 8);
+        let top = chunk.min_y + chunk.sections.len() as i32 * 16;
+        let y = (chunk.min_y..top).rev().find(|y| chunk.get_block_state(x, *y, z).is_some_and(|state| state != 0));
+        (x, y.map_or(64, |y| y + 1), z)
+    }
+}
+
+/// Returns directory of the dimension in the save
+fn dimension_dir(dir: &Path, dimension: &str) -> PathBuf {
+    match dimension {
+        "minecraft:overworld" | "" => dir.to_path_buf(),
+        "minecraft:the_nether" => dir.join("DIM-1"),
+        "minecraft:the_end" => dir.join("DIM1"),
+        _ => {
+            let (namespace, path) = dimension.split_once(':').unwrap_or(("minecraft", dimension));
+            dir.join("dimensions").join(namespace).join(path)
+        }
+    }
+}
+
+/// Converts block state container to NBT (states are stored by name and properties)
+fn block_states_nbt(blocks: &PalettedContainer, version: Version) -> NBTType {
+    let (palette, indexes) = disk_palette(blocks);
+    let palette = palette.into_iter().map(|id| {
+        let Some(state) = BlockState::from_id(version, id) else {
+            log::warn!(target: "miners-client", "Saving unknown block state {} as air", id);
+            return compound([("Name", NBTType::String(String::from("minecraft:air")))]);
+        };
+        let mut nbt = NBTCompound::new();
+        nbt.data.insert(String::from("Name"), NBTType::String(state.name().to_string()));
+        if !state.properties().is_empty() {
+            let properties = state.properties().iter().map(|(k, v)| (k.to_string(), NBTType::String(v.to_string()))).collect::<NBTCompound>();
+            nbt.data.insert(String::from("Properties"), NBTType::Compound(properties));
+        }
+        NBTType::Compound(nbt)
+    }).collect::<Vec<NBTType>>();
+    // Block states use at least 4 bits per entry
+    let bits = bits_for(palette.len()).max(4);
+    palette_nbt(palette, &indexes, bits)
+}
+
+/// Returns palette of the container (saves always use indirect palettes) and palette indexes of all entries
+fn disk_palette(container: &PalettedContainer) -> (Vec<i32>, Vec<usize>) {
+    let mut palette = Vec::new();
+    let indexes = container.values().into_iter().map(|value| {
+        palette.iter().position(|v| *v == value).unwrap_or_else(|| {
+            palette.push(value);
+            palette.len() - 1
+        })
+    }).collect();
+    (palette, indexes)
+}
+
+/// Creates paletted container NBT (`data` is omitted if there is only one entry in the palette)
+fn palette_nbt(palette: Vec<NBTType>, indexes: &[usize], bits: usize) -> NBTType {
+    let mut nbt = NBTCompound::new();
+    if palette.len() > 1 {
+        let per_long = 64 / bits;
+        let mut data = vec![0i64; indexes.len().div_ceil(per_long)];
+        for (i, index) in indexes.iter().enumerate() {
+            data[i / per_long] |= (*index as i64) << ((i % per_long) * bits);
+        }
+        nbt.data.insert(String::from("data"), NBTType::LongArray(data));
+    }
+    nbt.data.insert(String::from("palette"), NBTType::List(palette));
+    NBTType::Compound(nbt)
+}
+
+/// Returns number of bits needed for indexes of palette with `len` entries
+fn bits_for(len: usize) -> usize {
+    (usize::BITS - len.saturating_sub(1).leading_zeros()) as usize
+}
+
+/// Writes region file, keeping chunks of the existing file which weren't replaced
+fn write_region(path: &Path, chunks: Vec<RegionChunk>) -> io::Result<()> {
+    let mut entries: BTreeMap<usize, (u32, Vec<u8>)> = BTreeMap::new();
+    if let Ok(existing) = fs::read(path) {
+        for index in 0..1024 {
+            let header = |offset: usize| existing.get(offset..offset + 4).map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]));
+            let (Some(location), Some(timestamp)) = (header(index * 4), header(SECTOR_SIZE + index * 4)) else {
+                break;
+            };
+            let start = (location >> 8) as usize * SECTOR_SIZE;
+            let Some(length) = header(start).filter(|_| start >= 2 * SECTOR_SIZE) else {
+                continue;
+            };
+            if let Some(data) = existing.get(start + 4..start + 4 + length as usize) {
+                entries.insert(index, (timestamp, data.to_vec()));
+            }
+        }
+    }
+
+    let timestamp = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs() as u32).unwrap_or_default();
+    for (index, data) in chunks {
+        entries.insert(index, (timestamp, data));
+    }
+
+    let mut header = vec![0u8; 2 * SECTOR_SIZE];
+    let mut body = Vec::new();
+    for (index, (timestamp, data)) in entries {
+        let sectors = (data.len() + 4).div_ceil(SECTOR_SIZE);
+        if sectors > 255 {
+            //? Vanilla stores such chunks in separate `.mcc` files, which are not supported
+            log::warn!(target: "miners-client", "Skipping chunk {} of {} which is too large", index, path.display());
+            continue;
+        }
+        let sector = 2 + body.len() / SECTOR_SIZE;
+        header[index * 4..index * 4 + 4].copy_from_slice(&((sector as u32) << 8 | sectors as u32).to_be_bytes());
+        header[SECTOR_SIZE + index * 4..SECTOR_SIZE + index * 4 + 4].copy_from_slice(&timestamp.to_be_bytes());
+
+        body.extend((data.len() as u32).to_be_bytes());
+        body.extend(data);
+        body.resize(body.len().next_multiple_of(SECTOR_SIZE), 0);
+    }
+
+    header.extend(body);
+    fs::write(path, header)
+}
+
+/// Creates compound from its entries
+fn compound<const N: usize>(entries: [(&str, NBTType); N]) -> NBTType {
+    NBTType::Compound(entries.into_iter().collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Returns value of the compound entry
+    fn get<'a>(nbt: &'a NBTType, key: &str) -> &'a NBTType {
+        match nbt {
+            NBTType::Compound(compound) => compound.data.get(key).unwrap_or_else(|| panic!("Missing {}", key)),
+            _ => panic!("{:?} is not a compound", nbt),
+        }
+    }
+
+    fn list(nbt: &NBTType) -> &[NBTType] {
+        match nbt {
+            NBTType::List(list) => list,
+            _ => panic!("{:?} is not a list", nbt),
+        }
+    }
+
+    /// Reads chunk at the index from the region file
+    fn read_chunk(region: &[u8], index: usize) -> NBTType {
+        let header = |offset: usize| u32::from_be_bytes(region[offset..offset + 4].try_into().unwrap());
+        let location = header(index * 4);
+        let (start, sectors) = ((location >> 8) as usize * SECTOR_SIZE, (location & 255) as usize);
+        assert!(start >= 2 * SECTOR_SIZE && sectors > 0 && start + sectors * SECTOR_SIZE <= region.len());
+        assert_ne!(header(SECTOR_SIZE + index * 4), 0); // Timestamp
+
+        let length = header(start) as usize;
+        assert!(length + 4 <= sectors * SECTOR_SIZE);
+        assert_eq!(region[start + 4], ZLIB_COMPRESSION);
+        NBTType::read_zlib(&region[start + 5..start + 4 + length]).unwrap().1
+    }
+
+    #[test]
+    fn save_anvil_round_trip() {
+        let version = Version::V1_19_2;
+        let dir = std::env::temp_dir().join(format!("miners-anvil-test-{}", std::process::id()));
+        let mut world = World { dimension: String::from("minecraft:overworld"), version: Some(version), ..Default::default() };
+        let mut chunk = Chunk::empty(1, -1, -64, 384);
+        // Stone, grass block (snowy=false) and oak log (axis=x)
+        let blocks = [((16, -64, -16), 1), ((17, -64, -16), 9), ((31, -40, -1), 117)];
+        for ((x, y, z), state) in blocks {
+            chunk.set_block_state(x, y, z, state);
+        }
+        world.insert_chunk(chunk);
+
+        if !version.has_data() {
+            // Blocks can't be saved by their names without data
+            assert_eq!(world.save_anvil(&dir).map_err(|e| e.kind()), Err(io::ErrorKind::Unsupported));
+            return;
+        }
+        world.save_anvil(&dir).unwrap();
+        let region = fs::read(dir.join("region").join("r.0.-1.mca")).unwrap();
+        let level = NBTType::read_file(dir.join("level.dat")).unwrap().1;
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(region.len() % SECTOR_SIZE, 0);
+        // Only the chunk at X 1 and Z 31 of the region is stored
+        let index = 1 + 31 * 32;
+        assert!((0..1024).filter(|i| *i != index).all(|i| region[i * 4..i * 4 + 4] == [0; 4]));
+        let nbt = read_chunk(&region, index);
+        assert_eq!(get(&nbt, "DataVersion"), &NBTType::Int(3120));
+        assert_eq!(get(get(&level, "Data"), "DataVersion"), &NBTType::Int(3120));
+        assert_eq!((get(&nbt, "xPos"), get(&nbt, "zPos"), get(&nbt, "yPos")), (&NBTType::Int(1), &NBTType::Int(-1), &NBTType::Int(-4)));
+
+        // Sections with light only are stored below and above the world
+        let sections = list(get(&nbt, "sections"));
+        assert_eq!(sections.len(), 26);
+        let section = |y: i8| sections.iter().find(|section| get(section, "Y") == &NBTType::Byte(y)).unwrap();
+        let state = |name: &str, properties: &[(&str, &str)]| {
+            let mut nbt = NBTCompound::new();
+            nbt.data.insert(String::from("Name"), NBTType::String(name.to_string()));
+            if !properties.is_empty() {
+                let properties = properties.iter().map(|(k, v)| (k.to_string(), NBTType::String(v.to_string()))).collect();
+                nbt.data.insert(String::from("Properties"), NBTType::Compound(properties));
+            }
+            NBTType::Compound(nbt)
+        };
+
+        // Palette entries of all blocks of the section (4 bits per block, 16 blocks per long, index is X + Z * 16 + Y * 256)
+        let blocks = |y: i8| {
+            let block_states = get(section(y), "block_states");
+            let palette = list(get(block_states, "palette"));
+            let NBTType::LongArray(data) = get(block_states, "data") else { panic!("data is not a long array") };
+            assert_eq!(data.len(), 256);
+            (0..4096).map(|i| palette[((data[i / 16] >> ((i % 16) * 4)) & 15) as usize].clone()).collect::<Vec<NBTType>>()
+        };
+        let air = state("minecraft:air", &[]);
+
+        let bottom = blocks(-4);
+        assert_eq!(bottom[0], state("minecraft:stone", &[]));
+        assert_eq!(bottom[1], state("minecraft:grass_block", &[("snowy", "false")]));
+        assert!(bottom[2..].iter().all(|block| *block == air));
+        assert_eq!(list(get(get(section(-4), "block_states"), "palette")).len(), 3);
+
+        let oak_log = 15 + 15 * 16 + 8 * 256;
+        let blocks = blocks(-3);
+        assert_eq!(blocks[oak_log], state("minecraft:oak_log", &[("axis", "x")]));
+        assert!(blocks.iter().enumerate().all(|(i, block)| i == oak_log || *block == air));
+
+        // Sections with a single state don't store data
+        let empty = get(section(0), "block_states");
+        assert_eq!(list(get(empty, "palette")), [air]);
+        assert!(matches!(empty, NBTType::Compound(compound) if !compound.data.contains_key("data")));
+    }
+}
\ No newline at end of file
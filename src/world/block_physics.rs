@@ -0,0 +1,618 @@
+//! Physics of block states based on block names and properties from `miners-data`
+//!
+//! Vanilla reports don't contain collision shapes, so shapes of non-full blocks are defined here
+//! (same boxes as vanilla collision shapes, in 1/16 of the block), other colliding blocks are full blocks
+
+use miners_data::{BlockState, Version};
+use miners_protocol::utils::location::Vec3;
+
+use crate::physics::{Fluid, aabb::Aabb};
+
+use super::BlockPhysics;
+
+/// Blocks without collision (matched by name without namespace)
+const PASSABLE_BLOCKS: &[&str] = &[
+    "air", "cave_air", "void_air", "structure_void", "light", "moving_piston",
+    "grass", "tall_grass", "fern", "large_fern", "dead_bush", "seagrass", "tall_seagrass", "kelp", "kelp_plant",
+    "sugar_cane", "wheat", "carrots", "potatoes", "beetroots", "melon_stem", "pumpkin_stem", "attached_melon_stem",
+    "attached_pumpkin_stem", "sweet_berry_bush", "nether_wart", "cobweb", "fire", "soul_fire", "bamboo_sapling",
+    "torch", "wall_torch", "soul_torch", "soul_wall_torch", "redstone_torch", "redstone_wall_torch", "redstone_wire",
+    "rail", "powered_rail", "detector_rail", "activator_rail", "lever", "tripwire", "tripwire_hook",
+    "vine", "glow_lichen", "sculk_vein", "twisting_vines", "twisting_vines_plant", "weeping_vines", "weeping_vines_plant",
+    "cave_vines", "cave_vines_plant", "hanging_roots", "crimson_roots", "warped_roots", "nether_sprouts",
+    "crimson_fungus", "warped_fungus", "brown_mushroom", "red_mushroom", "small_dripleaf", "big_dripleaf_stem",
+    "spore_blossom", "frogspawn", "dandelion", "poppy", "blue_orchid", "allium", "azure_bluet", "red_tulip",
+    "orange_tulip", "white_tulip", "pink_tulip", "oxeye_daisy", "cornflower", "lily_of_the_valley", "wither_rose",
+    "sunflower", "lilac", "rose_bush", "peony", "water", "lava", "bubble_column", "nether_portal", "end_portal",
+    "end_gateway",
+    // Collide only with entities falling into them or standing on top of them (climbed instead)
+    "powder_snow", "scaffolding",
+];
+
+/// Name suffixes of blocks without collision
+const PASSABLE_SUFFIXES: &[&str] = &[
+    "_sapling", "_sign", "_button", "_pressure_plate", "_banner", "_coral", "_coral_fan", "_coral_wall_fan", "_propagule",
+];
+
+/// Blocks which can be climbed
+const CLIMBABLE_BLOCKS: &[&str] = &[
+    "ladder", "vine", "scaffolding", "twisting_vines", "twisting_vines_plant", "weeping_vines", "weeping_vines_plant",
+    "cave_vines", "cave_vines_plant",
+];
+
+/// Returns physics of the block state using data of the version (`None` if the state is unknown)
+pub fn data_block_physics(version: Version, state: i32) -> Option<BlockPhysics> {
+    let block_state = BlockState::from_id(version, state)?;
+
+    let name = block_state.name().strip_prefix("minecraft:").unwrap_or(block_state.name());
+    let mut physics = if PASSABLE_BLOCKS.contains(&name) || PASSABLE_SUFFIXES.iter().any(|suffix| name.ends_with(suffix)) {
+        BlockPhysics::passable()
+    } else {
+        BlockPhysics {
+            collision_boxes: collision_boxes(name, block_state.properties()),
+            ..BlockPhysics::passable()
+        }
+    };
+
+    physics.fluid = match name {
+        "water" | "bubble_column" | "seagrass" | "tall_seagrass" | "kelp" | "kelp_plant" => Some(Fluid::Water),
+        "lava" => Some(Fluid::Lava),
+        _ if block_state.property("waterlogged") == Some("true") => Some(Fluid::Water),
+        _ => None,
+    };
+    physics.climbable = CLIMBABLE_BLOCKS.contains(&name);
+    physics.friction = match name {
+        "ice" | "packed_ice" | "frosted_ice" => 0.98,
+        "blue_ice" => 0.989,
+        "slime_block" => 0.8,
+        _ => physics.friction,
+    };
+    // Water and bubble columns keep the speed, so the block below them isn't used
+    physics.speed_factor = match name {
+        "soul_sand" | "honey_block" => Some(0.4),
+        "water" | "bubble_column" => Some(1.0),
+        _ => None,
+    };
+    if name == "honey_block" {
+        physics.jump_factor = 0.5;
+    }
+    let block = block_state.block();
+    physics.hardness = block.hardness();
+    physics.requires_tool = block.requires_tool();
+    physics.tool = block.tool();
+    physics.tool_tier = block.tool_tier();
+    // Thin blocks are moved by up to 2 pixels depending on their position
+    if matches!(name, "bamboo" | "pointed_dripstone") {
+        physics.max_offset = 0.125;
+    }
+    Some(physics)
+}
+
+/// Box in 1/16 of the block (same as vanilla `Block.box`)
+fn block_box(x1: f64, y1: f64, z1: f64, x2: f64, y2: f64, z2: f64) -> Aabb {
+    Aabb::new(Vec3::new(x1, y1, z1) * (1.0 / 16.0), Vec3::new(x2, y2, z2) * (1.0 / 16.0))
+}
+
+/// Returns collision boxes of the colliding block with the properties
+pub(crate) fn collision_boxes(name: &str, properties: &[(&str, &str)]) -> Vec<Aabb> {
+    let property = |key: &str| properties.iter().find(|(k, _)| *k == key).map(|(_, value)| *value);
+    let is_true = |key: &str| property(key) == Some("true");
+    let number = |key: &str| property(key).and_then(|value| value.parse::<f64>().ok()).unwrap_or(0.0);
+    let facing = property("facing").unwrap_or("north");
+
+    if name.ends_with("_slab") {
+        return match property("type") {
+            Some("bottom") => vec![block_box(0.0, 0.0, 0.0, 16.0, 8.0, 16.0)],
+            Some("top") => vec![block_box(0.0, 8.0, 0.0, 16.0, 16.0, 16.0)],
+            _ => vec![Aabb::FULL_BLOCK],
+        };
+    }
+    if name.ends_with("_stairs") {
+        return stairs_boxes(facing, property("half") == Some("top"), property("shape").unwrap_or("straight"));
+    }
+    if name.ends_with("_fence") {
+        return cross_boxes(2.0, 24.0, |direction| is_true(direction));
+    }
+    if name.ends_with("glass_pane") || name == "iron_bars" {
+        return cross_boxes(1.0, 16.0, |direction| is_true(direction));
+    }
+    if name.ends_with("_wall") {
+        let mut boxes = cross_boxes(3.0, 24.0, |direction| property(direction).is_some_and(|height| height != "none"));
+        // Arms are narrower than the post, which is only present if `up` is set
+        boxes.remove(0);
+        if is_true("up") {
+            boxes.push(block_box(4.0, 0.0, 4.0, 12.0, 24.0, 12.0));
+        }
+        return boxes;
+    }
+    if name.ends_with("_fence_gate") {
+        return match (is_true("open"), facing) {
+            (true, _) => Vec::new(),
+            (false, "north" | "south") => vec![block_box(0.0, 0.0, 6.0, 16.0, 24.0, 10.0)],
+            (false, _) => vec![block_box(6.0, 0.0, 0.0, 10.0, 24.0, 16.0)],
+        };
+    }
+    if name.ends_with("_trapdoor") {
+        let side = match (is_true("open"), facing) {
+            (false, _) if property("half") == Some("top") => "up",
+            (false, _) => "down",
+            (true, facing) => opposite(facing),
+        };
+        return vec![wall_box(side, 3.0)];
+    }
+    if name.ends_with("_door") {
+        // Closed door is on the back side of the block, open door is turned towards its hinge
+        let side = match (is_true("open"), property("hinge") == Some("right")) {
+            (false, _) => opposite(facing),
+            (true, true) => clockwise(facing),
+            (true, false) => counter_clockwise(facing),
+        };
+        return vec![wall_box(side, 3.0)];
+    }
+    if name.ends_with("_bed") {
+        // Legs are at the outer end of each part
+        let end = if property("part") == Some("foot") { opposite(facing) } else { facing };
+        let mut boxes = vec![block_box(0.0, 3.0, 0.0, 16.0, 9.0, 16.0)];
+        for leg in [block_box(0.0, 0.0, 0.0, 3.0, 3.0, 3.0), block_box(0.0, 0.0, 13.0, 3.0, 3.0, 16.0),
+                    block_box(13.0, 0.0, 0.0, 16.0, 3.0, 3.0), block_box(13.0, 0.0, 13.0, 16.0, 3.0, 16.0)] {
+            let touches = match end {
+                "north" => leg.min.z == 0.0,
+                "south" => leg.max.z == 1.0,
+                "west" => leg.min.x == 0.0,
+                _ => leg.max.x == 1.0,
+            };
+            if touches {
+                boxes.push(leg);
+            }
+        }
+        return boxes;
+    }
+    if name.ends_with("_carpet") {
+        return vec![block_box(0.0, 0.0, 0.0, 16.0, 1.0, 16.0)];
+    }
+    if name.ends_with("candle_cake") {
+        return vec![block_box(1.0, 0.0, 1.0, 15.0, 8.0, 15.0), block_box(7.0, 8.0, 7.0, 9.0, 14.0, 9.0)];
+    }
+    if name.ends_with("candle") {
+        return vec![match property("candles") {
+            Some("1") => block_box(7.0, 0.0, 7.0, 9.0, 6.0, 9.0),
+            Some("2") => block_box(5.0, 0.0, 6.0, 11.0, 6.0, 9.0),
+            Some("3") => block_box(5.0, 0.0, 6.0, 10.0, 6.0, 11.0),
+            _ => block_box(5.0, 0.0, 5.0, 11.0, 6.0, 10.0),
+        }];
+    }
+    if name.ends_with("_wall_skull") || name.ends_with("_wall_head") {
+        let (x1, z1, x2, z2) = match facing {
+            "south" => (4.0, 0.0, 12.0, 8.0),
+            "east" => (0.0, 4.0, 8.0, 12.0),
+            "west" => (8.0, 4.0, 16.0, 12.0),
+            _ => (4.0, 8.0, 12.0, 16.0),
+        };
+        return vec![block_box(x1, 4.0, z1, x2, 12.0, z2)];
+    }
+    if name.ends_with("_skull") || (name.ends_with("_head") && name != "piston_head") {
+        return vec![block_box(4.0, 0.0, 4.0, 12.0, 8.0, 12.0)];
+    }
+    if name == "flower_pot" || name.starts_with("potted_") {
+        return vec![block_box(5.0, 0.0, 5.0, 11.0, 6.0, 11.0)];
+    }
+    if name.ends_with("amethyst_cluster") || name.ends_with("amethyst_bud") {
+        let (height, inset) = match name {
+            "amethyst_cluster" => (7.0, 3.0),
+            "large_amethyst_bud" => (5.0, 3.0),
+            "medium_amethyst_bud" => (4.0, 3.0),
+            _ => (3.0, 4.0),
+        };
+        let far = 16.0 - inset;
+        return vec![match facing {
+            "up" => block_box(inset, 0.0, inset, far, height, far),
+            "down" => block_box(inset, 16.0 - height, inset, far, 16.0, far),
+            "north" => block_box(inset, inset, 16.0 - height, far, far, 16.0),
+            "south" => block_box(inset, inset, 0.0, far, far, height),
+            "east" => block_box(0.0, inset, inset, height, far, far),
+            _ => block_box(16.0 - height, inset, inset, 16.0, far, far),
+        }];
+    }
+
+    match name {
+        "snow" => {
+            // Layers are one layer lower than they look, so single layer doesn't collide
+            let height = (number("layers") - 1.0).max(0.0) * 2.0;
+            if height == 0.0 { Vec::new() } else { vec![block_box(0.0, 0.0, 0.0, 16.0, height, 16.0)] }
+        },
+        "farmland" | "dirt_path" => vec![block_box(0.0, 0.0, 0.0, 16.0, 15.0, 16.0)],
+        "soul_sand" | "mud" => vec![block_box(0.0, 0.0, 0.0, 16.0, 14.0, 16.0)],
+        "honey_block" | "cactus" => vec![block_box(1.0, 0.0, 1.0, 15.0, 15.0, 15.0)],
+        "chest" | "trapped_chest" => {
+            // Double chests extend to the other half
+            let connected = match property("type") {
+                Some("left") => clockwise(facing),
+                Some("right") => counter_clockwise(facing),
+                _ => "",
+            };
+            vec![match connected {
+                "north" => block_box(1.0, 0.0, 0.0, 15.0, 14.0, 15.0),
+                "south" => block_box(1.0, 0.0, 1.0, 15.0, 14.0, 16.0),
+                "west" => block_box(0.0, 0.0, 1.0, 15.0, 14.0, 15.0),
+                "east" => block_box(1.0, 0.0, 1.0, 16.0, 14.0, 15.0),
+                _ => block_box(1.0, 0.0, 1.0, 15.0, 14.0, 15.0),
+            }]
+        },
+        "ender_chest" => vec![block_box(1.0, 0.0, 1.0, 15.0, 14.0, 15.0)],
+        "lantern" | "soul_lantern" => if is_true("hanging") {
+            vec![block_box(5.0, 1.0, 5.0, 11.0, 8.0, 11.0), block_box(6.0, 8.0, 6.0, 10.0, 10.0, 10.0)]
+        } else {
+            vec![block_box(5.0, 0.0, 5.0, 11.0, 7.0, 11.0), block_box(6.0, 7.0, 6.0, 10.0, 9.0, 10.0)]
+        },
+        "cake" => vec![block_box(1.0 + number("bites") * 2.0, 0.0, 1.0, 15.0, 8.0, 15.0)],
+        "ladder" => vec![wall_box(opposite(facing), 3.0)],
+        "lily_pad" => vec![block_box(1.0, 0.0, 1.0, 15.0, 1.5, 15.0)],
+        "enchanting_table" => vec![block_box(0.0, 0.0, 0.0, 16.0, 12.0, 16.0)],
+        "stonecutter" => vec![block_box(0.0, 0.0, 0.0, 16.0, 9.0, 16.0)],
+        "sculk_sensor" | "sculk_shrieker" => vec![block_box(0.0, 0.0, 0.0, 16.0, 8.0, 16.0)],
+        "campfire" | "soul_campfire" => vec![block_box(0.0, 0.0, 0.0, 16.0, 7.0, 16.0)],
+        "daylight_detector" => vec![block_box(0.0, 0.0, 0.0, 16.0, 6.0, 16.0)],
+        "repeater" | "comparator" => vec![block_box(0.0, 0.0, 0.0, 16.0, 2.0, 16.0)],
+        "conduit" => vec![block_box(5.0, 5.0, 5.0, 11.0, 11.0, 11.0)],
+        "dragon_egg" => vec![block_box(1.0, 0.0, 1.0, 15.0, 16.0, 15.0)],
+        "bamboo" => vec![block_box(6.5, 0.0, 6.5, 9.5, 16.0, 9.5)],
+        "end_portal_frame" => {
+            let mut boxes = vec![block_box(0.0, 0.0, 0.0, 16.0, 13.0, 16.0)];
+            if is_true("eye") {
+                boxes.push(block_box(4.0, 13.0, 4.0, 12.0, 16.0, 12.0));
+            }
+            boxes
+        },
+        "brewing_stand" => vec![block_box(1.0, 0.0, 1.0, 15.0, 2.0, 15.0), block_box(7.0, 0.0, 7.0, 9.0, 14.0, 9.0)],
+        "lectern" => vec![block_box(0.0, 0.0, 0.0, 16.0, 2.0, 16.0), block_box(4.0, 2.0, 4.0, 12.0, 14.0, 12.0)],
+        "azalea" | "flowering_azalea" => vec![block_box(0.0, 8.0, 0.0, 16.0, 16.0, 16.0), block_box(6.0, 0.0, 6.0, 10.0, 8.0, 10.0)],
+        "big_dripleaf" => match property("tilt") {
+            Some("partial") => vec![block_box(0.0, 11.0, 0.0, 16.0, 13.0, 16.0)],
+            Some("full") => Vec::new(),
+            _ => vec![block_box(0.0, 11.0, 0.0, 16.0, 15.0, 16.0)],
+        },
+        "sea_pickle" => vec![match property("pickles") {
+            Some("1") => block_box(6.0, 0.0, 6.0, 10.0, 6.0, 10.0),
+            Some("2") => block_box(3.0, 0.0, 3.0, 13.0, 6.0, 13.0),
+            Some("3") => block_box(2.0, 0.0, 2.0, 14.0, 6.0, 14.0),
+            _ => block_box(2.0, 0.0, 2.0, 14.0, 7.0, 14.0),
+        }],
+        "turtle_egg" => vec![if property("eggs") == Some("1") {
+            block_box(3.0, 0.0, 3.0, 12.0, 7.0, 12.0)
+        } else {
+            block_box(1.0, 0.0, 1.0, 15.0, 7.0, 15.0)
+        }],
+        "cocoa" => {
+            // Pod grows away from the log it's attached to (on the `facing` side)
+            let (width, height) = match property("age") {
+                Some("0") => (4.0, 5.0),
+                Some("1") => (6.0, 7.0),
+                _ => (8.0, 9.0),
+            };
+            let (min, max) = (8.0 - width / 2.0, 8.0 + width / 2.0);
+            let (y1, y2) = (12.0 - height, 12.0);
+            vec![match facing {
+                "south" => block_box(min, y1, 15.0 - width, max, y2, 15.0),
+                "west" => block_box(1.0, y1, min, 1.0 + width, y2, max),
+                "east" => block_box(15.0 - width, y1, min, 15.0, y2, max),
+                _ => block_box(min, y1, 1.0, max, y2, 1.0 + width),
+            }]
+        },
+        "chain" => vec![rod_box(property("axis").unwrap_or("y"), 6.5)],
+        "end_rod" | "lightning_rod" => vec![rod_box(axis(facing), 6.0)],
+        "anvil" | "chipped_anvil" | "damaged_anvil" => {
+            let mut boxes = vec![block_box(2.0, 0.0, 2.0, 14.0, 4.0, 14.0)];
+            if axis(facing) == "x" {
+                boxes.extend([block_box(3.0, 4.0, 4.0, 13.0, 5.0, 12.0), block_box(4.0, 5.0, 6.0, 12.0, 10.0, 10.0), block_box(0.0, 10.0, 3.0, 16.0, 16.0, 13.0)]);
+            } else {
+                boxes.extend([block_box(4.0, 4.0, 3.0, 12.0, 5.0, 13.0), block_box(6.0, 5.0, 4.0, 10.0, 10.0, 12.0), block_box(3.0, 10.0, 0.0, 13.0, 16.0, 16.0)]);
+            }
+            boxes
+        },
+        "cauldron" | "water_cauldron" | "lava_cauldron" | "powder_snow_cauldron" => {
+            // Hollow inside with legs in the corners
+            let mut boxes = vec![block_box(0.0, 3.0, 0.0, 16.0, 4.0, 16.0)];
+            boxes.extend(hollow_walls(4.0, 16.0, 2.0));
+            boxes.extend([
+                block_box(0.0, 0.0, 0.0, 2.0, 3.0, 4.0), block_box(2.0, 0.0, 0.0, 4.0, 3.0, 2.0),
+                block_box(14.0, 0.0, 0.0, 16.0, 3.0, 4.0), block_box(12.0, 0.0, 0.0, 14.0, 3.0, 2.0),
+                block_box(0.0, 0.0, 12.0, 2.0, 3.0, 16.0), block_box(2.0, 0.0, 14.0, 4.0, 3.0, 16.0),
+                block_box(14.0, 0.0, 12.0, 16.0, 3.0, 16.0), block_box(12.0, 0.0, 14.0, 14.0, 3.0, 16.0),
+            ]);
+            boxes
+        },
+        "composter" => {
+            let mut boxes = vec![block_box(0.0, 0.0, 0.0, 16.0, 2.0, 16.0)];
+            boxes.extend(hollow_walls(2.0, 16.0, 2.0));
+            boxes
+        },
+        "hopper" => {
+            let mut boxes = vec![block_box(0.0, 10.0, 0.0, 16.0, 11.0, 16.0), block_box(4.0, 4.0, 4.0, 12.0, 10.0, 12.0)];
+            boxes.extend(hollow_walls(11.0, 16.0, 2.0));
+            boxes.push(match facing {
+                "north" => block_box(6.0, 4.0, 0.0, 10.0, 8.0, 4.0),
+                "south" => block_box(6.0, 4.0, 12.0, 10.0, 8.0, 16.0),
+                "west" => block_box(0.0, 4.0, 6.0, 4.0, 8.0, 10.0),
+                "east" => block_box(12.0, 4.0, 6.0, 16.0, 8.0, 10.0),
+                _ => block_box(6.0, 0.0, 6.0, 10.0, 4.0, 10.0),
+            });
+            boxes
+        },
+        "bell" => bell_boxes(property("attachment").unwrap_or("floor"), facing),
+        "grindstone" => grindstone_boxes(property("face").unwrap_or("wall"), facing),
+        "chorus_plant" => {
+            let mut boxes = vec![block_box(3.0, 3.0, 3.0, 13.0, 13.0, 13.0)];
+            for (direction, side) in [("north", (3.0, 3.0, 0.0, 13.0, 13.0, 3.0)), ("south", (3.0, 3.0, 13.0, 13.0, 13.0, 16.0)),
+                                      ("west", (0.0, 3.0, 3.0, 3.0, 13.0, 13.0)), ("east", (13.0, 3.0, 3.0, 16.0, 13.0, 13.0)),
+                                      ("down", (3.0, 0.0, 3.0, 13.0, 3.0, 13.0)), ("up", (3.0, 13.0, 3.0, 13.0, 16.0, 13.0))] {
+                if is_true(direction) {
+                    boxes.push(block_box(side.0, side.1, side.2, side.3, side.4, side.5));
+                }
+            }
+            boxes
+        },
+        "pointed_dripstone" => vec![match (property("thickness"), property("vertical_direction")) {
+            (Some("tip_merge"), _) => block_box(5.0, 0.0, 5.0, 11.0, 16.0, 11.0),
+            (Some("tip"), Some("down")) => block_box(5.0, 5.0, 5.0, 11.0, 16.0, 11.0),
+            (Some("tip"), _) => block_box(5.0, 0.0, 5.0, 11.0, 11.0, 11.0),
+            (Some("frustum"), _) => block_box(4.0, 0.0, 4.0, 12.0, 16.0, 12.0),
+            (Some("middle"), _) => block_box(3.0, 0.0, 3.0, 13.0, 16.0, 13.0),
+            _ => block_box(2.0, 0.0, 2.0, 14.0, 16.0, 14.0),
+        }],
+        "piston" | "sticky_piston" if is_true("extended") => vec![wall_box(opposite(facing), 12.0)],
+        "piston_head" => {
+            let short = is_true("short");
+            let arm = match facing {
+                "up" => block_box(6.0, if short { 0.0 } else { -4.0 }, 6.0, 10.0, 12.0, 10.0),
+                "down" => block_box(6.0, 4.0, 6.0, 10.0, if short { 16.0 } else { 20.0 }, 10.0),
+                "north" => block_box(6.0, 6.0, 4.0, 10.0, 10.0, if short { 16.0 } else { 20.0 }),
+                "south" => block_box(6.0, 6.0, if short { 0.0 } else { -4.0 }, 10.0, 10.0, 12.0),
+                "west" => block_box(4.0, 6.0, 6.0, if short { 16.0 } else { 20.0 }, 10.0, 10.0),
+                _ => block_box(if short { 0.0 } else { -4.0 }, 6.0, 6.0, 12.0, 10.0, 10.0),
+            };
+            vec![wall_box(facing, 4.0), arm]
+        },
+        _ => vec![Aabb::FULL_BLOCK],
+    }
+}
+
+fn clockwise(direction: &str) -> &'static str {
+    match direction {
+        "north" => "east",
+        "east" => "south",
+        "south" => "west",
+        _ => "north",
+    }
+}
+
+fn counter_clockwise(direction: &str) -> &'static str {
+    match direction {
+        "north" => "west",
+        "west" => "south",
+        "south" => "east",
+        _ => "north",
+    }
+}
+
+fn opposite(direction: &str) -> &'static str {
+    match direction {
+        "north" => "south",
+        "south" => "north",
+        "west" => "east",
+        "east" => "west",
+        "up" => "down",
+        _ => "up",
+    }
+}
+
+fn axis(direction: &str) -> &'static str {
+    match direction {
+        "north" | "south" => "z",
+        "west" | "east" => "x",
+        _ => "y",
+    }
+}
+
+/// Box of specified thickness covering the side of the block (e.g. trapdoors and doors)
+fn wall_box(side: &str, thickness: f64) -> Aabb {
+    let far = 16.0 - thickness;
+    match side {
+        "north" => block_box(0.0, 0.0, 0.0, 16.0, 16.0, thickness),
+        "south" => block_box(0.0, 0.0, far, 16.0, 16.0, 16.0),
+        "west" => block_box(0.0, 0.0, 0.0, thickness, 16.0, 16.0),
+        "east" => block_box(far, 0.0, 0.0, 16.0, 16.0, 16.0),
+        "up" => block_box(0.0, far, 0.0, 16.0, 16.0, 16.0),
+        _ => block_box(0.0, 0.0, 0.0, 16.0, thickness, 16.0),
+    }
+}
+
+/// Box of a rod through the center of the block along the axis (e.g. chains)
+fn rod_box(axis: &str, min: f64) -> Aabb {
+    let max = 16.0 - min;
+    match axis {
+        "x" => block_box(0.0, min, min, 16.0, max, max),
+        "z" => block_box(min, min, 0.0, max, max, 16.0),
+        _ => block_box(min, 0.0, min, max, 16.0, max),
+    }
+}
+
+/// Walls of a hollow block between `y1` and `y2` (e.g. cauldrons)
+fn hollow_walls(y1: f64, y2: f64, thickness: f64) -> [Aabb; 4] {
+    let far = 16.0 - thickness;
+    [
+        block_box(0.0, y1, 0.0, thickness, y2, 16.0),
+        block_box(far, y1, 0.0, 16.0, y2, 16.0),
+        block_box(thickness, y1, 0.0, far, y2, thickness),
+        block_box(thickness, y1, far, far, y2, 16.0),
+    ]
+}
+
+/// Post with arms towards connected sides (fences, panes and walls)
+///
+/// The post is always the first box
+fn cross_boxes(half_width: f64, height: f64, connected: impl Fn(&str) -> bool) -> Vec<Aabb> {
+    let (min, max) = (8.0 - half_width, 8.0 + half_width);
+    let mut boxes = vec![block_box(min, 0.0, min, max, height, max)];
+    for (direction, (x1, z1, x2, z2)) in [("north", (min, 0.0, max, max)), ("south", (min, min, max, 16.0)),
+                                          ("west", (0.0, min, max, max)), ("east", (min, min, 16.0, max))] {
+        if connected(direction) {
+            boxes.push(block_box(x1, 0.0, z1, x2, height, z2));
+        }
+    }
+    boxes
+}
+
+/// Bottom slab (or top slab if upside down) with the step on the `facing` side
+fn stairs_boxes(facing: &str, top: bool, shape: &str) -> Vec<Aabb> {
+    let (slab, step) = if top { ((8.0, 16.0), (0.0, 8.0)) } else { ((0.0, 8.0), (8.0, 16.0)) };
+    let half = |side: &str| match side {
+        "north" => (0.0, 0.0, 16.0, 8.0),
+        "south" => (0.0, 8.0, 16.0, 16.0),
+        "west" => (0.0, 0.0, 8.0, 16.0),
+        _ => (8.0, 0.0, 16.0, 16.0),
+    };
+    // Intersection of two halves
+    let quarter = |a: &str, b: &str| {
+        let (a, b) = (half(a), half(b));
+        (f64::max(a.0, b.0), f64::max(a.1, b.1), f64::min(a.2, b.2), f64::min(a.3, b.3))
+    };
+    let steps = match shape {
+        "inner_left" => vec![half(facing), quarter(opposite(facing), counter_clockwise(facing))],
+        "inner_right" => vec![half(facing), quarter(opposite(facing), clockwise(facing))],
+        "outer_left" => vec![quarter(facing, counter_clockwise(facing))],
+        "outer_right" => vec![quarter(facing, clockwise(facing))],
+        _ => vec![half(facing)],
+    };
+
+    let mut boxes = vec![block_box(0.0, slab.0, 0.0, 16.0, slab.1, 16.0)];
+    boxes.extend(steps.into_iter().map(|(x1, z1, x2, z2)| block_box(x1, step.0, z1, x2, step.1, z2)));
+    boxes
+}
+
+fn bell_boxes(attachment: &str, facing: &str) -> Vec<Aabb> {
+    let bell = [block_box(5.0, 6.0, 5.0, 11.0, 13.0, 11.0), block_box(4.0, 4.0, 4.0, 12.0, 6.0, 12.0)];
+    let beam = match attachment {
+        "floor" if axis(facing) == "z" => return vec![block_box(0.0, 0.0, 4.0, 16.0, 16.0, 12.0)],
+        "floor" => return vec![block_box(4.0, 0.0, 0.0, 12.0, 16.0, 16.0)],
+        "ceiling" => block_box(7.0, 13.0, 7.0, 9.0, 16.0, 9.0),
+        "double_wall" if axis(facing) == "z" => block_box(7.0, 13.0, 0.0, 9.0, 15.0, 16.0),
+        "double_wall" => block_box(0.0, 13.0, 7.0, 16.0, 15.0, 9.0),
+        _ => match facing {
+            "north" => block_box(7.0, 13.0, 0.0, 9.0, 15.0, 13.0),
+            "south" => block_box(7.0, 13.0, 3.0, 9.0, 15.0, 16.0),
+            "east" => block_box(3.0, 13.0, 7.0, 16.0, 15.0, 9.0),
+            _ => block_box(0.0, 13.0, 7.0, 13.0, 15.0, 9.0),
+        },
+    };
+    vec![bell[0], bell[1], beam]
+}
+
+fn grindstone_boxes(face: &str, facing: &str) -> Vec<Aabb> {
+    let boxes: [(f64, f64, f64, f64, f64, f64); 5] = match (face, facing) {
+        ("floor", "north" | "south") => [(2.0, 0.0, 6.0, 4.0, 7.0, 10.0), (12.0, 0.0, 6.0, 14.0, 7.0, 10.0),
+            (2.0, 7.0, 5.0, 4.0, 13.0, 11.0), (12.0, 7.0, 5.0, 14.0, 13.0, 11.0), (4.0, 4.0, 2.0, 12.0, 16.0, 14.0)],
+        ("floor", _) => [(6.0, 0.0, 2.0, 10.0, 7.0, 4.0), (6.0, 0.0, 12.0, 10.0, 7.0, 14.0),
+            (5.0, 7.0, 2.0, 11.0, 13.0, 4.0), (5.0, 7.0, 12.0, 11.0, 13.0, 14.0), (2.0, 4.0, 4.0, 14.0, 16.0, 12.0)],
+        ("ceiling", "north" | "south") => [(2.0, 9.0, 6.0, 4.0, 16.0, 10.0), (12.0, 9.0, 6.0, 14.0, 16.0, 10.0),
+            (2.0, 3.0, 5.0, 4.0, 9.0, 11.0), (12.0, 3.0, 5.0, 14.0, 9.0, 11.0), (4.0, 0.0, 2.0, 12.0, 12.0, 14.0)],
+        ("ceiling", _) => [(6.0, 9.0, 2.0, 10.0, 16.0, 4.0), (6.0, 9.0, 12.0, 10.0, 16.0, 14.0),
+            (5.0, 3.0, 2.0, 11.0, 9.0, 4.0), (5.0, 3.0, 12.0, 11.0, 9.0, 14.0), (2.0, 0.0, 4.0, 14.0, 12.0, 12.0)],
+        (_, "south") => [(2.0, 6.0, 0.0, 4.0, 10.0, 7.0), (12.0, 6.0, 0.0, 14.0, 10.0, 7.0),
+            (2.0, 5.0, 7.0, 4.0, 11.0, 13.0), (12.0, 5.0, 7.0, 14.0, 11.0, 13.0), (4.0, 2.0, 4.0, 12.0, 14.0, 16.0)],
+        (_, "west") => [(7.0, 6.0, 2.0, 16.0, 10.0, 4.0), (7.0, 6.0, 12.0, 16.0, 10.0, 14.0),
+            (3.0, 5.0, 2.0, 9.0, 11.0, 4.0), (3.0, 5.0, 12.0, 9.0, 11.0, 14.0), (0.0, 2.0, 4.0, 12.0, 14.0, 12.0)],
+        (_, "east") => [(0.0, 6.0, 2.0, 9.0, 10.0, 4.0), (0.0, 6.0, 12.0, 9.0, 10.0, 14.0),
+            (7.0, 5.0, 2.0, 13.0, 11.0, 4.0), (7.0, 5.0, 12.0, 13.0, 11.0, 14.0), (4.0, 2.0, 4.0, 16.0, 14.0, 12.0)],
+        _ => [(2.0, 6.0, 7.0, 4.0, 10.0, 16.0), (12.0, 6.0, 7.0, 14.0, 10.0, 16.0),
+            (2.0, 5.0, 3.0, 4.0, 11.0, 9.0), (12.0, 5.0, 3.0, 14.0, 11.0, 9.0), (4.0, 2.0, 0.0, 12.0, 14.0, 12.0)],
+    };
+    boxes.iter().map(|b| block_box(b.0, b.1, b.2, b.3, b.4, b.5)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn boxes(name: &str, properties: &[(&str, &str)]) -> Vec<Aabb> {
+        collision_boxes(name, properties)
+    }
+
+    fn top(boxes: &[Aabb]) -> f64 {
+        boxes.iter().map(|block| block.max.y).fold(0.0, f64::max)
+    }
+
+    #[test]
+    fn fences_and_walls() {
+        let post = boxes("oak_fence", &[("north", "false"), ("east", "false"), ("south", "false"), ("west", "false")]);
+        assert_eq!(post, [block_box(6.0, 0.0, 6.0, 10.0, 24.0, 10.0)]);
+        let fence = boxes("nether_brick_fence", &[("north", "true"), ("east", "true"), ("south", "false"), ("west", "false")]);
+        assert_eq!(&fence[1..], [block_box(6.0, 0.0, 0.0, 10.0, 24.0, 10.0), block_box(6.0, 0.0, 6.0, 16.0, 24.0, 10.0)]);
+        assert_eq!(top(&fence), 1.5);
+
+        let wall = boxes("cobblestone_wall", &[("east", "none"), ("north", "low"), ("south", "tall"), ("up", "false"), ("west", "none")]);
+        assert_eq!(wall, [block_box(5.0, 0.0, 0.0, 11.0, 24.0, 11.0), block_box(5.0, 0.0, 5.0, 11.0, 24.0, 16.0)]);
+        let wall = boxes("cobblestone_wall", &[("east", "none"), ("north", "none"), ("south", "none"), ("up", "true"), ("west", "none")]);
+        assert_eq!(wall, [block_box(4.0, 0.0, 4.0, 12.0, 24.0, 12.0)]);
+
+        assert_eq!(boxes("oak_fence_gate", &[("facing", "east"), ("open", "false")]), [block_box(6.0, 0.0, 0.0, 10.0, 24.0, 16.0)]);
+        assert!(boxes("oak_fence_gate", &[("facing", "east"), ("open", "true")]).is_empty());
+        assert_eq!(boxes("glass_pane", &[("north", "false"), ("east", "false"), ("south", "false"), ("west", "true")]),
+            [block_box(7.0, 0.0, 7.0, 9.0, 16.0, 9.0), block_box(0.0, 0.0, 7.0, 9.0, 16.0, 9.0)]);
+    }
+
+    #[test]
+    fn stairs() {
+        let straight = boxes("oak_stairs", &[("facing", "north"), ("half", "bottom"), ("shape", "straight")]);
+        assert_eq!(straight, [block_box(0.0, 0.0, 0.0, 16.0, 8.0, 16.0), block_box(0.0, 8.0, 0.0, 16.0, 16.0, 8.0)]);
+        let upside_down = boxes("oak_stairs", &[("facing", "east"), ("half", "top"), ("shape", "straight")]);
+        assert_eq!(upside_down, [block_box(0.0, 8.0, 0.0, 16.0, 16.0, 16.0), block_box(8.0, 0.0, 0.0, 16.0, 8.0, 16.0)]);
+        let outer = boxes("oak_stairs", &[("facing", "north"), ("half", "bottom"), ("shape", "outer_left")]);
+        assert_eq!(outer[1], block_box(0.0, 8.0, 0.0, 8.0, 16.0, 8.0));
+        let inner = boxes("oak_stairs", &[("facing", "south"), ("half", "bottom"), ("shape", "inner_right")]);
+        assert_eq!(&inner[1..], [block_box(0.0, 8.0, 8.0, 16.0, 16.0, 16.0), block_box(0.0, 8.0, 0.0, 8.0, 16.0, 8.0)]);
+    }
+
+    #[test]
+    fn doors_and_trapdoors() {
+        let door = |facing: &str, open: &str, hinge: &str| boxes("oak_door", &[("facing", facing), ("half", "lower"), ("hinge", hinge), ("open", open), ("powered", "false")]);
+        assert_eq!(door("east", "false", "left"), [block_box(0.0, 0.0, 0.0, 3.0, 16.0, 16.0)]);
+        assert_eq!(door("east", "true", "right"), [block_box(0.0, 0.0, 13.0, 16.0, 16.0, 16.0)]);
+        assert_eq!(door("east", "true", "left"), [block_box(0.0, 0.0, 0.0, 16.0, 16.0, 3.0)]);
+        assert_eq!(door("north", "false", "left"), [block_box(0.0, 0.0, 13.0, 16.0, 16.0, 16.0)]);
+
+        let trapdoor = |facing: &str, half: &str, open: &str| boxes("iron_trapdoor", &[("facing", facing), ("half", half), ("open", open)]);
+        assert_eq!(trapdoor("north", "bottom", "false"), [block_box(0.0, 0.0, 0.0, 16.0, 3.0, 16.0)]);
+        assert_eq!(trapdoor("north", "top", "false"), [block_box(0.0, 13.0, 0.0, 16.0, 16.0, 16.0)]);
+        assert_eq!(trapdoor("north", "top", "true"), [block_box(0.0, 0.0, 13.0, 16.0, 16.0, 16.0)]);
+        assert_eq!(trapdoor("west", "bottom", "true"), [block_box(13.0, 0.0, 0.0, 16.0, 16.0, 16.0)]);
+        assert_eq!(boxes("ladder", &[("facing", "north")]), [block_box(0.0, 0.0, 13.0, 16.0, 16.0, 16.0)]);
+    }
+
+    #[test]
+    fn other_shapes() {
+        assert_eq!(boxes("chest", &[("facing", "north"), ("type", "single")]), [block_box(1.0, 0.0, 1.0, 15.0, 14.0, 15.0)]);
+        assert_eq!(boxes("chest", &[("facing", "north"), ("type", "left")]), [block_box(1.0, 0.0, 1.0, 16.0, 14.0, 15.0)]);
+        assert_eq!(boxes("trapped_chest", &[("facing", "north"), ("type", "right")]), [block_box(0.0, 0.0, 1.0, 15.0, 14.0, 15.0)]);
+
+        let bed = boxes("red_bed", &[("facing", "north"), ("occupied", "false"), ("part", "foot")]);
+        assert_eq!(bed, [block_box(0.0, 3.0, 0.0, 16.0, 9.0, 16.0), block_box(0.0, 0.0, 13.0, 3.0, 3.0, 16.0), block_box(13.0, 0.0, 13.0, 16.0, 3.0, 16.0)]);
+
+        assert_eq!(top(&boxes("lantern", &[("hanging", "false"), ("waterlogged", "false")])), 9.0 / 16.0);
+        assert_eq!(top(&boxes("soul_sand", &[])), 14.0 / 16.0);
+        assert_eq!(top(&boxes("farmland", &[("moisture", "7")])), 15.0 / 16.0);
+        assert_eq!(top(&boxes("honey_block", &[])), 15.0 / 16.0);
+        assert_eq!(top(&boxes("white_carpet", &[])), 1.0 / 16.0);
+        assert!(boxes("snow", &[("layers", "1")]).is_empty());
+        assert_eq!(top(&boxes("snow", &[("layers", "8")])), 14.0 / 16.0);
+        assert_eq!(boxes("cake", &[("bites", "3")]), [block_box(7.0, 0.0, 1.0, 15.0, 8.0, 15.0)]);
+        assert_eq!(boxes("candle", &[("candles", "3"), ("lit", "false"), ("waterlogged", "false")]), [block_box(5.0, 0.0, 6.0, 10.0, 6.0, 11.0)]);
+        assert_eq!(boxes("zombie_wall_head", &[("facing", "south")]), [block_box(4.0, 4.0, 0.0, 12.0, 12.0, 8.0)]);
+        assert_eq!(boxes("piston_head", &[("facing", "up"), ("short", "false"), ("type", "normal")]),
+            [block_box(0.0, 12.0, 0.0, 16.0, 16.0, 16.0), block_box(6.0, -4.0, 6.0, 10.0, 12.0, 10.0)]);
+        assert_eq!(boxes("piston", &[("extended", "true"), ("facing", "up")]), [block_box(0.0, 0.0, 0.0, 16.0, 12.0, 16.0)]);
+        assert_eq!(boxes("piston", &[("extended", "false"), ("facing", "up")]), [Aabb::FULL_BLOCK]);
+        assert_eq!(boxes("cauldron", &[]).len(), 13);
+        assert_eq!(boxes("stone", &[]), [Aabb::FULL_BLOCK]);
+        assert_eq!(boxes("oak_slab", &[("type", "top"), ("waterlogged", "false")]), [block_box(0.0, 8.0, 0.0, 16.0, 16.0, 16.0)]);
+    }
+}
@@ -0,0 +1,362 @@
+use miners_protocol::{packet::RawPacket, utils::{nbt::NBTType, location::BlockPos}};
+
+use super::palette::{DataReader, PalettedContainer, ContainerKind};
+
+/// Size of a single light array (one nibble per block of the section)
+pub const LIGHT_ARRAY_SIZE: usize = 2048;
+
+/// 16x16x16 part of the chunk
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChunkSection {
+    /// Number of non-air blocks
+    pub block_count: i16,
+    pub blocks: PalettedContainer,
+    pub biomes: PalettedContainer,
+}
+
+impl ChunkSection {
+    /// Creates section filled with air and the first biome
+    pub fn empty() -> ChunkSection {
+        ChunkSection {
+            block_count: 0,
+            blocks: PalettedContainer::single(ContainerKind::Blocks, 0),
+            biomes: PalettedContainer::single(ContainerKind::Biomes, 0),
+        }
+    }
+}
+
+/// Block entity (e.g. chest or sign) sent with the chunk
+#[derive(Debug, Clone)]
+pub struct BlockEntity {
+    pub position: BlockPos,
+    /// Id of the block entity type (from `minecraft:block_entity_type` registry)
+    pub type_id: i32,
+    pub nbt: Option<NBTType>,
+}
+
+/// Light of the chunk (arrays are `None` for sections without light data)
+///
+/// There is one more section below and above the world, so each vector has `sections + 2` entries
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ChunkLight {
+    pub sky_light: Vec<Option<Vec<u8>>>,
+    pub block_light: Vec<Option<Vec<u8>>>,
+}
+
+/// Column of sections from the bottom to the top of the world
+#[derive(Debug, Clone)]
+pub struct Chunk {
+    pub x: i32,
+    pub z: i32,
+    /// Lowest block Y coordinate of the chunk (from dimension type)
+    pub min_y: i32,
+    /// Heightmaps sent by the server (compound of packed long arrays)
+    pub heightmaps: Option<NBTType>,
+    pub sections: Vec<ChunkSection>,
+    pub block_entities: Vec<BlockEntity>,
+    pub light: ChunkLight,
+}
+
+impl Chunk {
+    /// Creates chunk filled with air
+    pub fn empty(x: i32, z: i32, min_y: i32, height: i32) -> Chunk {
+        let section_count = (height / 16).max(0) as usize;
+        Chunk {
+            x,
+            z,
+            min_y,
+            heightmaps: None,
+            sections: vec![ChunkSection::empty(); section_count],
+            block_entities: Vec::new(),
+            light: ChunkLight {
+                sky_light: vec![None; section_count + 2],
+                block_light: vec![None; section_count + 2],
+            },
+        }
+    }
+
+    /// Returns section containing the block at Y coordinate
+    fn section_index(&self, y: i32) -> Option<usize> {
+        let index = (y - self.min_y).div_euclid(16);
+        (index >= 0 && (index as usize) < self.sections.len()).then_some(index as usize)
+    }
+
+    /// Returns block state id at the position (coordinates are absolute, `None` if Y is out of the world)
+    pub fn get_block_state(&self, x: i32, y: i32, z: i32) -> Option<i32> {
+        let section = &self.sections[self.section_index(y)?];
+        Some(section.blocks.get(block_index(x, y, z)))
+    }
+
+    /// Sets block state id at the position, returns the previous one
+    pub fn set_block_state(&mut self, x: i32, y: i32, z: i32, state: i32) -> Option<i32> {
+        let index = self.section_index(y)?;
+        let section = &mut self.sections[index];
+        let old = section.blocks.set(block_index(x, y, z), state);
+        //? Only air counts as empty (cave and void air ids depend on the version)
+        match (old == 0, state == 0) {
+            (true, false) => section.block_count += 1,
+            (false, true) => section.block_count -= 1,
+            _ => {},
+        }
+        Some(old)
+    }
+
+    /// Returns biome id at the position (biomes are stored in 4x4x4 cells)
+    pub fn get_biome(&self, x: i32, y: i32, z: i32) -> Option<i32> {
+        let section = &self.sections[self.section_index(y)?];
+        let (x, y, z) = (x.rem_euclid(16) / 4, y.rem_euclid(16) / 4, z.rem_euclid(16) / 4);
+        Some(section.biomes.get((y * 16 + z * 4 + x) as usize))
+    }
+
+    /// Returns sky light level at the position (`None` if there is no light data)
+    pub fn get_sky_light(&self, x: i32, y: i32, z: i32) -> Option<u8> {
+        self.light_at(&self.light.sky_light, x, y, z)
+    }
+
+    /// Returns block light level at the position (`None` if there is no light data)
+    pub fn get_block_light(&self, x: i32, y: i32, z: i32) -> Option<u8> {
+        self.light_at(&self.light.block_light, x, y, z)
+    }
+
+    fn light_at(&self, arrays: &[Option<Vec<u8>>], x: i32, y: i32, z: i32) -> Option<u8> {
+        // Index 0 is the section below the world
+        let index = (y - self.min_y).div_euclid(16) + 1;
+        let array = arrays.get(usize::try_from(index).ok()?)?.as_ref()?;
+        let block = block_index(x, y, z);
+        let byte = array.get(block / 2)?;
+        Some(if block & 1 == 0 { byte & 0x0F } else { byte >> 4 })
+    }
+
+    /// Updates light of the chunk (only sections present in the data are changed)
+    pub fn apply_light(&mut self, data: LightData) {
+        let count = self.sections.len() + 2;
+        for (arrays, mask, empty_mask, updates) in [
+            (&mut self.light.sky_light, &data.sky_light_mask, &data.empty_sky_light_mask, data.sky_light),
+            (&mut self.light.block_light, &data.block_light_mask, &data.empty_block_light_mask, data.block_light),
+        ] {
+            arrays.resize(count, None);
+            let mut updates = updates.into_iter();
+            for (index, array) in arrays.iter_mut().enumerate() {
+                if is_bit_set(mask, index) {
+                    *array = updates.next();
+                } else if is_bit_set(empty_mask, index) {
+                    *array = Some(vec![0; LIGHT_ARRAY_SIZE]);
+                }
+            }
+        }
+    }
+}
+
+/// Returns index of the block in the section
+fn block_index(x: i32, y: i32, z: i32) -> usize {
+    (y.rem_euclid(16) * 256 + z.rem_euclid(16) * 16 + x.rem_euclid(16)) as usize
+}
+
+fn is_bit_set(bitset: &[i64], index: usize) -> bool {
+    bitset.get(index / 64).is_some_and(|long| long >> (index % 64) & 1 == 1)
+}
+
+/// Light data sent with chunks (0x21) and in update light packets (0x24)
+#[derive(Debug, Clone, Default)]
+pub struct LightData {
+    pub trust_edges: bool,
+    pub sky_light_mask: Vec<i64>,
+    pub block_light_mask: Vec<i64>,
+    pub empty_sky_light_mask: Vec<i64>,
+    pub empty_block_light_mask: Vec<i64>,
+    /// Arrays of sections set in `sky_light_mask` (from the lowest one)
+    pub sky_light: Vec<Vec<u8>>,
+    /// Arrays of sections set in `block_light_mask` (from the lowest one)
+    pub block_light: Vec<Vec<u8>>,
+}
+
+impl LightData {
+    pub(crate) fn read(packet: &mut RawPacket) -> LightData {
+        let trust_edges = packet.read_bool();
+        let sky_light_mask = packet.read_bitset();
+        let block_light_mask = packet.read_bitset();
+        let empty_sky_light_mask = packet.read_bitset();
+        let empty_block_light_mask = packet.read_bitset();
+        let sky_light_count = packet.read_varint();
+        let sky_light = (0..sky_light_count).map(|_| packet.read_byte_array()).collect();
+        let block_light_count = packet.read_varint();
+        let block_light = (0..block_light_count).map(|_| packet.read_byte_array()).collect();
+
+        LightData {
+            trust_edges,
+            sky_light_mask,
+            block_light_mask,
+            empty_sky_light_mask,
+            empty_block_light_mask,
+            sky_light,
+            block_light,
+        }
+    }
+}
+
+/// Represents chunk data and update light packet (0x21)
+#[derive(Debug, Clone)]
+pub struct ChunkDataPacket {
+    pub x: i32,
+    pub z: i32,
+    pub heightmaps: Option<NBTType>,
+    /// Raw section data (decoded with [`ChunkDataPacket::decode_sections`] once the world height is known)
+    pub data: Vec<u8>,
+    pub block_entities: Vec<BlockEntity>,
+    pub light: LightData,
+}
+
+impl ChunkDataPacket {
+    /// Decodes `section_count` sections from the chunk data (`None` if the data is malformed)
+    pub fn decode_sections(&self, section_count: usize) -> Option<Vec<ChunkSection>> {
+        let mut reader = DataReader::new(&self.data);
+        let mut sections = Vec::with_capacity(section_count);
+        for _ in 0..section_count {
+            if !reader.has_remaining() {
+                break;
+            }
+            sections.push(ChunkSection {
+                block_count: reader.read_short()?,
+                blocks: PalettedContainer::read(&mut reader, ContainerKind::Blocks)?,
+                biomes: PalettedContainer::read(&mut reader, ContainerKind::Biomes)?,
+            });
+        }
+        Some(sections)
+    }
+
+    /// Decodes the packet into chunk of the world with specified bounds
+    pub fn into_chunk(self, min_y: i32, height: i32) -> Option<Chunk> {
+        let mut chunk = Chunk::empty(self.x, self.z, min_y, height);
+        let sections = self.decode_sections(chunk.sections.len())?;
+        for (index, section) in sections.into_iter().enumerate() {
+            chunk.sections[index] = section;
+        }
+        chunk.heightmaps = self.heightmaps;
+        chunk.block_entities = self.block_entities;
+        chunk.apply_light(self.light);
+        Some(chunk)
+    }
+}
+
+impl From<RawPacket> for ChunkDataPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        let x = packet.read_int();
+        let z = packet.read_int();
+        let heightmaps = NBTType::from_packet(&mut packet)
+            .map_err(|e| log::warn!(target: "miners-client", "Failed to read heightmaps of chunk {} {}: {}", x, z, e))
+            .ok();
+        let data = packet.read_byte_array();
+
+        let block_entity_count = packet.read_varint();
+        let mut block_entities = Vec::new();
+        for _ in 0..block_entity_count {
+            let packed_xz = packet.read_byte();
+            let y = packet.read_short() as i32;
+            let type_id = packet.read_varint();
+            let nbt = NBTType::from_packet_optional(&mut packet).ok().flatten();
+            block_entities.push(BlockEntity {
+                position: BlockPos::new(x * 16 + (packed_xz >> 4) as i32, y, z * 16 + (packed_xz & 0x0F) as i32),
+                type_id,
+                nbt,
+            });
+        }
+
+        let light = LightData::read(&mut packet);
+
+        ChunkDataPacket {
+            x,
+            z,
+            heightmaps,
+            data,
+            block_entities,
+            light,
+        }
+    }
+}
+
+/// Represents update light packet (0x24)
+#[derive(Debug, Clone)]
+pub struct UpdateLightPacket {
+    pub x: i32,
+    pub z: i32,
+    pub light: LightData,
+}
+
+impl From<RawPacket> for UpdateLightPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        UpdateLightPacket {
+            x: packet.read_varint(),
+            z: packet.read_varint(),
+            light: LightData::read(&mut packet),
+        }
+    }
+}
+
+/// Represents unload chunk packet (0x1C)
+#[derive(Debug, Clone)]
+pub struct UnloadChunkPacket {
+    pub x: i32,
+    pub z: i32,
+}
+
+impl From<RawPacket> for UnloadChunkPacket {
+    fn from(mut packet: RawPacket) -> Self {
+        UnloadChunkPacket {
+            x: packet.read_int(),
+            z: packet.read_int(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn light_masks() {
+        // 4 sections (6 light sections including the ones below and above the world)
+        let mut chunk = Chunk::empty(0, 0, -64, 64);
+        let mut sky = vec![0; LIGHT_ARRAY_SIZE];
+        sky[0] = 0xF7; // (0, y, 0) = 7, (1, y, 0) = 15
+        chunk.apply_light(LightData {
+            // Sections 1 and 3 have data, section 2 is empty
+            sky_light_mask: vec![0b1010],
+            empty_sky_light_mask: vec![0b0100],
+            sky_light: vec![sky, vec![0x11; LIGHT_ARRAY_SIZE]],
+            ..Default::default()
+        });
+
+        assert_eq!(chunk.get_sky_light(0, -64, 0), Some(7));
+        assert_eq!(chunk.get_sky_light(1, -64, 0), Some(15));
+        assert_eq!(chunk.get_sky_light(0, -48, 0), Some(0));
+        assert_eq!(chunk.get_sky_light(5, -32, 5), Some(1));
+        assert_eq!(chunk.get_sky_light(0, -80, 0), None);
+        assert_eq!(chunk.get_sky_light(0, 0, 0), None);
+        assert_eq!(chunk.get_block_light(0, -64, 0), None);
+
+        // Sections missing in both masks keep their light
+        chunk.apply_light(LightData {
+            block_light_mask: vec![0b10],
+            block_light: vec![vec![0xFF; LIGHT_ARRAY_SIZE]],
+            ..Default::default()
+        });
+        assert_eq!(chunk.get_sky_light(1, -64, 0), Some(15));
+        assert_eq!(chunk.get_block_light(15, -49, 15), Some(15));
+    }
+
+    #[test]
+    fn light_malformed() {
+        let mut chunk = Chunk::empty(0, 0, 0, 16);
+        chunk.apply_light(LightData {
+            // Bits outside of the world and fewer arrays than set bits are ignored
+            sky_light_mask: vec![-1, -1],
+            sky_light: vec![vec![0x22; 16]],
+            ..Default::default()
+        });
+        assert_eq!(chunk.light.sky_light.len(), 3);
+        assert_eq!(chunk.get_sky_light(0, -16, 0), Some(2));
+        // Array is shorter than 2048 bytes
+        assert_eq!(chunk.get_sky_light(0, -1, 0), None);
+        assert_eq!(chunk.get_sky_light(0, 0, 0), None);
+    }
+}
\ No newline at end of file
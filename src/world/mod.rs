@@ -0,0 +1,365 @@
+//! Storage of chunks received from the server
+//!
+//! Chunks are kept separately for each dimension and decoded from paletted containers,
+//! so block states and biomes can be looked up by absolute coordinates
+
+use std::collections::HashMap;
+
+use miners_data::{ToolKind, ToolTier, Version};
+use miners_protocol::utils::location::{BlockPos, Vec3};
+
+use crate::{physics::{PhysicsWorld, Fluid, Tool, DEFAULT_FRICTION, aabb::Aabb}, registry::DimensionType};
+
+use self::chunk::Chunk;
+
+pub mod anvil;
+pub mod block_physics;
+pub mod chunk;
+pub mod palette;
+
+/// Physical properties of the block state
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockPhysics {
+    /// Collision boxes relative to the block origin
+    pub collision_boxes: Vec<Aabb>,
+    pub fluid: Option<Fluid>,
+    pub friction: f64,
+    pub climbable: bool,
+    /// Maximum horizontal offset of the collision boxes (thin blocks are moved depending on their position)
+    pub max_offset: f64,
+    /// Multiplier of the horizontal velocity of the player in or on the block (`None` if the block doesn't change it)
+    pub speed_factor: Option<f64>,
+    /// Multiplier of the jump velocity of the player in or on the block
+    pub jump_factor: f64,
+    /// Time needed to break the block (negative if it can't be broken, `None` if it's unknown)
+    pub hardness: Option<f32>,
+    /// Whether the block breaks slower without the correct tool
+    pub requires_tool: bool,
+    /// Tool which breaks the block faster
+    pub tool: Option<ToolKind>,
+    /// Minimum tier of the correct tool
+    pub tool_tier: Option<ToolTier>,
+}
+
+impl BlockPhysics {
+    /// Physics of air and other passable blocks
+    pub fn passable() -> BlockPhysics {
+        BlockPhysics {
+            collision_boxes: Vec::new(),
+            fluid: None,
+            friction: DEFAULT_FRICTION,
+            climbable: false,
+            max_offset: 0.0,
+            speed_factor: None,
+            jump_factor: 1.0,
+            hardness: None,
+            requires_tool: false,
+            tool: None,
+            tool_tier: None,
+        }
+    }
+
+    /// Physics of a full solid block (e.g. stone)
+    pub fn solid() -> BlockPhysics {
+        BlockPhysics {
+            collision_boxes: vec![Aabb::FULL_BLOCK],
+            ..BlockPhysics::passable()
+        }
+    }
+
+    /// Progress of breaking the block in a single tick (same as vanilla, 0.0 if the block can't be broken or its hardness is unknown)
+    pub fn destroy_progress(&self, tool: Option<&Tool>, eyes_in_water: bool, on_ground: bool) -> f32 {
+        let Some(hardness) = self.hardness.filter(|hardness| *hardness >= 0.0) else { return 0.0 };
+        let tool = tool.filter(|tool| Some(tool.kind) == self.tool);
+
+        let mut speed = tool.map_or(1.0, |tool| tool.tier.speed());
+        if let Some(tool) = tool.filter(|tool| tool.efficiency > 0 && speed > 1.0) {
+            speed += (tool.efficiency as u32 * tool.efficiency as u32 + 1) as f32;
+        }
+        if eyes_in_water {
+            speed /= 5.0;
+        }
+        if !on_ground {
+            speed /= 5.0;
+        }
+
+        // Blocks which require tool break slower without the correct one (or with lower tier)
+        let correct_tool = !self.requires_tool
+            || tool.is_some_and(|tool| self.tool_tier.is_none_or(|tier| tool.tier.level() >= tier.level()));
+        speed / hardness / if correct_tool { 30.0 } else { 100.0 }
+    }
+}
+
+/// Chunks loaded by the client
+///
+/// # Example
+/// ```rs
+/// let state = client.world().get_block_state(0, 64, 0);
+/// let biome = client.world().get_biome(0, 64, 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct World {
+    /// Chunks of each dimension (by dimension name and chunk position)
+    pub dimensions: HashMap<String, HashMap<(i32, i32), Chunk>>,
+    /// Name of the current dimension (e.g. `minecraft:overworld`)
+    pub dimension: String,
+    /// Lowest block Y coordinate of the current dimension
+    pub min_y: i32,
+    /// Height of the current dimension (in blocks)
+    pub height: i32,
+    /// Version of the server (`None` if there is no data for it)
+    pub version: Option<Version>,
+    /// Names of biomes by their network ids (from `minecraft:worldgen/biome` registry)
+    pub biome_names: HashMap<i32, String>,
+    /// Physics of block states used when simulating movement in the world (`None` if the state is unknown)
+    pub block_physics: fn(Version, i32) -> Option<BlockPhysics>,
+    /// Blocks changed by the client which weren't acknowledged yet (sequence and state sent by the server)
+    pub(crate) predictions: HashMap<BlockPos, (i32, i32)>,
+}
+
+impl Default for World {
+    fn default() -> Self {
+        World {
+            dimensions: HashMap::new(),
+            dimension: String::new(),
+            // Bounds of the overworld
+            min_y: -64,
+            height: 384,
+            version: None,
+            biome_names: HashMap::new(),
+            block_physics: block_physics::data_block_physics,
+            predictions: HashMap::new(),
+        }
+    }
+}
+
+impl World {
+    /// Switches to another dimension (chunks of other dimensions are kept)
+    pub fn set_dimension(&mut self, name: &str, dimension_type: Option<&DimensionType>) {
+        self.dimension = name.to_string();
+        self.predictions.clear();
+        if let Some(dimension_type) = dimension_type {
+            self.min_y = dimension_type.min_y;
+            self.height = dimension_type.height;
+        }
+    }
+
+    /// Returns chunks of the current dimension
+    pub fn chunks(&self) -> impl Iterator<Item = &Chunk> {
+        self.dimensions.get(&self.dimension).into_iter().flat_map(|chunks| chunks.values())
+    }
+
+    /// Returns chunk at the chunk position (in the current dimension)
+    pub fn get_chunk(&self, x: i32, z: i32) -> Option<&Chunk> {
+        self.dimensions.get(&self.dimension)?.get(&(x, z))
+    }
+
+    /// Returns mutable chunk at the chunk position (in the current dimension)
+    pub fn get_chunk_mut(&mut self, x: i32, z: i32) -> Option<&mut Chunk> {
+        self.dimensions.get_mut(&self.dimension)?.get_mut(&(x, z))
+    }
+
+    /// Returns chunk containing the block
+    pub fn chunk_at(&self, x: i32, z: i32) -> Option<&Chunk> {
+        self.get_chunk(x.div_euclid(16), z.div_euclid(16))
+    }
+
+    /// Adds chunk to the current dimension (replacing the previous one)
+    pub fn insert_chunk(&mut self, chunk: Chunk) {
+        self.dimensions.entry(self.dimension.clone()).or_default().insert((chunk.x, chunk.z), chunk);
+    }
+
+    /// Removes chunk from the current dimension
+    pub fn unload_chunk(&mut self, x: i32, z: i32) -> Option<Chunk> {
+        self.predictions.retain(|pos, _| (pos.x.div_euclid(16), pos.z.div_euclid(16)) != (x, z));
+        self.dimensions.get_mut(&self.dimension)?.remove(&(x, z))
+    }
+
+    /// Whether the chunk containing the block is loaded
+    pub fn is_chunk_loaded(&self, x: i32, z: i32) -> bool {
+        self.chunk_at(x, z).is_some()
+    }
+
+    /// Returns block state id at the position (`None` if the chunk isn't loaded or Y is out of the world)
+    pub fn get_block_state(&self, x: i32, y: i32, z: i32) -> Option<i32> {
+        self.chunk_at(x, z)?.get_block_state(x, y, z)
+    }
+
+    /// Sets block state id at the position, returns the previous one (`None` if the chunk isn't loaded)
+    pub fn set_block_state(&mut self, x: i32, y: i32, z: i32, state: i32) -> Option<i32> {
+        self.get_chunk_mut(x.div_euclid(16), z.div_euclid(16))?.set_block_state(x, y, z, state)
+    }
+
+    /// Changes block locally before the server confirms it (e.g. when the block is broken by the client)
+    ///
+    /// State sent by the server is restored once the action with `sequence` is acknowledged.
+    /// Returns the previous state (`None` if the chunk isn't loaded).
+    pub fn predict_block_state(&mut self, pos: BlockPos, state: i32, sequence: i32) -> Option<i32> {
+        let old = self.set_block_state(pos.x, pos.y, pos.z, state)?;
+        // Keep the state sent by the server if the block was already predicted
+        let server_state = self.predictions.get(&pos).map_or(old, |(_, server_state)| *server_state);
+        self.predictions.insert(pos, (sequence, server_state));
+        Some(old)
+    }
+
+    /// Applies block change sent by the server, returns the previous state if the block changed
+    ///
+    /// Changes of predicted blocks are deferred until the prediction is acknowledged
+    pub fn apply_block_update(&mut self, pos: BlockPos, state: i32) -> Option<i32> {
+        if let Some((_, server_state)) = self.predictions.get_mut(&pos) {
+            *server_state = state;
+            return None;
+        }
+        self.set_block_state(pos.x, pos.y, pos.z, state).filter(|old| *old != state)
+    }
+
+    /// Acknowledges predictions up to `sequence`, restoring states sent by the server
+    ///
+    /// Returns changed blocks with their previous and new states
+    pub fn acknowledge(&mut self, sequence: i32) -> Vec<(BlockPos, i32, i32)> {
+        let acknowledged = self.predictions.iter()
+            .filter(|(_, (predicted_sequence, _))| *predicted_sequence <= sequence)
+            .map(|(pos, (_, server_state))| (*pos, *server_state))
+            .collect::<Vec<(BlockPos, i32)>>();
+
+        let mut changes = Vec::new();
+        for (pos, state) in acknowledged {
+            self.predictions.remove(&pos);
+            if let Some(old) = self.apply_block_update(pos, state) {
+                changes.push((pos, old, state));
+            }
+        }
+        changes
+    }
+
+    /// Returns biome id at the position (from `minecraft:worldgen/biome` registry)
+    pub fn get_biome(&self, x: i32, y: i32, z: i32) -> Option<i32> {
+        self.chunk_at(x, z)?.get_biome(x, y, z)
+    }
+
+    /// Returns sky light level at the position
+    pub fn get_sky_light(&self, x: i32, y: i32, z: i32) -> Option<u8> {
+        self.chunk_at(x, z)?.get_sky_light(x, y, z)
+    }
+
+    /// Returns block light level at the position
+    pub fn get_block_light(&self, x: i32, y: i32, z: i32) -> Option<u8> {
+        self.chunk_at(x, z)?.get_block_light(x, y, z)
+    }
+
+    /// Returns block state at the position using data of the server version
+    pub fn get_block(&self, x: i32, y: i32, z: i32) -> Option<miners_data::BlockState> {
+        miners_data::BlockState::from_id(self.version?, self.get_block_state(x, y, z)?)
+    }
+
+    /// Returns physics of the block at the position (air if there is no block)
+    ///
+    /// Returns None if the physics of the block is unknown (there is no data for the server version)
+    pub fn block_physics_at(&self, pos: BlockPos) -> Option<BlockPhysics> {
+        let Some(state) = self.get_block_state(pos.x, pos.y, pos.z) else {
+            return Some(BlockPhysics::passable());
+        };
+        let mut physics = (self.block_physics)(self.version?, state)?;
+        if physics.max_offset > 0.0 {
+            let offset = block_offset(pos, physics.max_offset);
+            physics.collision_boxes = physics.collision_boxes.iter().map(|block| block.offset(offset)).collect();
+        }
+        Some(physics)
+    }
+}
+
+/// Returns horizontal offset of the block at the position (same as vanilla)
+fn block_offset(pos: BlockPos, max_offset: f64) -> Vec3 {
+    let mut seed = (pos.x.wrapping_mul(3129871) as i64) ^ (pos.z as i64).wrapping_mul(116129781);
+    seed = seed.wrapping_mul(seed).wrapping_mul(42317861).wrapping_add(seed.wrapping_mul(11));
+    seed >>= 16;
+    let offset = |bits: i64| (((bits & 15) as f32 / 15.0) as f64 - 0.5) * 0.5;
+    Vec3::new(offset(seed).clamp(-max_offset, max_offset), 0.0, offset(seed >> 8).clamp(-max_offset, max_offset))
+}
+
+/// Blocks with unknown physics count as not loaded, so the player doesn't move
+/// and the pathfinder doesn't walk through them instead of guessing their collisions
+impl PhysicsWorld for World {
+    fn is_loaded(&self, pos: BlockPos) -> bool {
+        self.is_chunk_loaded(pos.x, pos.z) && self.block_physics_at(pos).is_some()
+    }
+
+    fn collision_boxes(&self, pos: BlockPos) -> Vec<Aabb> {
+        self.block_physics_at(pos).map(|physics| physics.collision_boxes).unwrap_or_default()
+    }
+
+    fn fluid(&self, pos: BlockPos) -> Option<Fluid> {
+        self.block_physics_at(pos)?.fluid
+    }
+
+    fn friction(&self, pos: BlockPos) -> f64 {
+        self.block_physics_at(pos).map_or(DEFAULT_FRICTION, |physics| physics.friction)
+    }
+
+    fn is_climbable(&self, pos: BlockPos) -> bool {
+        self.block_physics_at(pos).is_some_and(|physics| physics.climbable)
+    }
+
+    fn speed_factor(&self, pos: BlockPos) -> Option<f64> {
+        self.block_physics_at(pos)?.speed_factor
+    }
+
+    fn jump_factor(&self, pos: BlockPos) -> f64 {
+        self.block_physics_at(pos).map_or(1.0, |physics| physics.jump_factor)
+    }
+
+    fn destroy_progress(&self, pos: BlockPos, tool: Option<&Tool>, eyes_in_water: bool, on_ground: bool) -> f32 {
+        self.block_physics_at(pos).map_or(0.0, |physics| physics.destroy_progress(tool, eyes_in_water, on_ground))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_offsets() {
+        assert_eq!(block_offset(BlockPos::new(0, 64, 0), 0.125), Vec3::new(-0.125, 0.0, -0.125));
+        for x in -20..20 {
+            for z in -20..20 {
+                let offset = block_offset(BlockPos::new(x, 0, z), 0.125);
+                assert!(offset.x.abs() <= 0.125 && offset.z.abs() <= 0.125 && offset.y == 0.0);
+                assert_eq!(offset, block_offset(BlockPos::new(x, 100, z), 0.125));
+            }
+        }
+    }
+
+    #[test]
+    fn destroy_progress() {
+        let stone = BlockPhysics {
+            hardness: Some(1.5),
+            requires_tool: true,
+            tool: Some(ToolKind::Pickaxe),
+            ..BlockPhysics::solid()
+        };
+        let tool = |kind, tier, efficiency| Some(Tool { kind, tier, efficiency });
+        let ticks = |physics: &BlockPhysics, tool: Option<Tool>, eyes_in_water, on_ground| {
+            (1.0 / physics.destroy_progress(tool.as_ref(), eyes_in_water, on_ground)).ceil()
+        };
+        assert_eq!(ticks(&stone, None, false, true), 150.0);
+        assert_eq!(ticks(&stone, tool(ToolKind::Axe, ToolTier::Diamond, 5), false, true), 150.0);
+        assert_eq!(ticks(&stone, tool(ToolKind::Pickaxe, ToolTier::Wood, 0), false, true), 23.0);
+        assert_eq!(ticks(&stone, tool(ToolKind::Pickaxe, ToolTier::Diamond, 0), false, true), 6.0);
+        assert_eq!(ticks(&stone, tool(ToolKind::Pickaxe, ToolTier::Diamond, 5), false, true), 2.0);
+        assert_eq!(ticks(&stone, tool(ToolKind::Pickaxe, ToolTier::Wood, 0), true, false), 563.0);
+
+        // Tier of the tool is too low
+        let iron_ore = BlockPhysics { hardness: Some(3.0), tool_tier: Some(ToolTier::Stone), ..stone.clone() };
+        assert_eq!(ticks(&iron_ore, tool(ToolKind::Pickaxe, ToolTier::Wood, 0), false, true), 150.0);
+        assert_eq!(ticks(&iron_ore, tool(ToolKind::Pickaxe, ToolTier::Stone, 0), false, true), 23.0);
+
+        let dirt = BlockPhysics { hardness: Some(0.5), requires_tool: false, tool: Some(ToolKind::Shovel), ..BlockPhysics::solid() };
+        assert_eq!(ticks(&dirt, None, false, true), 15.0);
+        let grass = BlockPhysics { hardness: Some(0.0), ..BlockPhysics::passable() };
+        assert!(grass.destroy_progress(None, false, true) >= 1.0);
+
+        let bedrock = BlockPhysics { hardness: Some(-1.0), ..BlockPhysics::solid() };
+        assert_eq!(bedrock.destroy_progress(tool(ToolKind::Pickaxe, ToolTier::Netherite, 5).as_ref(), false, true), 0.0);
+        assert_eq!(BlockPhysics::solid().destroy_progress(None, false, true), 0.0);
+    }
+}
\ No newline at end of file
@@ -0,0 +1,373 @@
+/// Reader of raw chunk data (reading it through `RawPacket` byte by byte would be too slow)
+pub(crate) struct DataReader<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl<'a> DataReader<'a> {
+    pub fn new(data: &'a [u8]) -> DataReader<'a> {
+        DataReader { data, position: 0 }
+    }
+
+    pub fn has_remaining(&self) -> bool {
+        self.position < self.data.len()
+    }
+
+    pub fn read_byte(&mut self) -> Option<u8> {
+        let byte = *self.data.get(self.position)?;
+        self.position += 1;
+        Some(byte)
+    }
+
+    pub fn read_short(&mut self) -> Option<i16> {
+        Some(i16::from_be_bytes([self.read_byte()?, self.read_byte()?]))
+    }
+
+    pub fn read_long(&mut self) -> Option<u64> {
+        let bytes = self.data.get(self.position..self.position + 8)?;
+        self.position += 8;
+        Some(u64::from_be_bytes(bytes.try_into().ok()?))
+    }
+
+    pub fn read_varint(&mut self) -> Option<i32> {
+        let mut result = 0;
+        for i in 0..5 {
+            let byte = self.read_byte()?;
+            result |= ((byte & 0x7F) as i32) << (7 * i);
+            if byte & 0x80 == 0 {
+                return Some(result);
+            }
+        }
+        None
+    }
+}
+
+/// Kind of the paletted container (determines its size and palette limits)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerKind {
+    /// 16x16x16 block states
+    Blocks,
+    /// 4x4x4 biomes
+    Biomes,
+}
+
+impl ContainerKind {
+    /// Number of entries in the container
+    pub fn size(&self) -> usize {
+        match self {
+            ContainerKind::Blocks => 4096,
+            ContainerKind::Biomes => 64,
+        }
+    }
+
+    fn min_indirect_bits(&self) -> u8 {
+        match self {
+            ContainerKind::Blocks => 4,
+            ContainerKind::Biomes => 1,
+        }
+    }
+
+    fn max_indirect_bits(&self) -> u8 {
+        match self {
+            ContainerKind::Blocks => 8,
+            ContainerKind::Biomes => 3,
+        }
+    }
+
+    /// Bits per entry of direct palette (enough for all block states and biomes of supported versions)
+    fn direct_bits(&self) -> u8 {
+        match self {
+            ContainerKind::Blocks => 15,
+            ContainerKind::Biomes => 6,
+        }
+    }
+}
+
+/// Palette mapping values stored in the container to global ids
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Palette {
+    /// Whole container holds a single value
+    SingleValue(i32),
+    /// Stored values are indexes into the palette
+    Indirect(Vec<i32>),
+    /// Stored values are global ids
+    Direct,
+}
+
+/// Container of block states or biomes stored as packed long array with palette
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PalettedContainer {
+    pub kind: ContainerKind,
+    pub bits_per_entry: u8,
+    pub palette: Palette,
+    /// Packed entries (entries don't span multiple longs)
+    pub data: Vec<u64>,
+}
+
+impl PalettedContainer {
+    /// Creates container filled with a single value
+    pub fn single(kind: ContainerKind, value: i32) -> PalettedContainer {
+        PalettedContainer {
+            kind,
+            bits_per_entry: 0,
+            palette: Palette::SingleValue(value),
+            data: Vec::new(),
+        }
+    }
+
+    /// Reads container from chunk data
+    pub(crate) fn read(reader: &mut DataReader, kind: ContainerKind) -> Option<PalettedContainer> {
+        let mut bits_per_entry = reader.read_byte()?;
+        // Entries never have more than 32 bits (larger values would also break packing math)
+        if bits_per_entry > 32 {
+            return None;
+        }
+
+        let palette = if bits_per_entry == 0 {
+            Palette::SingleValue(reader.read_varint()?)
+        } else if bits_per_entry <= kind.max_indirect_bits() {
+            // Smaller indirect palettes use the minimal size (same as vanilla)
+            bits_per_entry = bits_per_entry.max(kind.min_indirect_bits());
+            let length = reader.read_varint()?;
+            Palette::Indirect((0..length).map(|_| reader.read_varint()).collect::<Option<Vec<i32>>>()?)
+        } else {
+            Palette::Direct
+        };
+
+        let length = reader.read_varint()?;
+        let data = (0..length).map(|_| reader.read_long()).collect::<Option<Vec<u64>>>()?;
+
+        Some(PalettedContainer {
+            kind,
+            bits_per_entry,
+            palette,
+            data,
+        })
+    }
+
+    /// Creates container from global ids (choosing the smallest palette)
+    pub fn from_values(kind: ContainerKind, values: &[i32]) -> PalettedContainer {
+        let mut distinct = Vec::new();
+        for value in values {
+            if !distinct.contains(value) {
+                distinct.push(*value);
+            }
+        }
+
+        if distinct.len() <= 1 {
+            return PalettedContainer::single(kind, distinct.first().copied().unwrap_or_default());
+        }
+
+        let needed_bits = (usize::BITS - (distinct.len() - 1).leading_zeros()) as u8;
+        let (bits_per_entry, palette) = if needed_bits <= kind.max_indirect_bits() {
+            (needed_bits.max(kind.min_indirect_bits()), Palette::Indirect(distinct))
+        } else {
+            let max_value = values.iter().copied().max().unwrap_or_default().max(0) as u32;
+            let value_bits = (u32::BITS - max_value.leading_zeros()) as u8;
+            (kind.direct_bits().max(value_bits), Palette::Direct)
+        };
+
+        let mut container = PalettedContainer {
+            kind,
+            bits_per_entry,
+            palette,
+            data: vec![0; kind.size().div_ceil(64 / bits_per_entry as usize)],
+        };
+        for (index, value) in values.iter().enumerate() {
+            let stored = match &container.palette {
+                Palette::Indirect(palette) => palette.iter().position(|v| v == value).unwrap_or_default() as u64,
+                _ => *value as u64,
+            };
+            container.write_raw(index, stored);
+        }
+        container
+    }
+
+    /// Returns global id at the index (`y * 256 + z * 16 + x` for blocks, `y * 16 + z * 4 + x` for biomes)
+    pub fn get(&self, index: usize) -> i32 {
+        match &self.palette {
+            Palette::SingleValue(value) => *value,
+            Palette::Indirect(palette) => palette.get(self.read_raw(index) as usize).copied().unwrap_or_default(),
+            Palette::Direct => self.read_raw(index) as i32,
+        }
+    }
+
+    /// Sets global id at the index, returns the previous one
+    pub fn set(&mut self, index: usize, value: i32) -> i32 {
+        let old = self.get(index);
+        if old == value {
+            return old;
+        }
+
+        let stored = match &mut self.palette {
+            Palette::SingleValue(_) => None,
+            Palette::Indirect(palette) => match palette.iter().position(|v| *v == value) {
+                Some(position) => Some(position as u64),
+                None if palette.len() < 1 << self.bits_per_entry => {
+                    palette.push(value);
+                    Some(palette.len() as u64 - 1)
+                },
+                None => None,
+            },
+            Palette::Direct if (value as u64) < 1 << self.bits_per_entry => Some(value as u64),
+            Palette::Direct => None,
+        };
+
+        match stored {
+            Some(stored) => self.write_raw(index, stored),
+            None => {
+                // Palette is full, so the container is rebuilt with more bits per entry
+                let mut values = self.values();
+                values[index] = value;
+                *self = PalettedContainer::from_values(self.kind, &values);
+            }
+        }
+        old
+    }
+
+    /// Returns all global ids in the container
+    pub fn values(&self) -> Vec<i32> {
+        (0..self.kind.size()).map(|index| self.get(index)).collect()
+    }
+
+    fn read_raw(&self, index: usize) -> u64 {
+        if self.bits_per_entry == 0 {
+            return 0;
+        }
+        let bits = self.bits_per_entry as usize;
+        let per_long = 64 / bits;
+        let long = self.data.get(index / per_long).copied().unwrap_or_default();
+        (long >> ((index % per_long) * bits)) & ((1u64 << bits) - 1)
+    }
+
+    fn write_raw(&mut self, index: usize, value: u64) {
+        let bits = self.bits_per_entry as usize;
+        let per_long = 64 / bits;
+        let shift = (index % per_long) * bits;
+        let mask = ((1u64 << bits) - 1) << shift;
+        if let Some(long) = self.data.get_mut(index / per_long) {
+            *long = (*long & !mask) | ((value << shift) & mask);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Serializes container the same way as the chunk data packet
+    fn encode(container: &PalettedContainer) -> Vec<u8> {
+        let mut packet = miners_protocol::packet::RawPacket::empty(0);
+        packet.write_byte(container.bits_per_entry);
+        match &container.palette {
+            Palette::SingleValue(value) => packet.write_varint(*value),
+            Palette::Indirect(palette) => {
+                packet.write_varint(palette.len() as i32);
+                palette.iter().for_each(|value| packet.write_varint(*value));
+            },
+            Palette::Direct => {},
+        }
+        packet.write_varint(container.data.len() as i32);
+        container.data.iter().for_each(|long| packet.write_ulong(*long));
+        packet.data
+    }
+
+    #[test]
+    fn pack_unpack() {
+        let mut container = PalettedContainer {
+            kind: ContainerKind::Blocks,
+            bits_per_entry: 5,
+            palette: Palette::Direct,
+            data: vec![0; 4096usize.div_ceil(12)],
+        };
+        for index in 0..4096 {
+            container.write_raw(index, (index % 32) as u64);
+        }
+        // Entries don't span multiple longs (12 entries of 5 bits per long, 4 bits are unused)
+        assert_eq!(container.data[0] >> 60, 0);
+        for index in 0..4096 {
+            assert_eq!(container.read_raw(index), (index % 32) as u64);
+        }
+    }
+
+    #[test]
+    fn from_values_round_trip() {
+        for (kind, values) in [
+            (ContainerKind::Blocks, vec![7; 4096]),
+            (ContainerKind::Blocks, (0..4096).map(|i| i % 3).collect()),
+            (ContainerKind::Blocks, (0..4096).map(|i| i % 300).collect()),
+            (ContainerKind::Blocks, (0..4096).map(|i| i * 7).collect()),
+            (ContainerKind::Biomes, (0..64).map(|i| i % 5).collect()),
+            (ContainerKind::Biomes, (0..64).collect()),
+        ] {
+            let container = PalettedContainer::from_values(kind, &values);
+            assert_eq!(container.values(), values);
+
+            let data = encode(&container);
+            let read = PalettedContainer::read(&mut DataReader::new(&data), kind).unwrap();
+            assert_eq!(read, container);
+        }
+    }
+
+    #[test]
+    fn palette_kinds() {
+        let single = PalettedContainer::from_values(ContainerKind::Blocks, &[1; 4096]);
+        assert_eq!((single.bits_per_entry, &single.palette), (0, &Palette::SingleValue(1)));
+
+        let indirect = PalettedContainer::from_values(ContainerKind::Blocks, &(0..4096).map(|i| i % 2).collect::<Vec<i32>>());
+        assert_eq!((indirect.bits_per_entry, indirect.palette), (4, Palette::Indirect(vec![0, 1])));
+
+        let direct = PalettedContainer::from_values(ContainerKind::Blocks, &(0..4096).collect::<Vec<i32>>());
+        assert_eq!((direct.bits_per_entry, direct.palette), (15, Palette::Direct));
+    }
+
+    #[test]
+    fn set_grows_palette() {
+        let mut container = PalettedContainer::single(ContainerKind::Blocks, 0);
+        for index in 0..4096 {
+            container.set(index, index as i32 % 20);
+        }
+        assert_eq!(container.bits_per_entry, 5);
+        assert_eq!(container.values(), (0..4096).map(|i| i % 20).collect::<Vec<i32>>());
+
+        assert_eq!(container.set(5, 1000), 5);
+        assert_eq!(container.bits_per_entry, 5);
+        assert_eq!(container.get(5), 1000);
+
+        // More than 256 distinct values don't fit into indirect palette
+        for index in 0..300 {
+            container.set(index, index as i32 + 2000);
+        }
+        assert_eq!(container.palette, Palette::Direct);
+        assert_eq!(container.get(299), 2299);
+        assert_eq!(container.get(300), 0);
+    }
+
+    #[test]
+    fn read_clamps_indirect_bits() {
+        // Blocks with 1 bit per entry are read with the minimal indirect size (4 bits, same as vanilla)
+        let mut data = vec![1, 2, 10, 20, 64];
+        let mut long = 0u64;
+        for index in 0..16 {
+            long |= ((index % 2) as u64) << (index * 4);
+        }
+        for i in 0..64 {
+            data.extend_from_slice(&if i == 0 { long } else { 0 }.to_be_bytes());
+        }
+        let container = PalettedContainer::read(&mut DataReader::new(&data), ContainerKind::Blocks).unwrap();
+        assert_eq!(container.bits_per_entry, 4);
+        assert_eq!(container.get(1), 20);
+        assert_eq!(container.get(2), 10);
+    }
+
+    #[test]
+    fn read_malformed() {
+        // More than 32 bits per entry
+        assert_eq!(PalettedContainer::read(&mut DataReader::new(&[33, 0]), ContainerKind::Blocks), None);
+        assert_eq!(PalettedContainer::read(&mut DataReader::new(&[255, 0]), ContainerKind::Biomes), None);
+        // Truncated palette and data
+        assert_eq!(PalettedContainer::read(&mut DataReader::new(&[4, 3, 1]), ContainerKind::Blocks), None);
+        assert_eq!(PalettedContainer::read(&mut DataReader::new(&[15, 2, 0, 0, 0]), ContainerKind::Blocks), None);
+        assert_eq!(PalettedContainer::read(&mut DataReader::new(&[]), ContainerKind::Blocks), None);
+    }
+}
\ No newline at end of file